@@ -0,0 +1,60 @@
+// Approximate equality. Float areas pick up rounding error (π, square roots, dimensions
+// like 0.1 that have no exact binary form), so two computations of the "same" area
+// rarely compare equal with `==`. Instead, values count as equal when they are within
+// `epsilon` of each other.
+
+// The tolerance used by `assert_area_eq!` when none is given.
+pub const DEFAULT_EPSILON: f64 = 1e-9;
+
+pub trait ApproxEq {
+    fn approx_eq(&self, other: &Self, epsilon: f64) -> bool;
+}
+
+impl ApproxEq for f64 {
+    fn approx_eq(&self, other: &Self, epsilon: f64) -> bool {
+        (self - other).abs() <= epsilon
+    }
+}
+
+// Like `assert_eq!`, but compares with `ApproxEq`. Works on areas (`f64`) and on shapes;
+// the epsilon is optional and defaults to `DEFAULT_EPSILON`. Only the tests use it.
+#[cfg(test)]
+macro_rules! assert_area_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        assert_area_eq!($left, $right, $crate::approx::DEFAULT_EPSILON)
+    };
+    ($left:expr, $right:expr, $epsilon:expr $(,)?) => {{
+        let (left, right, epsilon) = (&$left, &$right, $epsilon);
+        assert!(
+            $crate::approx::ApproxEq::approx_eq(left, right, epsilon),
+            "assertion `left ≈ right` failed (epsilon {:e})\n  left: {:?}\n right: {:?}",
+            epsilon,
+            left,
+            right
+        );
+    }};
+}
+
+// `macro_rules!` macros are only visible below their definition; re-exporting lets other
+// modules import this one by path like any other item.
+#[cfg(test)]
+pub(crate) use assert_area_eq;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_within_epsilon_are_equal() {
+        assert!(1.0.approx_eq(&(1.0 + 1e-10), DEFAULT_EPSILON));
+        assert!(!1.0.approx_eq(&1.1, DEFAULT_EPSILON));
+        assert!(0.1.approx_eq(&(0.3 - 0.2), 1e-12));
+        assert_area_eq!(0.1 + 0.2, 0.3);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion `left ≈ right` failed")]
+    fn assert_area_eq_panics_outside_epsilon() {
+        assert_area_eq!(1.0, 1.5, 0.1);
+    }
+}
@@ -0,0 +1,232 @@
+// A compact binary format for shapes, without any dependencies. Each shape is a one-byte
+// tag followed by its dimensions as little-endian `f64`s:
+//
+//   tag 1: rectangle, width and length (17 bytes)
+//   tag 2: circle, radius (9 bytes)
+//   tag 3: square, side (9 bytes)
+//   tag 4: triangle, sides a, b and c (25 bytes)
+//
+// A sequence written by `encode_all` starts with the number of shapes as a little-endian
+// `u32`, so the decoder knows where the data is supposed to end.
+use std::fmt;
+
+use crate::kind::ShapeKind;
+use crate::shapes::{Circle, Rectangle, ShapeError, Square, Triangle};
+
+const RECT_TAG: u8 = 1;
+const CIRCLE_TAG: u8 = 2;
+const SQUARE_TAG: u8 = 3;
+const TRIANGLE_TAG: u8 = 4;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodeError {
+    // The input ended in the middle of a shape (or of the count), `needed` bytes short.
+    Truncated { needed: usize },
+    // The byte at `offset` isn't one of the shape tags.
+    UnknownTag { tag: u8, offset: usize },
+    // All shapes were decoded, but `count` more bytes follow them.
+    TrailingBytes { count: usize },
+    // The dimensions were read, but the shape's constructor rejects them.
+    InvalidShape(ShapeError),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::Truncated { needed } => {
+                write!(f, "input ends {} byte(s) too early", needed)
+            }
+            DecodeError::UnknownTag { tag, offset } => {
+                write!(f, "unknown shape tag {} at byte {}", tag, offset)
+            }
+            DecodeError::TrailingBytes { count } => {
+                write!(f, "{} unexpected byte(s) after the last shape", count)
+            }
+            DecodeError::InvalidShape(err) => write!(f, "invalid shape: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl From<ShapeError> for DecodeError {
+    fn from(err: ShapeError) -> Self {
+        DecodeError::InvalidShape(err)
+    }
+}
+
+impl ShapeKind {
+    // Append the tag and the dimensions of this shape to `out`.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        let (tag, fields): (u8, &[f64]) = match self {
+            ShapeKind::Rect(rect) => (RECT_TAG, &[rect.width(), rect.length()]),
+            ShapeKind::Circ(circle) => (CIRCLE_TAG, &[circle.radius()]),
+            ShapeKind::Sq(square) => (SQUARE_TAG, &[square.side()]),
+            ShapeKind::Tri(triangle) => {
+                let (a, b, c) = triangle.sides();
+                (TRIANGLE_TAG, &[a, b, c])
+            }
+        };
+        out.push(tag);
+        for field in fields {
+            out.extend_from_slice(&field.to_le_bytes());
+        }
+    }
+
+    // Decode the shape at the start of `bytes`, returning it together with the number of
+    // bytes it took up. Anything after that is left alone, so a caller can keep decoding.
+    // The dimensions go through the validating constructors, like any other input.
+    pub fn decode(bytes: &[u8]) -> Result<(ShapeKind, usize), DecodeError> {
+        let (&tag, rest) = bytes.split_first().ok_or(DecodeError::Truncated { needed: 1 })?;
+        let shape = match tag {
+            RECT_TAG => {
+                let [width, length] = read_fields(rest)?;
+                ShapeKind::Rect(Rectangle::new(width, length)?)
+            }
+            CIRCLE_TAG => {
+                let [radius] = read_fields(rest)?;
+                ShapeKind::Circ(Circle::new(radius)?)
+            }
+            SQUARE_TAG => {
+                let [side] = read_fields(rest)?;
+                ShapeKind::Sq(Square::new(side)?)
+            }
+            TRIANGLE_TAG => {
+                let [a, b, c] = read_fields(rest)?;
+                ShapeKind::Tri(Triangle::new(a, b, c)?)
+            }
+            _ => return Err(DecodeError::UnknownTag { tag, offset: 0 }),
+        };
+        let used = 1 + shape.field_count() * F64_SIZE;
+        Ok((shape, used))
+    }
+
+    fn field_count(&self) -> usize {
+        match self {
+            ShapeKind::Rect(_) => 2,
+            ShapeKind::Circ(_) | ShapeKind::Sq(_) => 1,
+            ShapeKind::Tri(_) => 3,
+        }
+    }
+}
+
+const F64_SIZE: usize = std::mem::size_of::<f64>();
+
+// Read `N` little-endian `f64`s from the start of `bytes`.
+fn read_fields<const N: usize>(bytes: &[u8]) -> Result<[f64; N], DecodeError> {
+    let needed = N * F64_SIZE;
+    if bytes.len() < needed {
+        return Err(DecodeError::Truncated {
+            needed: needed - bytes.len(),
+        });
+    }
+    let mut fields = [0.0; N];
+    for (field, chunk) in fields.iter_mut().zip(bytes.chunks_exact(F64_SIZE)) {
+        *field = f64::from_le_bytes(chunk.try_into().expect("chunks are 8 bytes long"));
+    }
+    Ok(fields)
+}
+
+// Encode a sequence of shapes: the count, then every shape in order.
+pub fn encode_all(shapes: &[ShapeKind]) -> Vec<u8> {
+    let count = u32::try_from(shapes.len()).expect("more than u32::MAX shapes");
+    let mut out = count.to_le_bytes().to_vec();
+    for shape in shapes {
+        shape.encode(&mut out);
+    }
+    out
+}
+
+// Decode the output of `encode_all`. The whole input has to be used: fewer bytes than the
+// count promises is `Truncated`, and more is `TrailingBytes`.
+pub fn decode_all(bytes: &[u8]) -> Result<Vec<ShapeKind>, DecodeError> {
+    let (count, mut rest) = match bytes.split_first_chunk::<4>() {
+        Some((count, rest)) => (u32::from_le_bytes(*count), rest),
+        None => {
+            return Err(DecodeError::Truncated {
+                needed: 4 - bytes.len(),
+            })
+        }
+    };
+    let mut shapes = Vec::new();
+    for _ in 0..count {
+        let offset = bytes.len() - rest.len();
+        let (shape, used) = ShapeKind::decode(rest).map_err(|err| match err {
+            // `decode` only sees its own slice; report where the tag sits in the whole input.
+            DecodeError::UnknownTag { tag, .. } => DecodeError::UnknownTag { tag, offset },
+            err => err,
+        })?;
+        shapes.push(shape);
+        rest = &rest[used..];
+    }
+    if !rest.is_empty() {
+        return Err(DecodeError::TrailingBytes { count: rest.len() });
+    }
+    Ok(shapes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::XorShift64;
+    use crate::shapes::Area;
+
+    // `count` random shapes of all four kinds, with dimensions in [0.1, 10). The third side
+    // of a triangle stays below the sum of the other two, so every triangle is valid.
+    fn random_kinds(count: usize, rng: &mut XorShift64) -> Result<Vec<ShapeKind>, ShapeError> {
+        let mut dimension = || 0.1 + 9.9 * rng.next_f64();
+        (0..count)
+            .map(|i| {
+                Ok(match i % 4 {
+                    0 => ShapeKind::Rect(Rectangle::new(dimension(), dimension())?),
+                    1 => ShapeKind::Circ(Circle::new(dimension())?),
+                    2 => ShapeKind::Sq(Square::new(dimension())?),
+                    _ => {
+                        let (a, b) = (dimension(), dimension());
+                        let c = (a - b).abs() + (a + b - (a - b).abs()) * dimension() / 10.0;
+                        ShapeKind::Tri(Triangle::new(a, b, c)?)
+                    }
+                })
+            })
+            .collect()
+    }
+
+    fn pair() -> Result<Vec<u8>, ShapeError> {
+        Ok(encode_all(&[ShapeKind::Circ(Circle::new(1.0)?), ShapeKind::Rect(Rectangle::new(2.0, 3.0)?)]))
+    }
+
+    #[test]
+    fn round_trips_keep_areas_and_bytes() -> Result<(), DecodeError> {
+        for seed in 1..=20 {
+            let original = random_kinds(100, &mut XorShift64::new(seed))?;
+            let bytes = encode_all(&original);
+            let decoded = decode_all(&bytes)?;
+            let original_areas: Vec<f64> = original.iter().map(|shape| shape.area()).collect();
+            let decoded_areas: Vec<f64> = decoded.iter().map(|shape| shape.area()).collect();
+            assert_eq!(original_areas, decoded_areas);
+            assert_eq!(encode_all(&decoded), bytes);
+        }
+        assert_eq!(pair()?.len(), 4 + 9 + 17);
+        Ok(())
+    }
+
+    #[test]
+    fn damaged_input_is_reported() -> Result<(), ShapeError> {
+        let bytes = pair()?;
+        let mut unknown = bytes.clone();
+        unknown[13] = 9;
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        let mut invalid = bytes.clone();
+        invalid[5..13].copy_from_slice(&(-1.0f64).to_le_bytes());
+        assert_eq!(decode_all(&bytes[..20]).err(), Some(DecodeError::Truncated { needed: 10 }));
+        assert_eq!(decode_all(&bytes[..2]).err(), Some(DecodeError::Truncated { needed: 2 }));
+        assert_eq!(decode_all(&unknown).err(), Some(DecodeError::UnknownTag { tag: 9, offset: 13 }));
+        assert_eq!(decode_all(&trailing).err(), Some(DecodeError::TrailingBytes { count: 1 }));
+        assert_eq!(
+            decode_all(&invalid).err().map(|err| err.to_string()),
+            Some("invalid shape: radius must not be negative".to_string())
+        );
+        Ok(())
+    }
+}
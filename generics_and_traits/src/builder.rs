@@ -0,0 +1,76 @@
+// The builder pattern: collect a rectangle's settings step by step with chained method
+// calls, then validate everything at once in `build`.
+use crate::shapes::{Rectangle, ShapeError};
+
+#[derive(Debug, Clone, Default)]
+pub struct RectangleBuilder {
+    width: Option<f64>,  // `None` until `width` or `square` is called.
+    length: Option<f64>, // `None` until `length` or `square` is called.
+    scale: Option<f64>,  // Optional factor applied to both dimensions in `build`.
+}
+
+impl RectangleBuilder {
+    pub fn new() -> Self {
+        RectangleBuilder::default()
+    }
+
+    // Each setter takes `self` by value and returns it, which is what allows chaining:
+    // `RectangleBuilder::new().width(3.0).length(4.0).build()`.
+    pub fn width(mut self, width: f64) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn length(mut self, length: f64) -> Self {
+        self.length = Some(length);
+        self
+    }
+
+    // Shortcut for `width(side).length(side)`.
+    pub fn square(self, side: f64) -> Self {
+        self.width(side).length(side)
+    }
+
+    // Multiply both dimensions by `factor` when building. The scaled dimensions are
+    // validated like any others, so a zero or negative factor makes `build` fail.
+    pub fn with_scale(mut self, factor: f64) -> Self {
+        self.scale = Some(factor);
+        self
+    }
+
+    // Check that both dimensions were provided, apply the scale, and hand the result
+    // to `Rectangle::new`, which rejects negative, zero and non-finite values.
+    pub fn build(&self) -> Result<Rectangle<f64>, ShapeError> {
+        let width = self.width.ok_or(ShapeError::MissingDimension("width"))?;
+        let length = self.length.ok_or(ShapeError::MissingDimension("length"))?;
+        let scale = self.scale.unwrap_or(1.0);
+        Rectangle::new(width * scale, length * scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_validated_rectangles() -> Result<(), ShapeError> {
+        let built = RectangleBuilder::new().width(3.0).length(4.0).build()?;
+        assert_eq!(built, Rectangle::new(3.0, 4.0)?);
+        let scaled_square = RectangleBuilder::new().square(2.0).with_scale(1.5).build()?;
+        assert_eq!(scaled_square, Rectangle::new(3.0, 3.0)?);
+        Ok(())
+    }
+
+    #[test]
+    fn missing_or_invalid_dimensions_fail() {
+        let failures = [
+            (RectangleBuilder::new().length(4.0), ShapeError::MissingDimension("width")),
+            (RectangleBuilder::new().width(3.0), ShapeError::MissingDimension("length")),
+            (RectangleBuilder::new().width(-3.0).length(4.0), ShapeError::NegativeDimension("width")),
+            (RectangleBuilder::new().square(2.0).with_scale(0.0), ShapeError::ZeroDimension("width")),
+        ];
+        for (builder, err) in failures {
+            assert_eq!(builder.build(), Err(err));
+        }
+    }
+}
@@ -0,0 +1,78 @@
+// Lazily cached areas for shapes that are expensive to measure.
+use std::cell::OnceCell;
+
+use crate::shapes::Area;
+
+// Wraps a shape and remembers its area after the first call to `area`.
+// `Area::area` only takes `&self`, so the cache needs interior mutability: a `OnceCell`
+// can be filled through a shared reference, but only once.
+#[derive(Debug)]
+pub struct CachedShape<S: Area> {
+    shape: S,
+    area: OnceCell<f64>,
+}
+
+impl<S: Area> CachedShape<S> {
+    pub fn new(shape: S) -> Self {
+        CachedShape {
+            shape,
+            area: OnceCell::new(),
+        }
+    }
+
+    pub fn get(&self) -> &S {
+        &self.shape
+    }
+
+    // Mutable access to the wrapped shape. The cache isn't cleared automatically:
+    // call `invalidate` after changing anything the area depends on.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.shape
+    }
+
+    // Forget the cached area, so the next `area` call recomputes it.
+    pub fn invalidate(&mut self) {
+        self.area.take();
+    }
+}
+
+// The wrapper is itself a shape, so it can go anywhere an `Area` is expected.
+impl<S: Area> Area for CachedShape<S> {
+    fn area(&self) -> f64 {
+        *self.area.get_or_init(|| self.shape.area())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    // A square that counts how often its area is computed.
+    #[derive(Debug)]
+    struct CountingSquare {
+        side: f64,
+        calls: Cell<u32>,
+    }
+
+    impl Area for CountingSquare {
+        fn area(&self) -> f64 {
+            self.calls.set(self.calls.get() + 1);
+            self.side * self.side
+        }
+    }
+
+    #[test]
+    fn area_is_computed_once_until_invalidated() {
+        let mut cached = CachedShape::new(CountingSquare { side: 2.0, calls: Cell::new(0) });
+        assert_eq!((cached.area(), cached.area()), (4.0, 4.0));
+        assert_eq!(cached.get().calls.get(), 1);
+        // Mutating the shape leaves a stale value until `invalidate`.
+        cached.get_mut().side = 3.0;
+        assert_eq!(cached.area(), 4.0);
+        cached.invalidate();
+        assert_eq!(cached.area(), 9.0);
+        assert_eq!(cached.get().calls.get(), 2);
+    }
+}
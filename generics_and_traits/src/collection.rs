@@ -0,0 +1,136 @@
+// A named collection type for heterogeneous shapes, wrapping `Vec<Box<dyn Area>>`.
+use std::ops::Deref;
+
+use crate::shapes::Area;
+
+#[derive(Debug, Default)]
+pub struct ShapeCollection {
+    shapes: Vec<Box<dyn Area>>,
+}
+
+impl ShapeCollection {
+    pub fn new() -> Self {
+        ShapeCollection::default()
+    }
+
+    pub fn push(&mut self, shape: Box<dyn Area>) {
+        self.shapes.push(shape);
+    }
+
+    pub fn len(&self) -> usize {
+        self.shapes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    pub fn total_area(&self) -> f64 {
+        self.shapes.iter().map(|shape| shape.area()).sum()
+    }
+
+    // The shape with the biggest area, or `None` for an empty collection.
+    pub fn largest(&self) -> Option<&dyn Area> {
+        self.shapes
+            .iter()
+            .max_by(|a, b| a.area().total_cmp(&b.area()))
+            .map(|shape| shape.as_ref())
+    }
+}
+
+// A collection is a shape too, whose area is the sum of its contents. That lets a
+// collection be boxed and stored inside another one.
+impl Area for ShapeCollection {
+    fn area(&self) -> f64 {
+        self.total_area()
+    }
+}
+
+// `Deref` to a slice gives read access to everything slices offer: indexing, `iter`,
+// `first`, and passing `&collection` where a `&[Box<dyn Area>]` is expected.
+impl Deref for ShapeCollection {
+    type Target = [Box<dyn Area>];
+
+    fn deref(&self) -> &[Box<dyn Area>] {
+        &self.shapes
+    }
+}
+
+// `collect()` into a collection.
+impl FromIterator<Box<dyn Area>> for ShapeCollection {
+    fn from_iter<I: IntoIterator<Item = Box<dyn Area>>>(iter: I) -> Self {
+        ShapeCollection {
+            shapes: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<Box<dyn Area>> for ShapeCollection {
+    fn extend<I: IntoIterator<Item = Box<dyn Area>>>(&mut self, iter: I) {
+        self.shapes.extend(iter);
+    }
+}
+
+// `for shape in collection` consumes it, yielding the boxes in insertion order.
+impl IntoIterator for ShapeCollection {
+    type Item = Box<dyn Area>;
+    type IntoIter = std::vec::IntoIter<Box<dyn Area>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.shapes.into_iter()
+    }
+}
+
+// `for shape in &collection` borrows it instead.
+impl<'a> IntoIterator for &'a ShapeCollection {
+    type Item = &'a Box<dyn Area>;
+    type IntoIter = std::slice::Iter<'a, Box<dyn Area>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.shapes.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::{Circle, ShapeError, Square};
+
+    fn squares(sides: &[f64]) -> ShapeCollection {
+        sides.iter().map(|&side| -> Box<dyn Area> { Box::new((side, side)) }).collect()
+    }
+
+    #[test]
+    fn collect_extend_and_iterate_in_order() -> Result<(), ShapeError> {
+        let mut collection = squares(&[1.0, 2.0, 3.0]);
+        collection.extend([Box::new(Circle::new(1.0)?) as Box<dyn Area>]);
+        let areas: Vec<f64> = collection.iter().map(|shape| shape.area()).collect();
+        assert_eq!(areas, [1.0, 4.0, 9.0, std::f64::consts::PI]);
+        assert_eq!(collection.len(), 4);
+        assert_eq!(collection.total_area(), 14.0 + std::f64::consts::PI);
+        assert_eq!(collection.largest().map(|shape| shape.area()), Some(9.0));
+        let consumed: Vec<f64> = collection.into_iter().map(|shape| shape.area()).collect();
+        assert_eq!(consumed, areas);
+        Ok(())
+    }
+
+    #[test]
+    fn empty_collection() {
+        let empty = ShapeCollection::new();
+        assert!(empty.is_empty());
+        assert_eq!(empty.total_area(), 0.0);
+        assert!(empty.largest().is_none());
+    }
+
+    #[test]
+    fn nested_collections_count_as_one_shape() -> Result<(), ShapeError> {
+        let inner = squares(&[1.0, 2.0]);
+        let mut nested = ShapeCollection::new();
+        nested.push(Box::new(inner));
+        nested.push(Box::new(Square::new(2)?));
+        assert_eq!((nested.len(), nested.area()), (2, 9.0));
+        let areas: Vec<f64> = (&nested).into_iter().map(|shape| shape.area()).collect();
+        assert_eq!(areas, [5.0, 4.0]);
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+// A figure made out of several primitive shapes, built with the `+` operator.
+use std::fmt;
+use std::ops::Add;
+
+use crate::shapes::{
+    Annulus, Area, Circle, Ellipse, Rectangle, RegularPolygon, Square, Trapezoid, Triangle,
+};
+
+// Marker trait for the shapes that can be combined with `+`. `CompositeShape` deliberately
+// doesn't implement it: that keeps `composite + shape` (append) and `composite + composite`
+// (merge) as separate impls, so adding to a composite never nests one inside another.
+// The `Display` bound is what lets a composite list its parts.
+pub trait Primitive: Area + fmt::Display + 'static {}
+
+// A composite keeps each part as a `Box<dyn Area>` along with its display name,
+// captured when the part is added (a `dyn Area` only has `Debug`, not `Display`).
+#[derive(Default)]
+pub struct CompositeShape {
+    parts: Vec<(String, Box<dyn Area>)>,
+}
+
+impl CompositeShape {
+    pub fn new() -> Self {
+        CompositeShape::default()
+    }
+
+    // Add one more part, returning the composite so calls can be chained.
+    pub fn with<S: Primitive>(mut self, shape: S) -> Self {
+        self.parts.push((shape.to_string(), Box::new(shape)));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.parts.len()
+    }
+}
+
+// The area of a composite is the sum of its parts (overlaps are not subtracted).
+impl Area for CompositeShape {
+    fn area(&self) -> f64 {
+        self.parts.iter().map(|(_, shape)| shape.area()).sum()
+    }
+}
+
+// Displays as e.g. "Composite [Rectangle 2 x 3 + Circle r=1]".
+impl fmt::Display for CompositeShape {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<&str> = self.parts.iter().map(|(name, _)| name.as_str()).collect();
+        write!(f, "Composite [{}]", names.join(" + "))
+    }
+}
+
+// Written by hand so that `{:?}` lists just the part names: a derived impl would print
+// every part twice, once by name and once more in struct syntax.
+impl fmt::Debug for CompositeShape {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<&str> = self.parts.iter().map(|(name, _)| name.as_str()).collect();
+        f.debug_struct("CompositeShape").field("parts", &names).finish()
+    }
+}
+
+// `composite + shape` appends the shape to the existing composite.
+impl<S: Primitive> Add<S> for CompositeShape {
+    type Output = CompositeShape;
+
+    fn add(self, rhs: S) -> CompositeShape {
+        self.with(rhs)
+    }
+}
+
+// `composite + composite` merges the two lists of parts rather than nesting.
+impl Add for CompositeShape {
+    type Output = CompositeShape;
+
+    fn add(mut self, rhs: CompositeShape) -> CompositeShape {
+        self.parts.extend(rhs.parts);
+        self
+    }
+}
+
+// Every primitive needs the same two impls: the marker trait, and `shape + other`
+// producing a new composite. Because of the orphan rules `Add` can't be implemented for
+// a bare type parameter, so a macro stamps the impls out for each shape type instead.
+macro_rules! impl_primitive {
+    ($($shape:ident),*) => {
+        $(
+            impl<T: Into<f64> + Copy + fmt::Debug + fmt::Display + 'static> Primitive for $shape<T> {}
+
+            impl<T, S> Add<S> for $shape<T>
+            where
+                $shape<T>: Primitive,
+                S: Primitive,
+            {
+                type Output = CompositeShape;
+
+                fn add(self, rhs: S) -> CompositeShape {
+                    CompositeShape::new().with(self).with(rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive!(Rectangle, Circle, Square, Triangle, Ellipse, Trapezoid, RegularPolygon, Annulus);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::ShapeError;
+
+    #[test]
+    fn adding_shapes_sums_their_areas() -> Result<(), ShapeError> {
+        let figure = Rectangle::new(2.0, 3.0)? + Circle::new(1.0)?;
+        assert_eq!(figure.to_string(), "Composite [Rectangle 2 x 3 + Circle r=1]");
+        assert_eq!(figure.area(), 6.0 + std::f64::consts::PI);
+        Ok(())
+    }
+
+    #[test]
+    fn composites_append_and_merge_without_nesting() -> Result<(), ShapeError> {
+        let figure = Rectangle::new(2.0, 3.0)? + Circle::new(1.0)? + Triangle::new(3.0, 4.0, 5.0)?;
+        let figure = figure + (Square::new(1.0)? + Square::new(2.0)?);
+        assert_eq!(
+            format!("{:?}", figure),
+            "CompositeShape { parts: [\"Rectangle 2 x 3\", \"Circle r=1\", \"Triangle 3, 4, 5\", \"Square 1\", \"Square 2\"] }"
+        );
+        assert_eq!(figure.len(), 5);
+        assert_eq!(figure.area(), 6.0 + std::f64::consts::PI + 6.0 + 1.0 + 4.0);
+        Ok(())
+    }
+}
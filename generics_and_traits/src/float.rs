@@ -0,0 +1,39 @@
+// A minimal floating point abstraction so shape math can run entirely in `f32`
+// (half the memory) or entirely in `f64` (more precision), chosen by the dimension type.
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Sub};
+
+// Everything the float-generic area formulas need: arithmetic that stays in `Self`,
+// comparison, and the constants and functions that differ between `f32` and `f64`.
+// Each operation returns `Self`, so an `f32` computation never widens to `f64` behind the scenes.
+pub trait Float:
+    Copy
+    + Debug
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    const PI: Self;
+    const HALF: Self;
+    fn sqrt(self) -> Self;
+}
+
+impl Float for f32 {
+    const PI: f32 = std::f32::consts::PI;
+    const HALF: f32 = 0.5;
+
+    fn sqrt(self) -> f32 {
+        f32::sqrt(self)
+    }
+}
+
+impl Float for f64 {
+    const PI: f64 = std::f64::consts::PI;
+    const HALF: f64 = 0.5;
+
+    fn sqrt(self) -> f64 {
+        f64::sqrt(self)
+    }
+}
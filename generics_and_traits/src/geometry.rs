@@ -0,0 +1,247 @@
+use crate::shapes::{Circle, Rectangle, Triangle};
+
+// A generic point in the plane. A point has no invariants to protect,
+// so unlike the shapes its fields are public.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+}
+
+// Define a trait `Contains` for hit testing: does a point fall inside the shape?
+// Points exactly on the boundary count as inside.
+pub trait Contains {
+    fn contains(&self, point: &Point<f64>) -> bool;
+}
+
+// Shapes don't store a position, so a `Rectangle` is taken to be anchored with its
+// bottom-left corner at the origin, spanning [0, width] x [0, length].
+impl<T: Into<f64> + Copy> Contains for Rectangle<T> {
+    fn contains(&self, point: &Point<f64>) -> bool {
+        (0.0..=self.width().into()).contains(&point.x)
+            && (0.0..=self.length().into()).contains(&point.y)
+    }
+}
+
+// A `Circle` is taken to be centered on the origin. Comparing squared distances
+// avoids a square root and keeps points on the circle itself inside.
+impl<T: Into<f64> + Copy> Contains for Circle<T> {
+    fn contains(&self, point: &Point<f64>) -> bool {
+        let radius = self.radius().into();
+        point.x * point.x + point.y * point.y <= radius * radius
+    }
+}
+
+// A rectangle placed in the plane: `origin` is its bottom-left corner and the
+// sides stay parallel to the axes (an axis-aligned rectangle).
+// `Rectangle` itself has no position, so this wrapper adds one rather than changing every shape.
+#[derive(Debug)]
+pub struct PlacedRect {
+    pub origin: Point<f64>,
+    pub rect: Rectangle<f64>,
+}
+
+impl PlacedRect {
+    pub fn new(origin: Point<f64>, rect: Rectangle<f64>) -> Self {
+        PlacedRect { origin, rect }
+    }
+
+    // The opposite (top-right) corner.
+    fn far_corner(&self) -> Point<f64> {
+        Point::new(
+            self.origin.x + self.rect.width(),
+            self.origin.y + self.rect.length(),
+        )
+    }
+
+    // Two axis-aligned rectangles intersect when their ranges overlap on both axes.
+    // The comparisons are inclusive, so rectangles that only touch along an edge
+    // (or at a corner) intersect, with an overlap area of zero.
+    pub fn intersects(&self, other: &Self) -> bool {
+        let (far, other_far) = (self.far_corner(), other.far_corner());
+        self.origin.x <= other_far.x
+            && other.origin.x <= far.x
+            && self.origin.y <= other_far.y
+            && other.origin.y <= far.y
+    }
+
+    // The overlap along each axis is the gap between the larger start and the smaller end;
+    // a negative gap means the rectangles are apart on that axis, so it is clamped to zero.
+    pub fn overlap_area(&self, other: &Self) -> f64 {
+        let (far, other_far) = (self.far_corner(), other.far_corner());
+        let overlap_x = far.x.min(other_far.x) - self.origin.x.max(other.origin.x);
+        let overlap_y = far.y.min(other_far.y) - self.origin.y.max(other.origin.y);
+        overlap_x.max(0.0) * overlap_y.max(0.0)
+    }
+}
+
+// Rigid transforms that map a shape onto a congruent one, so its area never changes.
+// They are taken about the coordinate origin, matching `Contains`: rotating turns a quarter
+// turn counter-clockwise, `(x, y)` -> `(-y, x)`; `flip_horizontal` mirrors left-right,
+// `(x, y)` -> `(-x, y)`, and `flip_vertical` mirrors top-bottom, `(x, y)` -> `(x, -y)`.
+pub trait Transform: Sized {
+    fn rotate90(&self) -> Self;
+    fn flip_horizontal(&self) -> Self;
+    fn flip_vertical(&self) -> Self;
+}
+
+// A placed rectangle moves as well as turning. After a rotation the corner that ends up
+// bottom-left is the image of the old top-left corner, (x, y + l) -> (-y - l, x); after a
+// flip it is the image of the corner on the other side of the mirror line.
+impl Transform for PlacedRect {
+    fn rotate90(&self) -> Self {
+        let origin = Point::new(-(self.origin.y + self.rect.length()), self.origin.x);
+        PlacedRect::new(origin, self.rect.rotated())
+    }
+
+    fn flip_horizontal(&self) -> Self {
+        let origin = Point::new(-(self.origin.x + self.rect.width()), self.origin.y);
+        PlacedRect::new(origin, self.rect.flip_horizontal())
+    }
+
+    fn flip_vertical(&self) -> Self {
+        let origin = Point::new(self.origin.x, -(self.origin.y + self.rect.length()));
+        PlacedRect::new(origin, self.rect.flip_vertical())
+    }
+}
+
+// The centroid is a shape's center of mass (for a uniform shape): the natural spot for a
+// label. Unplaced shapes use the same positions as `Contains`.
+pub trait Centroid {
+    fn centroid(&self) -> Point<f64>;
+}
+
+// The center of [0, width] x [0, length].
+impl<T: Into<f64> + Copy> Centroid for Rectangle<T> {
+    fn centroid(&self) -> Point<f64> {
+        Point::new(self.width().into() / 2.0, self.length().into() / 2.0)
+    }
+}
+
+impl Centroid for PlacedRect {
+    fn centroid(&self) -> Point<f64> {
+        let center = self.rect.centroid();
+        Point::new(self.origin.x + center.x, self.origin.y + center.y)
+    }
+}
+
+// A circle is centered on the origin.
+impl<T> Centroid for Circle<T> {
+    fn centroid(&self) -> Point<f64> {
+        Point::new(0.0, 0.0)
+    }
+}
+
+// A `Triangle` only knows its side lengths, so it is placed with side c running from the
+// origin along the x-axis to (c, 0), and the third vertex above the axis at distance b
+// from the origin and a from (c, 0). The centroid is the average of the three vertices.
+impl<T: Into<f64> + Copy> Centroid for Triangle<T> {
+    fn centroid(&self) -> Point<f64> {
+        let (a, b, c) = self.sides();
+        let (a, b, c) = (a.into(), b.into(), c.into());
+        // From the law of cosines; the triangle inequality keeps the root's argument positive.
+        let x = (b * b + c * c - a * a) / (2.0 * c);
+        let y = (b * b - x * x).sqrt();
+        Point::new((0.0 + c + x) / 3.0, (0.0 + 0.0 + y) / 3.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::{Area, ShapeError};
+
+    // Rotations and reflections move a shape without resizing it, so every transform (and
+    // every combination of them) must leave the area unchanged.
+    fn area_is_invariant<S: Transform + Area>(shape: &S) -> bool {
+        let area = shape.area();
+        [
+            shape.rotate90(),
+            shape.rotate90().rotate90(),
+            shape.flip_horizontal(),
+            shape.flip_vertical(),
+            shape.rotate90().flip_horizontal(),
+        ]
+        .iter()
+        .all(|transformed| transformed.area() == area)
+    }
+
+    #[test]
+    fn points_on_the_boundary_are_inside() -> Result<(), ShapeError> {
+        // The rectangle spans [0, 4] x [0, 3] and the circle is centered on the origin.
+        let (rect, circle) = (Rectangle::new(4.0, 3.0)?, Circle::new(2)?);
+        let probes = [
+            (Point::new(1.0, 1.0), true, true),
+            (Point::new(4.0, 3.0), true, false),
+            (Point::new(0.0, -2.0), false, true),
+            (Point::new(5.0, 5.0), false, false),
+        ];
+        for (probe, in_rect, in_circle) in probes {
+            assert_eq!((rect.contains(&probe), circle.contains(&probe)), (in_rect, in_circle));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn overlapping_placed_rectangles() -> Result<(), ShapeError> {
+        let base = PlacedRect::new(Point::new(0.0, 0.0), Rectangle::new(4.0, 4.0)?);
+        let others = [
+            (Point::new(10.0, 10.0), Rectangle::new(1.0, 1.0)?, false, 0.0),
+            (Point::new(2.0, 3.0), Rectangle::new(4.0, 4.0)?, true, 2.0),
+            (Point::new(1.0, 1.0), Rectangle::new(2.0, 2.0)?, true, 4.0),
+            // Touching along an edge intersects, with no overlap.
+            (Point::new(4.0, 0.0), Rectangle::new(3.0, 2.0)?, true, 0.0),
+        ];
+        for (origin, rect, intersects, overlap) in others {
+            let other = PlacedRect::new(origin, rect);
+            assert_eq!(base.intersects(&other), intersects);
+            assert_eq!(other.intersects(&base), intersects);
+            assert_eq!(base.overlap_area(&other), overlap);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn transforms_keep_the_area() -> Result<(), ShapeError> {
+        assert!(area_is_invariant(&Rectangle::new(10, 5)?));
+        assert!(area_is_invariant(&Rectangle::new(4.5, 3.2)?));
+        assert!(area_is_invariant(&Rectangle::new(1, 1)?));
+        assert!(area_is_invariant(&Rectangle::new(0.1, 300.0)?));
+        assert!(area_is_invariant(&Circle::new(3.7)?));
+        Ok(())
+    }
+
+    #[test]
+    fn placed_rectangles_move_when_transformed() -> Result<(), ShapeError> {
+        // [1, 5] x [2, 4] turns about the origin to [-4, -2] x [1, 5].
+        let placed = PlacedRect::new(Point::new(1.0, 2.0), Rectangle::new(4.0, 2.0)?);
+        let turned = placed.rotate90();
+        assert_eq!(turned.origin, Point::new(-4.0, 1.0));
+        assert_eq!(turned.rect, Rectangle::new(2.0, 4.0)?);
+        assert_eq!(placed.flip_horizontal().origin, Point::new(-5.0, 2.0));
+        assert_eq!(placed.flip_vertical().origin, Point::new(1.0, -4.0));
+        // Four quarter turns come back to the start.
+        let around = turned.rotate90().rotate90().rotate90();
+        assert_eq!((around.origin, &around.rect), (placed.origin, &placed.rect));
+        assert_eq!(turned.rect.area(), placed.rect.area());
+        Ok(())
+    }
+
+    #[test]
+    fn centroids() -> Result<(), ShapeError> {
+        assert_eq!(Rectangle::new(10, 5)?.centroid(), Point::new(5.0, 2.5));
+        let placed = PlacedRect::new(Point::new(1.0, 2.0), Rectangle::new(4.0, 2.0)?);
+        assert_eq!(placed.centroid(), Point::new(3.0, 3.0));
+        assert_eq!(Circle::new(7)?.centroid(), Point::new(0.0, 0.0));
+        // The 3-4-5 triangle has corners (0, 0), (5, 0) and (3.2, 2.4).
+        let center = Triangle::new(3, 4, 5)?.centroid();
+        assert!((center.x - 41.0 / 15.0).abs() < 1e-12 && (center.y - 0.8).abs() < 1e-12);
+        Ok(())
+    }
+}
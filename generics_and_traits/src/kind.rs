@@ -0,0 +1,44 @@
+// A closed set of shapes as an enum: the static-dispatch alternative to `Box<dyn Area>`.
+// Every variant is known at compile time, so `area` is a `match` instead of a vtable
+// lookup, and the shapes are stored inline without a heap allocation each. The price is
+// that adding a new kind of shape means editing this enum.
+use crate::shapes::{Area, Circle, Rectangle, Square, Triangle};
+
+#[derive(Debug)]
+pub enum ShapeKind {
+    Rect(Rectangle<f64>),
+    Circ(Circle<f64>),
+    Sq(Square<f64>),
+    Tri(Triangle<f64>),
+}
+
+impl Area for ShapeKind {
+    fn area(&self) -> f64 {
+        match self {
+            ShapeKind::Rect(rect) => rect.area(),
+            ShapeKind::Circ(circle) => circle.area(),
+            ShapeKind::Sq(square) => square.area(),
+            ShapeKind::Tri(triangle) => triangle.area(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::ShapeError;
+
+    #[test]
+    fn each_variant_uses_its_shapes_area() -> Result<(), ShapeError> {
+        let kinds = [
+            (ShapeKind::Rect(Rectangle::new(2.0, 3.0)?), 6.0),
+            (ShapeKind::Circ(Circle::new(1.0)?), std::f64::consts::PI),
+            (ShapeKind::Sq(Square::new(3.0)?), 9.0),
+            (ShapeKind::Tri(Triangle::new(3.0, 4.0, 5.0)?), 6.0),
+        ];
+        for (kind, area) in kinds {
+            assert_eq!(kind.area(), area);
+        }
+        Ok(())
+    }
+}
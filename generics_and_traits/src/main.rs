@@ -1,75 +1,741 @@
-use std::fmt::Debug; // Import the Debug trait, which allows types to be formatted using `{:?}` for debugging.
+use std::cell::Cell; // Shared-reference counter for the cached area demo.
+use std::cmp::Ordering; // `rank_by` falls back to `Ordering::Equal` for incomparable keys.
+use std::collections::HashMap; // Shapes with hashable dimensions as map keys.
+use std::env; // Command-line arguments for the area calculator mode.
+use std::error::Error; // Import the Error trait so `main` can propagate any error with `?`.
+use std::fmt::{Debug, Display}; // `Debug` formats with `{:?}`, `Display` with `{}` for user-facing output.
+use std::hint::black_box; // Keeps the optimizer from skipping the benchmarked work.
+use std::process; // Non-zero exit code when the command-line shapes are invalid.
+use std::thread; // Scoped threads for `total_area_parallel`.
+use std::time::Instant; // Timing for the dispatch benchmark.
 
-// Define a trait `Area` for calculating the area of shapes.
-// Any type implementing this trait must define the `area` method.
-trait Area {
-    fn area(&self) -> f64;  // The method `area` must return the area as an `f64`.
+mod approx; // `ApproxEq` and `assert_area_eq!` for comparing float areas.
+mod binary; // `encode_all`/`decode_all`, a compact binary format for shapes.
+mod builder; // `RectangleBuilder`, a fluent way to construct rectangles.
+mod cache; // `CachedShape`, which computes an area once and remembers it.
+mod collection; // `ShapeCollection`, a collectable, iterable group of boxed shapes.
+mod composite; // `CompositeShape` and the `+` operator for shapes.
+mod float; // The `Float` trait shared by the f32 and f64 code paths.
+mod geometry; // `Point` and other coordinate-based helpers.
+mod kind; // `ShapeKind`, an enum of shapes for static dispatch.
+mod measured; // `MeasuredShape`, which counts calls to `area`.
+mod ordered; // `OrderedDim`, a hashable dimension, and `count_duplicates`.
+mod parse; // `FromStr` impls for the compact "rect 10 5" text format.
+mod polygon; // `FixedPolygon` with a compile-time number of vertices, `Polygon` with any number.
+mod positive; // `PositiveF64`/`PositiveU32`, dimensions that are always valid.
+mod random; // `gen_shapes`, driven by any closure returning random numbers.
+mod render; // ASCII-art drawings of integer rectangles, squares and circles.
+mod report; // `comparison_table`, a text table comparing a set of shapes.
+mod rounding; // `Rounding` policies for whole-number areas.
+// The shapes live in their own module so their fields can be private:
+// code outside `shapes` has to go through the validating constructors.
+mod shapes;
+mod solids; // 3D shapes and the `Volume` trait.
+mod stats; // `area_stats`: count, total, mean, median, min and max of a collection.
+mod units; // `Meters`/`Feet` newtypes and unit-aware areas.
+#[cfg(feature = "serde")]
+mod serialization; // JSON loading and saving, enabled with `--features serde`.
+
+use approx::{ApproxEq, DEFAULT_EPSILON};
+use binary::{decode_all, encode_all, DecodeError};
+use builder::RectangleBuilder;
+use cache::CachedShape;
+use collection::ShapeCollection;
+use geometry::{Centroid, Contains, PlacedRect, Point, Transform};
+use kind::ShapeKind;
+use measured::{AtomicMeasuredShape, MeasuredShape};
+use ordered::{count_duplicates, OrderedDim, ShapeKey};
+use polygon::{FixedPolygon, Polygon};
+use positive::{PositiveF64, PositiveU32, ValidCircle, ValidRect};
+use random::{gen_shapes, XorShift64};
+use render::Render;
+use report::{comparison_table, comparison_table_with_precision};
+use rounding::Rounding;
+use shapes::{
+    as_f64_area, Annulus, Area, AreaOf, BoundingBox, Circle, Circle32, Circle64, Ellipse, Perimeter,
+    Rectangle, Rectangle2, Rectangle32, Rectangle64, RegularPolygon, Scale, ScaledSelf, Shape, Square, Trapezoid, Triangle,
+};
+use solids::{Cuboid, Cylinder, Sphere, SurfaceArea, Volume};
+use stats::{area_stats, sum_areas, TotalArea};
+use units::{AreaIn, Feet, Meters, Quantity, SquareFeet, SquareMeters, TypedArea};
+
+// A generic function `print_area` that accepts any type `T` that implements both `Area` and `Debug` traits.
+// This function prints the shape and its calculated area, using the shape's `describe`.
+fn print_area<T: Area + Debug>(shape: &T) {
+    println!("{}", shape.describe());
 }
 
-// A generic struct `Rectangle` that can take any type `T` for its width and length.
-#[derive(Debug)] // Derive the `Debug` trait to allow printing the struct using `{:?}`.
-struct Rectangle<T> {
-    width: T,    // Width of the rectangle, of generic type `T`.
-    length: T,   // Length of the rectangle, also of type `T`.
+// The dynamic-dispatch version of `print_area`: it takes a trait object, so it works on
+// elements of a `Vec<Box<dyn Area>>`. Printing with `{:?}` is possible because `Debug`
+// is a supertrait of `Area`.
+fn print_area_dyn(shape: &dyn Area) {
+    println!("Area of the provided {:?} is {:?}", shape, shape.area());
 }
 
-// Implement the `Area` trait for `Rectangle`, where `T` is a generic type.
-// The `T` must implement both the `Into<f64>` and `Copy` traits.
-impl<T: Into<f64> + Copy> Area for Rectangle<T> {
-    fn area(&self) -> f64 {
-        // Convert the width and length from `T` into `f64` and compute the area.
-        self.width.into() * self.length.into()
+// The 3D counterpart of `print_area`.
+fn print_volume<T: Volume + Debug>(solid: &T) {
+    println!("Volume of the provided {:?} is {:?}", solid, solid.volume());
+}
+
+// Print both measurements of a solid.
+fn print_solid<T: Volume + SurfaceArea + Debug>(solid: &T) {
+    println!(
+        "{:?} has volume {:.4} and surface area {:.4}",
+        solid,
+        solid.volume(),
+        solid.surface_area()
+    );
+}
+
+// Like `print_area`, but bounded on `Display` instead of `Debug`, so it prints the
+// human-readable form of the shape (e.g. "Rectangle 10 x 5") rather than raw struct syntax.
+fn print_area_display<T: Area + Display>(shape: &T) {
+    println!("{}", describe_shape(shape, &shape.to_string()));
+}
+
+// Builds the same sentence as `print_area_display`, but the caller supplies the name, so
+// the shape needs no formatting trait of its own. `?Sized` also lets it take a `&dyn Area`.
+fn describe_shape<T: Area + ?Sized>(shape: &T, name: &str) -> String {
+    format!("Area of {} is {}", name, shape.area())
+}
+
+// Like `print_area`, but for the exact `AreaOf` API: the area's type is whatever the shape
+// chose as `Output`, so it is that associated type that has to be `Debug`.
+fn print_exact_area<T>(shape: &T)
+where
+    T: AreaOf + Debug,
+    T::Output: Debug,
+{
+    println!("Exact area of {:?} is {:?}", shape, shape.area_of());
+}
+
+// Print a shape's area next to its bounding box's area. The box is always at least as big;
+// the two only match for rectangles (and squares), which fill their box exactly.
+fn print_bounding_box<T: Area + BoundingBox + Display>(shape: &T) {
+    let bounds = shape.bounding_box();
+    println!(
+        "{} has area {:.3} inside a {} box of area {:.3}",
+        shape,
+        shape.area(),
+        bounds,
+        bounds.area()
+    );
+}
+
+// A generic function `print_measurements` that accepts any type implementing `Area`,
+// `Perimeter` and `Debug`. Multiple trait bounds are combined with `+`.
+fn print_measurements<T: Area + Perimeter + Debug>(shape: &T) {
+    println!(
+        "{:?} has area {:?} and perimeter {:?}",
+        shape,
+        shape.area(),
+        shape.perimeter()
+    );
+}
+
+// `Area` only has methods taking `&self` and no generic methods, which makes it
+// object safe: different shapes can be stored behind `Box<dyn Area>` in one collection.
+// Calls on a trait object are resolved at runtime through the vtable (dynamic dispatch).
+// Thanks to the blanket impls for `&T` and `Box<T>`, the same function sums a
+// `Vec<Box<dyn Area>>`, a `Vec<&dyn Area>` or a plain `Vec<Rectangle<f64>>`.
+fn total_area<S: Area>(shapes: &[S]) -> f64 {
+    shapes.iter().map(|shape| shape.area()).sum()
+}
+
+// `total_area` spread over `threads` threads: the slice is split into one chunk per thread,
+// each thread sums its chunk, and the partial sums are added up at the end. Scoped threads
+// may borrow the slice, so unlike `thread::spawn` nothing has to be cloned or moved in;
+// `Send + Sync` is what allows the shapes to be read from other threads.
+// Zero threads is treated as one, and no more threads are started than there are shapes.
+fn total_area_parallel(shapes: &[Box<dyn Area + Send + Sync>], threads: usize) -> f64 {
+    let threads = threads.clamp(1, shapes.len().max(1));
+    let chunk_size = shapes.len().div_ceil(threads).max(1);
+    thread::scope(|scope| {
+        let handles: Vec<_> = shapes
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || total_area(chunk)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("summing areas doesn't panic"))
+            .fold(0.0, |total, partial| total + partial)
+    })
+}
+
+// Time one `total_area` call over `shapes`: `S` is `ShapeKind` for enum dispatch or
+// `Box<dyn Area>` for trait objects.
+fn time_total_area<S: Area>(label: &str, shapes: &[S]) {
+    let start = Instant::now();
+    let total = total_area(black_box(shapes));
+    let duration = start.elapsed();
+    println!("{}: total area {:.0} in {:?}", label, total, duration);
+}
+
+// Sum the areas of `count` shapes stored both ways. The same dimensions are used for both
+// collections, so the totals match and only the dispatch mechanism differs.
+fn benchmark_dispatch(count: usize) -> Result<(), Box<dyn Error>> {
+    let mut enums = Vec::with_capacity(count);
+    let mut boxed: Vec<Box<dyn Area>> = Vec::with_capacity(count);
+    for i in 0..count {
+        let size = (i % 100 + 1) as f64;
+        match i % 4 {
+            0 => {
+                enums.push(ShapeKind::Rect(Rectangle::new(size, 2.0)?));
+                boxed.push(Box::new(Rectangle::new(size, 2.0)?));
+            }
+            1 => {
+                enums.push(ShapeKind::Circ(Circle::new(size)?));
+                boxed.push(Box::new(Circle::new(size)?));
+            }
+            2 => {
+                enums.push(ShapeKind::Sq(Square::new(size)?));
+                boxed.push(Box::new(Square::new(size)?));
+            }
+            _ => {
+                enums.push(ShapeKind::Tri(Triangle::new(size, size, size)?));
+                boxed.push(Box::new(Triangle::new(size, size, size)?));
+            }
+        }
     }
+    time_total_area("enum dispatch (Vec<ShapeKind>)", &enums);
+    time_total_area("dyn dispatch (Vec<Box<dyn Area>>)", &boxed);
+    Ok(())
+}
+
+// `f64` only implements `PartialOrd` (because of NaN), so `sort` and `sort_by_key` can't be used.
+// `f64::total_cmp` provides a total ordering that `sort_by` accepts.
+// Generic over `S`, so it sorts boxed trait objects and concrete shapes alike.
+fn sort_by_area<S: Area>(shapes: &mut [S]) {
+    shapes.sort_by(|a, b| a.area().total_cmp(&b.area()));
+}
+
+// The indices of `shapes` ordered by any key the closure derives from a shape, smallest
+// key first, without reordering the shapes themselves. `K` only needs `PartialOrd`, so
+// `f64` keys work; keys that can't be compared (NaN) are treated as equal, and the stable
+// sort then keeps those shapes in their original order.
+fn rank_by<K, F>(shapes: &[Box<dyn Area>], key: F) -> Vec<usize>
+where
+    F: Fn(&dyn Area) -> K,
+    K: PartialOrd,
+{
+    // Each key is computed once up front rather than on every comparison.
+    let keys: Vec<K> = shapes.iter().map(|shape| key(shape.as_ref())).collect();
+    let mut indices: Vec<usize> = (0..shapes.len()).collect();
+    indices.sort_by(|&a, &b| keys[a].partial_cmp(&keys[b]).unwrap_or(Ordering::Equal));
+    indices
 }
 
-// A generic struct `Circle` that can take any type `T` for its radius.
-#[derive(Debug)] // Derive the `Debug` trait for the `Circle` struct as well.
-struct Circle<T> {
-    radius: T,   // Radius of the circle, of generic type `T`.
+// Return the shape with the biggest area, or `None` for an empty slice.
+// `as_ref` turns the `&Box<dyn Area>` into a plain `&dyn Area`.
+// The lifetime `'a` says the returned reference borrows from the slice: no shape is cloned,
+// and the caller can keep the result only as long as the collection itself is alive.
+// (Elision would infer exactly this signature; it is spelled out here to show the rule.)
+#[allow(clippy::needless_lifetimes)]
+fn largest_shape<'a>(shapes: &'a [Box<dyn Area>]) -> Option<&'a dyn Area> {
+    shapes
+        .iter()
+        .max_by(|a, b| a.area().total_cmp(&b.area()))
+        .map(|shape| shape.as_ref())
 }
 
-// Implement the `Area` trait for `Circle`, where `T` is a generic type.
-// The `T` must implement both `Into<f64>` and `Copy` traits.
-impl<T: Into<f64> + Copy> Area for Circle<T> {
+// Pick out the `Rectangle<f64>`s from a mixed collection by downcasting each trait object.
+// `downcast_ref` returns `None` for every other type, including rectangles with other
+// dimension types such as `Rectangle<i32>`. `as_ref` unwraps the box first, or it would be
+// the `Box` itself that is viewed as `Any`.
+fn find_rectangles(shapes: &[Box<dyn Area>]) -> Vec<&Rectangle<f64>> {
+    shapes
+        .iter()
+        .filter_map(|shape| shape.as_ref().as_any().downcast_ref::<Rectangle<f64>>())
+        .collect()
+}
+
+// A square that counts how often its area is computed, to show `CachedShape` at work.
+// `Cell` lets `area`, which only gets `&self`, increment the counter.
+#[derive(Debug)]
+struct CountingSquare {
+    side: f64,
+    calls: Cell<u32>,
+}
+
+impl Area for CountingSquare {
     fn area(&self) -> f64 {
-        // Calculate the area of the circle using the formula π * r^2.
-        std::f64::consts::PI * self.radius.into() * self.radius.into()
+        self.calls.set(self.calls.get() + 1);
+        self.side * self.side
+    }
+
+    // Overrides `Area::describe`; it reports the counter without computing the area.
+    fn describe(&self) -> String {
+        format!(
+            "Counting square of side {} (area computed {} times)",
+            self.side,
+            self.calls.get()
+        )
     }
 }
 
-// A generic function `print_area` that accepts any type `T` that implements both `Area` and `Debug` traits.
-// This function prints the shape and its calculated area.
-fn print_area<T: Area + Debug>(shape: &T) {
-    println!("Area of the provided {:?} is {:?}", shape, shape.area());
+// Calculator mode: print the area of every shape given on the command line, then the total.
+// Invalid input exits with status 1 after a message naming the supported shapes.
+fn run_cli(args: &[String]) {
+    match parse::parse_args(args) {
+        Ok(shapes) => {
+            for shape in &shapes {
+                println!("{:?}: area {:.4}", shape, shape.area());
+            }
+            println!("Total area: {:.4}", total_area(&shapes));
+        }
+        Err(err) => {
+            eprintln!("error: {}", err);
+            eprintln!(
+                "usage: generics_and_traits [SHAPE NUMBERS...]... e.g. `circle 3.5 rect 2 4`"
+            );
+            eprintln!("supported shapes: {}", parse::SUPPORTED_SHAPES.join(", "));
+            process::exit(1);
+        }
+    }
 }
 
-fn main() {
-    // Create a rectangle with integer dimensions (i32).
-    let rect = Rectangle {
-        width: 10,  // width of type i32
-        length: 5,  // length of type i32
-    };
-
-    // Create a rectangle with floating point dimensions (f64).
-    let rect_f64 = Rectangle {
-        width: 4.5, // width of type f64
-        length: 3.2, // length of type f64
-    };
-
-    // Create a circle with an integer radius (i32).
-    let circle = Circle {
-        radius: 7,   // radius of type i32
-    };
-
-    // Create a circle with a floating point radius (f64).
-    let circle_f64 = Circle {
-        radius: 3.7, // radius of type f64
-    };
-
-    // Call the `print_area` function with each shape.
-    // This will print the debug output of the shape and its computed area.
-    print_area(&rect);       // Rectangle with integer dimensions
+fn main() -> Result<(), Box<dyn Error>> {
+    // With arguments, e.g. `cargo run -- circle 3.5 rect 2 4`, the binary works as an area
+    // calculator; without any it runs the demonstrations below.
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        run_cli(&args);
+        return Ok(());
+    }
+
+    // Shapes with integer and floating point dimensions. `new` validates the dimensions and
+    // returns a `Result`; `?` propagates any error.
+    let rect = Rectangle::new(10, 5)?;
+    let rect_f64 = Rectangle::new(4.5, 3.2)?;
+    let circle = Circle::new(7)?;
+    let circle_f64 = Circle::new(3.7)?;
+    let triangle = Triangle::new(3, 4, 5)?;
+    let triangle_f64 = Triangle::new(2.5, 2.5, 3.0)?;
+    let square = Square::new(4)?;
+    let ellipse = Ellipse::new(5, 3)?;
+    let trapezoid = Trapezoid::new(3, 5, 4)?;
+    let hexagon = RegularPolygon::new(2, 6)?;
+    let ring = Annulus::from_circles(Circle::new(5.0)?, Circle::new(3.0)?)?;
+    println!("rect is {} wide and {} long", rect.width(), rect.length()); // Output: rect is 10 wide and 5 long
+    println!("circle_f64 has radius {}, square has side {}", circle_f64.radius(), square.side()); // Output: circle_f64 has radius 3.7, square has side 4
+    if let Err(err) = Triangle::new(1, 2, 10) {
+        println!("Rejected shape: {}", err); // Output: Rejected shape: the sum of any two sides must be greater than the third
+    }
+
+    // Call the `print_area` function with each shape. It prints the shape's `describe`, a
+    // provided method of `Area`: the default, or a shape's own override as for circles.
+    print_area(&rect);       // Output: Rectangle { width: 10, length: 5 } has area 50.000
     print_area(&rect_f64);   // Rectangle with floating point dimensions
     print_area(&circle);     // Circle with integer radius
     print_area(&circle_f64); // Circle with floating point radius
+    print_area(&triangle);     // Triangle with integer sides
+    print_area(&triangle_f64); // Triangle with floating point sides
+    // References and boxes are shapes too, through the blanket impls.
+    print_area(&&triangle);
+    print_area(&Box::new(Circle::new(2.0)?)); // Output: Circle of radius 2.0 has area 12.566 and circumference 12.566
+    print_area(&(3, 4)); // Output: (3, 4) has area 12.000
+    for shape in [&trapezoid as &dyn Area, &ring, &Rectangle2::new(3, 4.5)?] {
+        print_area_dyn(shape); // Output: Area of the provided Trapezoid { base_a: 3, base_b: 5, height: 4 } is 16.0 ...
+    }
+
+    // The `Display` version prints the shape in a friendlier format.
+    print_area_display(&rect_f64); // Output: Area of Rectangle 4.5 x 3.2 is 14.4
+    println!("{}", describe_shape(&(3, 4), "a (3, 4) tuple")); // Output: Area of a (3, 4) tuple is 12
+    println!(
+        "{} {} {} {}",
+        rect.rotated(),
+        circle.scaled(3.0),
+        ScaledSelf::scaled(&Circle::new(1.5)?, 2.0),
+        Rectangle::from_corners(Point::new(1, 1), Point::new(4, 3))?
+    ); // Output: Rectangle 5 x 10 Circle r=21 Circle r=3 Rectangle 3 x 2
+
+    // Print area and perimeter together for shapes implementing both traits.
+    print_measurements(&rect);     // Perimeter: 2 * (10 + 5) = 30
+    print_measurements(&circle);   // Circumference: 2 * π * 7
+    print_measurements(&triangle); // Perimeter: 3 + 4 + 5 = 12
+    print_measurements(&ellipse); // Perimeter by Ramanujan's approximation: 25.526986
+    print_measurements(&hexagon); // Output: RegularPolygon { side_length: 2, num_sides: 6 } has area 10.392304845413264 and perimeter 12.0
+    print_measurements(&(2.5, 4.0)); // Output: (2.5, 4.0) has area 10.0 and perimeter 13.0
+
+    // A square converts into a rectangle through `From`/`Into`, and back when the sides match.
+    print_measurements(&square); // Output: Square { side: 4 } has area 16.0 and perimeter 16.0
+    let square_as_rect: Rectangle<i32> = square.into();
+    println!("{:?}", square_as_rect.try_into_square()); // Output: Ok(Square { side: 4 })
+    let dims: (i32, i32) = Rectangle::try_from((6, 2))?.into();
+    let from_array: Rectangle<f64> = [1.5, 4.0].try_into()?;
+    println!("{:?} and {}", dims, from_array); // Output: (6, 2) and Rectangle 1.5 x 4
+    println!(
+        "{} is a parallelogram: {}, hexagon circumradius {:.3}",
+        trapezoid,
+        trapezoid.is_parallelogram(),
+        hexagon.circumradius()
+    ); // Output: Trapezoid 3 | 5 h=4 is a parallelogram: false, hexagon circumradius 2.000
+
+    // Predicates and circles from a diameter.
+    let near_square = Rectangle::new(0.1 * 3.0, 0.3)?;
+    println!(
+        "{}: square {}, within 1e-9 {}, aspect ratio {}",
+        near_square,
+        near_square.is_square(),
+        near_square.is_square_approx(1e-9),
+        Rectangle::new(16, 9)?.aspect_ratio()
+    ); // Output: Rectangle 0.30000000000000004 x 0.3: square false, within 1e-9 true, aspect ratio 1.7777777777777777
+    let from_seven = Circle::from_diameter(7)?;
+    println!(
+        "{}: unit {}, diameter {}, circumference {:.4}",
+        from_seven,
+        from_seven.is_unit(),
+        from_seven.diameter(),
+        from_seven.circumference()
+    ); // Output: Circle r=3.5: unit false, diameter 7, circumference 21.9911
+    let unit = Circle::new(1.0)?;
+    let (inscribed, inner_error) = unit.approximate(64);
+    let (_, outer_error) = unit.approximate_circumscribed(64);
+    println!(
+        "Quarter sector {:.4} ({:.4} from 90°), quarter segment {:.4}, {} off by {:.4}% / {:.4}%",
+        unit.sector_area(std::f64::consts::FRAC_PI_2),
+        unit.sector_area_deg(90.0),
+        unit.segment_area(std::f64::consts::FRAC_PI_2),
+        inscribed,
+        inner_error * 100.0,
+        outer_error * 100.0
+    ); // Output: Quarter sector 0.7854 (0.7854 from 90°), quarter segment 0.2854, RegularPolygon 64 x 0.09813534865483603 off by 0.1606% / 0.0804%
+
+    // Coordinates: hit testing, placed rectangles, transforms, polygons, centroids and drawings.
+    let origin = Point::new(0.0, 0.0);
+    let placed = PlacedRect::new(Point::new(1.0, 2.0), Rectangle::new(4.0, 2.0)?);
+    let other = PlacedRect::new(Point::new(2.0, 3.0), Rectangle::new(4.0, 4.0)?);
+    println!(
+        "Contains (4, 3): {}, overlap {}, intersects {}",
+        Rectangle::new(4.0, 3.0)?.contains(&Point::new(4.0, 3.0)),
+        placed.overlap_area(&other),
+        placed.intersects(&other)
+    ); // Output: Contains (4, 3): true, overlap 3, intersects true
+    let turned = placed.rotate90().flip_horizontal().flip_vertical();
+    println!("{:?} at {:?}, centroid {:?}", turned.rect, turned.origin, placed.centroid());
+    // Output: Rectangle { width: 2.0, length: 4.0 } at Point { x: 2.0, y: -5.0 }, centroid Point { x: 3.0, y: 3.0 }
+    let cornered = Triangle::from_points(origin, Point::new(4.0, 0.0), Point::new(0.0, 3.0))?;
+    println!(
+        "{} has Heron area {}, cross-product area {} and centroid {:?}",
+        cornered,
+        cornered.area(),
+        Triangle::area_from_points(origin, Point::new(4.0, 0.0), Point::new(0.0, 3.0)),
+        triangle.centroid()
+    );
+    let unit_square: FixedPolygon<4> = Rectangle::new(1.0, 1.0)?.into();
+    let l_polygon = Polygon::new(vec![
+        origin,
+        Point::new(2.0, 0.0),
+        Point::new(2.0, 1.0),
+        Point::new(1.0, 1.0),
+        Point::new(1.0, 2.0),
+        Point::new(0.0, 2.0),
+    ])?;
+    print_measurements(&l_polygon); // Output: Polygon { vertices: [...] } has area 3.0 and perimeter 8.0
+    let right_triangle = FixedPolygon::new([origin, Point::new(3.0, 0.0), Point::new(0.0, 4.0)])?;
+    println!(
+        "Unit square of {} vertices: area {}, triangle area {}, L of {} vertices centroid {:?}",
+        unit_square.vertices().len(),
+        unit_square.area(),
+        right_triangle.area(),
+        l_polygon.vertices().len(),
+        l_polygon.centroid()
+    ); // Output: Unit square of 4 vertices: area 1, triangle area 6, L of 6 vertices centroid Point { x: 0.8333333333333334, y: 0.8333333333333334 }
+    println!("{}", Rectangle::new(5u32, 3u32)?.render());
+    println!("{}", Circle::new(3u32)?.render());
+    print_bounding_box(&circle); // Output: Circle r=7 has area 153.938 inside a Rectangle 14 x 14 box of area 196.000
+
+    // Builders and positive dimensions both validate once, up front.
+    let built = RectangleBuilder::new().square(2.0).with_scale(1.5).build()?;
+    println!("Built {}", built); // Output: Built Rectangle 3 x 3
+    if let Err(err) = RectangleBuilder::new().width(3.0).build() {
+        println!("Builder rejected: {}", err); // Output: Builder rejected: length was not set
+    }
+    if let (Some(width), Some(radius)) = (PositiveF64::try_new(2.5), PositiveU32::try_new(2)) {
+        let valid_rect: ValidRect = Rectangle::from_positive(width, width);
+        let valid_circle: ValidCircle = Circle::from_positive(radius);
+        println!("{} + 1 = {}; {:.3} {:.3}", *width, *width + 1.0, valid_rect.area(), valid_circle.area());
+    } // Output: 2.5 + 1 = 3.5; 6.250 12.566
+    println!("PositiveF64 of 0: {:?}", PositiveF64::try_new(0.0)); // Output: PositiveF64 of 0: None
+
+    // Hashable dimensions: shapes built from `OrderedDim` can be map keys.
+    let repeated: Vec<Box<dyn Area>> = vec![
+        Box::new(Rectangle::new(2.0, 3.0)?),
+        Box::new(Circle::new(1.5)?),
+        Box::new(Rectangle::new(2.0, 3.0)?),
+    ];
+    let (two, three) = OrderedDim::try_new(2.0).zip(OrderedDim::try_new(3.0)).ok_or("2 and 3 are not NaN")?;
+    let door = ShapeKey::Rect(Rectangle::new(two, three)?);
+    let counts: HashMap<ShapeKey, usize> = count_duplicates(&repeated);
+    println!("{:?} appears {} times, {}", door, counts[&door], f64::from(two)); // Output: Rect(...) appears 2 times, 2
+
+    // Unit-tagged dimensions: the newtypes implement `Into<f64>`, so they plug into the shapes.
+    let room = Rectangle::new(Feet(10.0), Feet(12.0))?;
+    let pool = Circle::new(Meters(2.0))?;
+    let floor: Quantity<SquareFeet> = room.typed_area();
+    let pool_area: Quantity<SquareMeters> = pool.typed_area();
+    println!(
+        "{} is {:.4} m², {} is {:.4} ft², together {:.4} m²",
+        room,
+        room.area_in::<Meters>(),
+        pool,
+        pool.area_in::<Feet>(),
+        (floor.convert::<SquareMeters>() + pool_area).value()
+    ); // Output: Rectangle 10 ft x 12 ft is 11.1484 m², Circle r=2 m is 135.2633 ft², together 23.7147 m²
+    let hallway = Rectangle::new(Feet(3.0), Feet(10.0))?.typed_area();
+    println!(
+        "Floor plus hallway {}, minus hallway {}; {} is {:.4} ft",
+        floor + hallway,
+        floor - hallway,
+        Feet(10.0).to_meters(),
+        Meters(1.0).to_feet().0
+    ); // Output: Floor plus hallway 150 ft², minus hallway 90 ft²; 3.048 m is 3.2808 ft
+
+    // Solids.
+    print_volume(&Sphere::new(1.0)?); // Output: Volume of the provided Sphere { radius: 1.0 } is 4.1887902047863905
+    print_solid(&Cuboid::new(2, 3, 4)?); // Output: Cuboid { width: 2, length: 3, height: 4 } has volume 24.0000 and surface area 52.0000
+    print_solid(&Cylinder::new(Circle::new(2.0)?, 5.0)?);
+
+    // Exact integer areas, f32 precision and approximate equality.
+    print_exact_area(&Rectangle::new_exact(9_007_199_254_740_993u64, 3)?); // Output: ... is 27021597764222979
+    print_exact_area(&Square::new_exact(12u64)?); // Output: ... is 144
+    print_exact_area(&Circle32::new(1.5)?); // Output: ... is 7.0685835
+    let precise: f64 = Circle64::new(1.5)?.area_of();
+    let small: f32 = Rectangle32::new(0.1, 0.2)?.area_of();
+    let wide: f64 = Rectangle64::new(0.1, 0.2)?.area_of();
+    println!("{} {} {} {}", precise, small, wide, as_f64_area(&rect)); // Output: 7.0685834705770345 0.020000001 0.020000000000000004 50
+    let tenth = Circle::new(0.1)?;
+    let almost_tenth = Circle::new(0.3 - 0.2)?;
+    println!(
+        "Equal with ==: {}, within the default epsilon: {}",
+        tenth.area() == almost_tenth.area(),
+        tenth.approx_eq(&almost_tenth, DEFAULT_EPSILON)
+    ); // Output: Equal with ==: false, within the default epsilon: true
+    println!("{:?}", circle_f64.area_rounded(Rounding::Nearest)); // Output: Ok(43)
+    if let Err(err) = (1e10, 1e10).area_rounded(Rounding::Ceil) {
+        println!("Rounding failed: {}", err); // Output: Rounding failed: area 100000000000000000000 does not fit in a u64
+    }
+
+    // Defaults, cached and composite shapes.
+    let mut unit_squares: [Square<f64>; 2] = Default::default();
+    unit_squares[0] = Square::new(3.0)?;
+    let taken = std::mem::take(&mut unit_squares[0]);
+    println!("Took {}, left {}, default {}", taken, unit_squares[0], Rectangle::<i32>::default()); // Output: Took Square 3, left Square 1, default Rectangle 1 x 1
+    let mut cached = CachedShape::new(CountingSquare { side: 2.0, calls: Cell::new(0) });
+    println!("Cached area {} then {}", cached.area(), cached.area());
+    cached.get_mut().side = 3.0;
+    cached.invalidate();
+    println!("Area after invalidate: {}", cached.area()); // Output: Area after invalidate: 9
+    println!("{}", cached.get().describe()); // Output: Counting square of side 3 (area computed 2 times)
+    let figure = Rectangle::new(2.0, 3.0)? + Circle::new(1.0)? + Triangle::new(3.0, 4.0, 5.0)?;
+    let figure = figure + (Square::new(1.0)? + Square::new(2.0)?);
+    println!("{} has {} parts, area {:.3}", figure, figure.len(), figure.area()); // Output: ... has 5 parts, area 20.142
+
+    // Parsing shapes from text and arguments.
+    let parsed = parse::parse_shapes("# comment\nrect 10 5\n\ncircle 3.7\nsquare 2.5")?;
+    let parsed_rect: Rectangle<f64> = "rect 2 3".parse()?;
+    println!("{} parsed shapes, total area {:.3}, and {}", parsed.len(), total_area(&parsed), parsed_rect);
+    // Output: 3 parsed shapes, total area 99.258, and Rectangle 2 x 3
+    if let Err(err) = parse::parse_args(&["circle", "3", "hexagon", "1"]) {
+        println!("{}", err); // Output: unknown shape 'hexagon' (supported: rect, square, circle, triangle)
+    }
+
+    // `Shape` combines `Area`, `Perimeter` and `Debug` in one trait object, e.g. for tables.
+    let full_shapes: Vec<Box<dyn Shape>> = vec![
+        Box::new(Rectangle::new(3, 4)?),
+        Box::new(Circle::new(1.0)?),
+        Box::new(Triangle::new(3, 4, 5)?),
+        Box::new(Ellipse::new(5.0, 3.0)?),
+    ];
+    for shape in &full_shapes {
+        println!("{}", shape.summary()); // Output: Rectangle { width: 3, length: 4 }: area 12.00, perimeter 14.00 ...
+    }
+    println!("{}", comparison_table(&full_shapes));
+    println!("{}", comparison_table_with_precision(&full_shapes[..1], 0));
+
+    // A heterogeneous collection: the concrete types differ, but every element is a `dyn Area`.
+    let mut shapes: Vec<Box<dyn Area>> = vec![
+        Box::new(rect_f64),
+        Box::new(circle_f64),
+        Box::new(triangle_f64),
+    ];
+    let TotalArea(summed) = shapes.iter().sum();
+    println!("Total area {:.3} = {:.3} = {:.3}", total_area(&shapes), sum_areas(&shapes), summed); // Output: Total area 60.408 = 60.408 = 60.408
+    if let Some(stats) = area_stats(&shapes) {
+        println!(
+            "{} shapes: mean {:.3}, median {:.3}, min {:.3}, max {:.3}",
+            stats.count, stats.mean, stats.median, stats.min, stats.max
+        ); // Output: 3 shapes: mean 20.136, median 14.400, min 3.000, max 43.008
+    }
+    for rect in find_rectangles(&shapes) {
+        println!("Found a rectangle {} wide", rect.width()); // Output: Found a rectangle 4.5 wide
+    }
+    println!("Ranked by area: {:?}", rank_by(&shapes, |shape| shape.area())); // Output: Ranked by area: [2, 0, 1]
+    sort_by_area(&mut shapes);
+    if let Some(biggest) = largest_shape(&shapes) {
+        println!("Largest area: {:?}", biggest.area()); // Output: Largest area: 43.00840342764427
+    }
+    let mut collection: ShapeCollection = shapes.into_iter().collect();
+    collection.push(Box::new(Square::new(2)?));
+    for shape in &collection {
+        print_area_dyn(shape.as_ref());
+    }
+    println!("{} shapes, largest {:?}", collection.len(), collection.largest()); // Output: 4 shapes, largest Some(Circle { radius: 3.7 })
+
+    // Measured shapes count how many areas a sort computes.
+    let mut measured = Vec::new();
+    for side in [5, 4, 3, 2, 1] {
+        measured.push(MeasuredShape::new(Square::new(side)?));
+    }
+    sort_by_area(&mut measured);
+    println!("Sorting 5 shapes computed {} areas", measured.iter().map(|shape| shape.calls()).sum::<usize>()); // Output: Sorting 5 shapes computed 20 areas
+    measured.iter().for_each(|shape| shape.reset());
+    sort_by_area(&mut measured);
+    println!("Sorting them again computed {} areas", measured.iter().map(|shape| shape.calls()).sum::<usize>()); // Output: Sorting them again computed 8 areas
+    let shared = [AtomicMeasuredShape::new((2, 3)), AtomicMeasuredShape::new((4, 5))];
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| total_area(&shared));
+        }
+    });
+    shared[1].reset();
+    println!("Calls across threads: {} and {}", shared[0].calls(), shared[1].calls()); // Output: Calls across threads: 4 and 0
+
+    // Random shapes, reproducible for a given seed, summed in parallel and written in the
+    // binary format.
+    let mut rng = XorShift64::new(42);
+    let random = gen_shapes(1000, || rng.next_f64());
+    println!("{} random shapes with total area {:.2}", random.len(), total_area(&random)); // Output: 1000 random shapes with total area 63807.10
+    let many = gen_shapes(10_000, || rng.next_f64());
+    println!("Parallel total of {} shapes: {:.2}", many.len(), total_area_parallel(&many, 4)); // Output: Parallel total of 10000 shapes: 634405.02
+    let pair = [ShapeKind::Circ(Circle::new(1.0)?), ShapeKind::Rect(Rectangle::new(2.0, 3.0)?)];
+    let bytes = encode_all(&pair);
+    println!("{} shapes in {} bytes, decoded {:?}", pair.len(), bytes.len(), decode_all(&bytes)?); // Output: 2 shapes in 30 bytes, decoded [Circ(...), Rect(...)]
+    if let Err(err @ DecodeError::Truncated { .. }) = decode_all(&bytes[..20]) {
+        println!("Decoding failed: {}", err); // Output: Decoding failed: input ends 10 byte(s) too early
+    }
+
+    // JSON round trip (only with `cargo run --features serde`).
+    #[cfg(feature = "serde")]
+    {
+        let json = r#"[{"type":"circle","radius":3.5},{"type":"rectangle","width":2,"length":4}]"#;
+        let loaded = serialization::load_shapes(json)?;
+        let areas: Vec<f64> = loaded.iter().map(|shape| shape.area()).collect();
+        println!("Loaded areas {:.3?}", areas); // Output: Loaded areas [38.485, 8.000]
+        println!("Saved JSON: {}", serialization::save_shapes(&loaded)?); // Output: [{"type":"circle","radius":3.5},{"type":"rectangle","width":2.0,"length":4.0}]
+    }
+
+    // Enum dispatch vs trait objects over a million shapes. Timings depend on the machine
+    // and build profile (try `cargo run --release`); the enum is usually the faster one.
+    benchmark_dispatch(1_000_000)?;
+    // Output (release build, one run): enum dispatch (Vec<ShapeKind>): total area 3882479909 in 2.9ms
+    // Output (release build, one run): dyn dispatch (Vec<Box<dyn Area>>): total area 3882479909 in 3.8ms
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_area_eq;
+    use shapes::ShapeError;
+
+    fn mixed() -> Result<Vec<Box<dyn Area>>, ShapeError> {
+        // Areas 14.4, 43.0 and 3.0.
+        Ok(vec![
+            Box::new(Rectangle::new(4.5, 3.2)?),
+            Box::new(Circle::new(3.7)?),
+            Box::new(Triangle::new(2.5, 2.5, 3.0)?),
+        ])
+    }
+
+    #[test]
+    fn describe_shape_takes_the_name_from_the_caller() -> Result<(), ShapeError> {
+        let rect = Rectangle::new(10, 5)?;
+        let floor: &dyn Area = &rect;
+        assert_eq!(describe_shape(floor, "the floor"), "Area of the floor is 50");
+        let rect_f64 = Rectangle::new(4.5, 3.2)?;
+        assert_eq!(describe_shape(&rect_f64, &rect_f64.to_string()), "Area of Rectangle 4.5 x 3.2 is 14.4");
+        Ok(())
+    }
+
+    #[test]
+    fn overridden_describe_is_used_through_a_box() {
+        let boxed: Box<dyn Area> = Box::new(CountingSquare { side: 1.5, calls: Cell::new(0) });
+        assert_eq!(boxed.describe(), "Counting square of side 1.5 (area computed 0 times)");
+        assert_eq!(boxed.area(), 2.25);
+        assert_eq!(boxed.describe(), "Counting square of side 1.5 (area computed 1 times)");
+    }
+
+    #[test]
+    fn total_area_of_boxed_borrowed_and_owned_shapes() -> Result<(), ShapeError> {
+        let (rect, circle) = (Rectangle::new(10, 5)?, Circle::new(1)?);
+        let borrowed: Vec<&dyn Area> = vec![&rect, &circle];
+        assert_eq!(total_area(&borrowed), 50.0 + std::f64::consts::PI);
+        let boxed: Vec<Box<dyn Area>> = vec![Box::new(Rectangle::new(10, 5)?), Box::new(Circle::new(1)?)];
+        assert_eq!(total_area(&boxed), total_area(&borrowed));
+        assert_eq!(total_area(&[Rectangle::new(1.0, 2.0)?, Rectangle::new(3.0, 4.0)?]), 14.0);
+        assert_eq!(total_area::<Box<dyn Area>>(&[]), 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn parallel_total_matches_the_sequential_one() {
+        // Adding in a different order may change the last few bits, but no more.
+        let mut rng = XorShift64::new(42);
+        let many = gen_shapes(10_000, || rng.next_f64());
+        let sequential = total_area(&many);
+        for threads in [0, 1, 4, 7, 20_000] {
+            assert_area_eq!(total_area_parallel(&many, threads), sequential, sequential * 1e-12);
+        }
+        assert_eq!(total_area_parallel(&[], 4), 0.0);
+    }
+
+    #[test]
+    fn rank_by_leaves_the_shapes_in_place() -> Result<(), ShapeError> {
+        let shapes = mixed()?;
+        assert_eq!(rank_by(&shapes, |shape| shape.area()), [2, 0, 1]);
+        assert_eq!(rank_by(&shapes, |shape| -shape.area()), [1, 0, 2]);
+        // NaN keys compare as equal, so the stable sort keeps their order.
+        assert_eq!(rank_by(&shapes, |_| f64::NAN), [0, 1, 2]);
+        Ok(())
+    }
+
+    #[test]
+    fn sorting_and_the_largest_shape() -> Result<(), ShapeError> {
+        let mut shapes = mixed()?;
+        assert_eq!(largest_shape(&shapes).map(|shape| shape.area()), Some(shapes[1].area()));
+        assert!(largest_shape(&[]).is_none());
+        sort_by_area(&mut shapes);
+        let sorted: Vec<f64> = shapes.iter().map(|shape| shape.area()).collect();
+        assert!(sorted.is_sorted());
+        Ok(())
+    }
+
+    #[test]
+    fn sorting_computes_two_areas_per_comparison() -> Result<(), ShapeError> {
+        let mut measured = Vec::new();
+        for side in [5, 4, 3, 2, 1] {
+            measured.push(MeasuredShape::new(Square::new(side)?));
+        }
+        // At least n - 1 and at most n * (n - 1) / 2 comparisons for 5 shapes.
+        sort_by_area(&mut measured);
+        let calls: usize = measured.iter().map(|shape| shape.calls()).sum();
+        assert!(calls.is_multiple_of(2) && (8..=20).contains(&calls));
+        measured.iter().for_each(|shape| shape.reset());
+        // Already in order, every shape is still looked at.
+        sort_by_area(&mut measured);
+        assert!(measured.iter().all(|shape| shape.calls() >= 1));
+        Ok(())
+    }
+
+    #[test]
+    fn only_f64_rectangles_are_found() -> Result<(), ShapeError> {
+        let mut shapes = mixed()?;
+        shapes.push(Box::new(Rectangle::new(1, 2)?));
+        let found = find_rectangles(&shapes);
+        assert_eq!(found, [&Rectangle::new(4.5, 3.2)?]);
+        assert!(shapes[1].as_ref().as_any().downcast_ref::<Circle<f64>>().is_some());
+        Ok(())
+    }
 }
@@ -0,0 +1,104 @@
+// Wrappers that count how often `area` is called, for seeing how much work a generic
+// algorithm does. Like `CachedShape`, they need interior mutability, because `area` only
+// gets `&self`.
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::shapes::Area;
+
+// Counts calls with a `Cell`, which is cheap but not `Sync`: a `MeasuredShape` can't be
+// shared between threads.
+#[derive(Debug)]
+pub struct MeasuredShape<S: Area> {
+    shape: S,
+    calls: Cell<usize>,
+}
+
+impl<S: Area> MeasuredShape<S> {
+    pub fn new(shape: S) -> Self {
+        MeasuredShape {
+            shape,
+            calls: Cell::new(0),
+        }
+    }
+
+    // How many times `area` has been called since creation or the last `reset`.
+    pub fn calls(&self) -> usize {
+        self.calls.get()
+    }
+
+    pub fn reset(&self) {
+        self.calls.set(0);
+    }
+}
+
+impl<S: Area> Area for MeasuredShape<S> {
+    fn area(&self) -> f64 {
+        self.calls.set(self.calls.get() + 1);
+        self.shape.area()
+    }
+}
+
+// The thread-safe version: an `AtomicUsize` can be incremented through `&self` from any
+// thread, so this wrapper is `Send + Sync` whenever the wrapped shape is. Only the count
+// itself is synchronized, so `Relaxed` ordering is enough.
+#[derive(Debug)]
+pub struct AtomicMeasuredShape<S: Area> {
+    shape: S,
+    calls: AtomicUsize,
+}
+
+impl<S: Area> AtomicMeasuredShape<S> {
+    pub fn new(shape: S) -> Self {
+        AtomicMeasuredShape {
+            shape,
+            calls: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::Relaxed)
+    }
+
+    pub fn reset(&self) {
+        self.calls.store(0, Ordering::Relaxed);
+    }
+}
+
+impl<S: Area> Area for AtomicMeasuredShape<S> {
+    fn area(&self) -> f64 {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        self.shape.area()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+    use crate::shapes::{ShapeError, Square};
+
+    #[test]
+    fn counts_every_area_call_until_reset() -> Result<(), ShapeError> {
+        let shape = MeasuredShape::new(Square::new(2.0)?);
+        assert_eq!((shape.area(), shape.area()), (4.0, 4.0));
+        assert_eq!(shape.calls(), 2);
+        shape.reset();
+        assert_eq!(shape.calls(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn atomic_counts_are_shared_between_threads() {
+        let shared = [AtomicMeasuredShape::new((2, 3)), AtomicMeasuredShape::new((4, 5))];
+        thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| shared.iter().map(|shape| shape.area()).sum::<f64>());
+            }
+        });
+        assert!(shared.iter().all(|shape| shape.calls() == 4));
+        shared[0].reset();
+        assert_eq!((shared[0].calls(), shared[1].calls()), (0, 4));
+    }
+}
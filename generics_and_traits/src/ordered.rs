@@ -0,0 +1,139 @@
+// Hashable dimensions. `f64` implements neither `Eq` nor `Hash` (NaN isn't equal to
+// itself), so a `Rectangle<f64>` can't be a `HashMap` key. `OrderedDim` rules NaN out when
+// it is created, after which comparing and hashing the raw bits is sound.
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use crate::shapes::{Area, Circle, Rectangle};
+
+// A non-NaN `f64` with `Eq`, `Ord` and `Hash`.
+//
+// Zero is the one value with two bit patterns: `0.0` and `-0.0` compare equal as floats
+// but have different bits. `try_new` turns `-0.0` into `0.0`, so both are the same
+// dimension, hash identically and count as duplicates of each other.
+#[derive(Debug, Clone, Copy)]
+pub struct OrderedDim(f64);
+
+impl OrderedDim {
+    // `None` for NaN. Infinities are allowed here; the shape constructors reject them.
+    pub fn try_new(value: f64) -> Option<Self> {
+        // `-0.0 + 0.0` is `0.0`, and adding zero leaves every other value unchanged.
+        (!value.is_nan()).then_some(OrderedDim(value + 0.0))
+    }
+}
+
+impl From<OrderedDim> for f64 {
+    fn from(value: OrderedDim) -> f64 {
+        value.0
+    }
+}
+
+// Equality and hashing both use `to_bits`, which keeps them consistent with each other:
+// equal values always have equal hashes.
+impl PartialEq for OrderedDim {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
+}
+
+impl Eq for OrderedDim {}
+
+impl Hash for OrderedDim {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
+// `total_cmp` orders by value, and without NaN or `-0.0` it agrees with the bitwise `==`.
+impl Ord for OrderedDim {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl PartialOrd for OrderedDim {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// The shapes that can be counted by `count_duplicates`, with hashable dimensions.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub enum ShapeKey {
+    Rect(Rectangle<OrderedDim>),
+    Circ(Circle<OrderedDim>),
+}
+
+impl ShapeKey {
+    // The key for a trait object, found by downcasting (which needs the `'static` bound).
+    // `None` for shapes other than `Rectangle<f64>` and `Circle<f64>`.
+    pub fn of(shape: &(dyn Area + 'static)) -> Option<ShapeKey> {
+        let any = shape.as_any();
+        if let Some(rect) = any.downcast_ref::<Rectangle<f64>>() {
+            Some(ShapeKey::Rect(Rectangle::new(dim(rect.width()), dim(rect.length())).ok()?))
+        } else if let Some(circle) = any.downcast_ref::<Circle<f64>>() {
+            Some(ShapeKey::Circ(Circle::new(dim(circle.radius())).ok()?))
+        } else {
+            None
+        }
+    }
+}
+
+// Dimensions of a validated shape are never NaN, so the conversion can't fail.
+fn dim(value: f64) -> OrderedDim {
+    OrderedDim::try_new(value).expect("validated dimensions are not NaN")
+}
+
+// How often each distinct rectangle and circle occurs. Dimensions have to match exactly,
+// bit for bit; shapes that have no `ShapeKey` are left out of the count.
+pub fn count_duplicates(shapes: &[Box<dyn Area>]) -> HashMap<ShapeKey, usize> {
+    let mut counts = HashMap::new();
+    for key in shapes.iter().filter_map(|shape| ShapeKey::of(shape.as_ref())) {
+        *counts.entry(key).or_insert(0) += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::{ShapeError, Square};
+
+    #[test]
+    fn nan_is_rejected_and_zeros_are_one_value() {
+        assert_eq!(OrderedDim::try_new(f64::NAN), None);
+        assert_eq!(OrderedDim::try_new(-0.0), OrderedDim::try_new(0.0));
+        assert!(OrderedDim::try_new(2.0) < OrderedDim::try_new(3.0));
+        assert_eq!(OrderedDim::try_new(2.5).map(f64::from), Some(2.5));
+    }
+
+    #[test]
+    fn shapes_with_ordered_dims_are_map_keys() -> Result<(), ShapeError> {
+        let (two, three) = (dim(2.0), dim(3.0));
+        let mut labels = HashMap::new();
+        labels.insert(Rectangle::new(two, three)?, "door");
+        labels.insert(Rectangle::new(three, three)?, "window");
+        assert_eq!(labels.get(&Rectangle::new(two, three)?), Some(&"door"));
+        assert_eq!(labels.get(&Rectangle::new(three, two)?), None);
+        Ok(())
+    }
+
+    #[test]
+    fn count_duplicates_keys_by_exact_dimensions() -> Result<(), ShapeError> {
+        let repeated: Vec<Box<dyn Area>> = vec![
+            Box::new(Rectangle::new(2.0, 3.0)?),
+            Box::new(Circle::new(1.5)?),
+            Box::new(Rectangle::new(2.0, 3.0)?),
+            Box::new(Rectangle::new(3.0, 2.0)?),
+            Box::new(Square::new(2.0)?),
+        ];
+        let counts = count_duplicates(&repeated);
+        // The rotated rectangle is its own key, and the square has none.
+        assert_eq!(counts.get(&ShapeKey::Rect(Rectangle::new(dim(2.0), dim(3.0))?)), Some(&2));
+        assert_eq!(counts.get(&ShapeKey::Circ(Circle::new(dim(1.5))?)), Some(&1));
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts.values().sum::<usize>(), 4);
+        Ok(())
+    }
+}
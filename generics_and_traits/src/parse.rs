@@ -0,0 +1,247 @@
+// Parsing shapes from a compact text format: a shape name followed by its dimensions,
+// separated by whitespace, e.g. "rect 10 5", "circle 3.7" or "triangle 3 4 5".
+use std::fmt;
+use std::str::FromStr;
+
+use crate::shapes::{Area, Circle, Rectangle, ShapeError, Square, Triangle};
+
+// Every way a line of shape text can fail to parse.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseShapeError {
+    // Nothing but whitespace.
+    Empty,
+    // A name that isn't one of the supported shapes.
+    UnknownShape(String),
+    // A different shape than the one being parsed, e.g. "circle 3" parsed as a `Rectangle`.
+    WrongShape { expected: &'static str, found: String },
+    // Too few or too many dimensions, e.g. "rect 10".
+    WrongArgCount { shape: &'static str, expected: usize, found: usize },
+    // A dimension that isn't a number, e.g. "rect ten 5".
+    InvalidNumber(String),
+    // Numbers that the shape's constructor rejects, e.g. "circle -1".
+    InvalidShape(ShapeError),
+}
+
+impl fmt::Display for ParseShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseShapeError::Empty => write!(f, "empty shape definition"),
+            ParseShapeError::UnknownShape(name) => write!(
+                f,
+                "unknown shape '{}' (supported: {})",
+                name,
+                SUPPORTED_SHAPES.join(", ")
+            ),
+            ParseShapeError::WrongShape { expected, found } => {
+                write!(f, "expected a {}, found '{}'", expected, found)
+            }
+            ParseShapeError::WrongArgCount { shape, expected, found } => write!(
+                f,
+                "{} takes {} number(s), found {}",
+                shape, expected, found
+            ),
+            ParseShapeError::InvalidNumber(token) => write!(f, "'{}' is not a number", token),
+            ParseShapeError::InvalidShape(err) => write!(f, "invalid shape: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ParseShapeError {}
+
+// Lets `?` turn a constructor's `ShapeError` into a `ParseShapeError`.
+impl From<ShapeError> for ParseShapeError {
+    fn from(err: ShapeError) -> Self {
+        ParseShapeError::InvalidShape(err)
+    }
+}
+
+// The shape names understood by `parse_shapes`, used in error messages.
+pub const SUPPORTED_SHAPES: [&str; 4] = ["rect", "square", "circle", "triangle"];
+
+// Split a definition into the shape name and its arguments, checking the name is one of
+// `names` (the first entry is the canonical name used in errors), and parse exactly `N` numbers.
+// The const generic `N` lets each caller destructure the result as a fixed-size array.
+fn parse_numbers<const N: usize>(
+    s: &str,
+    names: &[&'static str],
+) -> Result<[f64; N], ParseShapeError> {
+    let mut tokens = s.split_whitespace();
+    let name = tokens.next().ok_or(ParseShapeError::Empty)?;
+    if !names.contains(&name) {
+        return Err(ParseShapeError::WrongShape {
+            expected: names[0],
+            found: name.to_string(),
+        });
+    }
+    let args: Vec<&str> = tokens.collect();
+    if args.len() != N {
+        return Err(ParseShapeError::WrongArgCount {
+            shape: names[0],
+            expected: N,
+            found: args.len(),
+        });
+    }
+    let mut values = [0.0; N];
+    for (value, arg) in values.iter_mut().zip(&args) {
+        *value = arg
+            .parse()
+            .map_err(|_| ParseShapeError::InvalidNumber(arg.to_string()))?;
+    }
+    Ok(values)
+}
+
+// Implementing `FromStr` enables `"rect 10 5".parse::<Rectangle<f64>>()`.
+// The numbers still go through the validating constructors.
+impl FromStr for Rectangle<f64> {
+    type Err = ParseShapeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [width, length] = parse_numbers(s, &["rect", "rectangle"])?;
+        Ok(Rectangle::new(width, length)?)
+    }
+}
+
+impl FromStr for Square<f64> {
+    type Err = ParseShapeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [side] = parse_numbers(s, &["square"])?;
+        Ok(Square::new(side)?)
+    }
+}
+
+impl FromStr for Circle<f64> {
+    type Err = ParseShapeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [radius] = parse_numbers(s, &["circle"])?;
+        Ok(Circle::new(radius)?)
+    }
+}
+
+impl FromStr for Triangle<f64> {
+    type Err = ParseShapeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [a, b, c] = parse_numbers(s, &["triangle"])?;
+        Ok(Triangle::new(a, b, c)?)
+    }
+}
+
+// Parse a single definition of any supported shape.
+// The first word picks which `FromStr` impl handles the line.
+fn parse_shape(line: &str) -> Result<Box<dyn Area>, ParseShapeError> {
+    let name = line.split_whitespace().next().ok_or(ParseShapeError::Empty)?;
+    Ok(match name {
+        "rect" | "rectangle" => Box::new(line.parse::<Rectangle<f64>>()?),
+        "square" => Box::new(line.parse::<Square<f64>>()?),
+        "circle" => Box::new(line.parse::<Circle<f64>>()?),
+        "triangle" => Box::new(line.parse::<Triangle<f64>>()?),
+        _ => return Err(ParseShapeError::UnknownShape(name.to_string())),
+    })
+}
+
+// Parse one shape per line into a heterogeneous collection.
+// Blank lines and lines starting with `#` are skipped; the first bad line stops parsing.
+pub fn parse_shapes(lines: &str) -> Result<Vec<Box<dyn Area>>, ParseShapeError> {
+    lines
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(parse_shape)
+        .collect()
+}
+
+// How many numbers each shape name takes.
+fn arity(name: &str) -> Option<usize> {
+    match name {
+        "rect" | "rectangle" => Some(2),
+        "square" | "circle" => Some(1),
+        "triangle" => Some(3),
+        _ => None,
+    }
+}
+
+// Parse command-line style arguments, where several shapes follow each other in one flat
+// list: `circle 3.5 rect 2 4`. Each name consumes as many of the following arguments as
+// that shape takes, and the group is then parsed exactly like a line of `parse_shapes`.
+pub fn parse_args<S: AsRef<str>>(args: &[S]) -> Result<Vec<Box<dyn Area>>, ParseShapeError> {
+    let mut shapes: Vec<Box<dyn Area>> = Vec::new();
+    let mut rest = args;
+    while let Some((name, tail)) = rest.split_first() {
+        let name = name.as_ref();
+        let count = arity(name).ok_or_else(|| ParseShapeError::UnknownShape(name.to_string()))?;
+        // A trailing shape with too few numbers is reported with the numbers it did get.
+        let (numbers, tail) = tail.split_at(count.min(tail.len()));
+        let line = numbers
+            .iter()
+            .fold(name.to_string(), |line, number| line + " " + number.as_ref());
+        shapes.push(parse_shape(&line)?);
+        rest = tail;
+    }
+    Ok(shapes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn areas(shapes: &[Box<dyn Area>]) -> Vec<f64> {
+        shapes.iter().map(|shape| shape.area()).collect()
+    }
+
+    #[test]
+    fn parses_lines_skipping_blanks_and_comments() -> Result<(), ParseShapeError> {
+        let definitions = "
+            # A few shapes in the compact text format
+            rect 10 5
+            circle 1
+
+            triangle 3 4 5
+            square 2.5
+        ";
+        let parsed = parse_shapes(definitions)?;
+        assert_eq!(areas(&parsed), [50.0, std::f64::consts::PI, 6.0, 6.25]);
+        let rect: Rectangle<f64> = "rect 2 3".parse()?;
+        assert_eq!(rect, Rectangle::new(2.0, 3.0)?);
+        Ok(())
+    }
+
+    #[test]
+    fn every_kind_of_error_is_reported() {
+        let bad_inputs = [
+            ("hexagon 3", ParseShapeError::UnknownShape("hexagon".to_string())),
+            ("circle 1 2", ParseShapeError::WrongArgCount { shape: "circle", expected: 1, found: 2 }),
+            ("rect ten 5", ParseShapeError::InvalidNumber("ten".to_string())),
+            ("circle -1", ParseShapeError::InvalidShape(ShapeError::NegativeDimension("radius"))),
+        ];
+        for (input, err) in bad_inputs {
+            assert_eq!(parse_shapes(input).err(), Some(err));
+        }
+        assert_eq!(
+            "circle 3".parse::<Rectangle<f64>>(),
+            Err(ParseShapeError::WrongShape { expected: "rect", found: "circle".to_string() })
+        );
+        assert_eq!("".parse::<Circle<f64>>(), Err(ParseShapeError::Empty));
+        assert_eq!(
+            ParseShapeError::UnknownShape("hexagon".to_string()).to_string(),
+            "unknown shape 'hexagon' (supported: rect, square, circle, triangle)"
+        );
+    }
+
+    #[test]
+    fn parses_a_flat_argument_list() -> Result<(), ParseShapeError> {
+        let from_args = parse_args(&["circle", "1", "rect", "2", "4"])?;
+        assert_eq!(areas(&from_args), [std::f64::consts::PI, 8.0]);
+        let bad_args: [(&[&str], ParseShapeError); 4] = [
+            (&["rect", "2"], ParseShapeError::WrongArgCount { shape: "rect", expected: 2, found: 1 }),
+            (&["circle", "3", "hexagon", "1"], ParseShapeError::UnknownShape("hexagon".to_string())),
+            (&["rect", "2", "x"], ParseShapeError::InvalidNumber("x".to_string())),
+            (&["4"], ParseShapeError::UnknownShape("4".to_string())),
+        ];
+        for (args, err) in bad_args {
+            assert_eq!(parse_args(args).err(), Some(err));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,215 @@
+// Polygons given by their vertices.
+use crate::geometry::{Centroid, Point};
+use crate::shapes::{Area, Perimeter, Rectangle, ShapeError};
+
+// A polygon with exactly `N` vertices, fixed at compile time by the const generic.
+// The vertices live in an array, so no heap allocation is needed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedPolygon<const N: usize> {
+    vertices: [Point<f64>; N],
+}
+
+impl<const N: usize> FixedPolygon<N> {
+    // Evaluated once per `N` that is actually used. Naming it in `new` turns
+    // `FixedPolygon::<2>::new(..)` into a compile error instead of a runtime one.
+    const AT_LEAST_THREE_VERTICES: () = assert!(N >= 3, "a polygon needs at least 3 vertices");
+
+    // The vertices are taken in order around the polygon, clockwise or counter-clockwise.
+    // Every coordinate must be finite; a polygon whose vertices all lie on a line is
+    // allowed and simply has zero area.
+    pub fn new(vertices: [Point<f64>; N]) -> Result<Self, ShapeError> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::AT_LEAST_THREE_VERTICES;
+        check_vertices(&vertices)?;
+        Ok(FixedPolygon { vertices })
+    }
+
+    pub fn vertices(&self) -> &[Point<f64>; N] {
+        &self.vertices
+    }
+}
+
+// Each edge as a pair of consecutive vertices, including the closing edge from the last
+// vertex back to the first.
+fn edges(vertices: &[Point<f64>]) -> impl Iterator<Item = (Point<f64>, Point<f64>)> + '_ {
+    let next = vertices.iter().cycle().skip(1);
+    vertices.iter().copied().zip(next.copied())
+}
+
+// The shoelace formula: half the absolute sum of the cross products of consecutive
+// vertices. Walking the vertices the other way round flips the sign of the sum,
+// which the absolute value removes, so the order direction doesn't matter.
+fn shoelace_area(vertices: &[Point<f64>]) -> f64 {
+    let doubled: f64 = edges(vertices).map(|(p, q)| p.x * q.y - q.x * p.y).sum();
+    doubled.abs() / 2.0
+}
+
+// The area-weighted centroid from the same cross products as the shoelace formula:
+// every edge contributes a triangle with the origin, weighted by its signed area.
+// If the vertices are all collinear there is no area to weight by, so the plain
+// average of the vertices is used instead.
+fn shoelace_centroid(vertices: &[Point<f64>]) -> Point<f64> {
+    let (mut doubled_area, mut x, mut y) = (0.0, 0.0, 0.0);
+    for (p, q) in edges(vertices) {
+        let cross = p.x * q.y - q.x * p.y;
+        doubled_area += cross;
+        x += (p.x + q.x) * cross;
+        y += (p.y + q.y) * cross;
+    }
+    if doubled_area == 0.0 {
+        let count = vertices.len() as f64;
+        let sum_x: f64 = vertices.iter().map(|p| p.x).sum();
+        let sum_y: f64 = vertices.iter().map(|p| p.y).sum();
+        return Point::new(sum_x / count, sum_y / count);
+    }
+    // Cx = Σ (x_i + x_i+1) * cross_i / (6A), with A = doubled_area / 2.
+    Point::new(x / (3.0 * doubled_area), y / (3.0 * doubled_area))
+}
+
+// Every vertex must have finite coordinates.
+fn check_vertices(vertices: &[Point<f64>]) -> Result<(), ShapeError> {
+    if vertices.iter().any(|p| !p.x.is_finite() || !p.y.is_finite()) {
+        Err(ShapeError::NonFiniteDimension("vertex"))
+    } else {
+        Ok(())
+    }
+}
+
+impl<const N: usize> Area for FixedPolygon<N> {
+    fn area(&self) -> f64 {
+        shoelace_area(&self.vertices)
+    }
+}
+
+// A rectangle becomes the four corners of [0, width] x [0, length], the same placement
+// `Contains` assumes.
+impl From<Rectangle<f64>> for FixedPolygon<4> {
+    fn from(rect: Rectangle<f64>) -> Self {
+        let (w, l) = (rect.width(), rect.length());
+        FixedPolygon {
+            vertices: [
+                Point::new(0.0, 0.0),
+                Point::new(w, 0.0),
+                Point::new(w, l),
+                Point::new(0.0, l),
+            ],
+        }
+    }
+}
+
+// A polygon with any number of vertices, decided at runtime. The vertices are taken in
+// order around the boundary, clockwise or counter-clockwise. The polygon is assumed to be
+// simple: for a self-intersecting one such as a bow tie, the shoelace formula lets the
+// oppositely wound parts cancel out, so `area` is not the area it visibly covers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon {
+    vertices: Vec<Point<f64>>,
+}
+
+impl Polygon {
+    // At least three vertices with finite coordinates are required.
+    pub fn new(vertices: Vec<Point<f64>>) -> Result<Self, ShapeError> {
+        if vertices.len() < 3 {
+            // A closed polygon has as many sides as vertices.
+            return Err(ShapeError::TooFewSides(vertices.len() as u32));
+        }
+        check_vertices(&vertices)?;
+        Ok(Polygon { vertices })
+    }
+
+    pub fn vertices(&self) -> &[Point<f64>] {
+        &self.vertices
+    }
+}
+
+impl Area for Polygon {
+    fn area(&self) -> f64 {
+        shoelace_area(&self.vertices)
+    }
+}
+
+impl Centroid for Polygon {
+    fn centroid(&self) -> Point<f64> {
+        shoelace_centroid(&self.vertices)
+    }
+}
+
+impl<const N: usize> Centroid for FixedPolygon<N> {
+    fn centroid(&self) -> Point<f64> {
+        shoelace_centroid(&self.vertices)
+    }
+}
+
+// The sum of the edge lengths, including the edge that closes the polygon.
+impl Perimeter for Polygon {
+    fn perimeter(&self) -> f64 {
+        edges(&self.vertices)
+            .map(|(p, q)| (q.x - p.x).hypot(q.y - p.y))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::Triangle;
+
+    // A 2 x 2 square with a 1 x 1 corner cut out.
+    fn l_shape() -> Vec<Point<f64>> {
+        vec![
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 2.0),
+            Point::new(0.0, 2.0),
+        ]
+    }
+
+    #[test]
+    fn fixed_polygon_area_ignores_the_direction() -> Result<(), ShapeError> {
+        let unit_square: FixedPolygon<4> = Rectangle::new(1.0, 1.0)?.into();
+        assert_eq!(unit_square.area(), 1.0);
+        let mut reversed = *unit_square.vertices();
+        reversed.reverse();
+        assert_eq!(FixedPolygon::new(reversed)?.area(), 1.0);
+        // The 3-4-5 right triangle agrees with Heron's formula.
+        let right_triangle =
+            FixedPolygon::new([Point::new(0.0, 0.0), Point::new(3.0, 0.0), Point::new(0.0, 4.0)])?;
+        assert_eq!(right_triangle.area(), Triangle::new(3.0, 4.0, 5.0)?.area());
+        let infinite = [Point::new(0.0, 0.0), Point::new(f64::INFINITY, 0.0), Point::new(0.0, 1.0)];
+        assert_eq!(FixedPolygon::new(infinite), Err(ShapeError::NonFiniteDimension("vertex")));
+        Ok(())
+    }
+
+    #[test]
+    fn polygon_area_and_perimeter() -> Result<(), ShapeError> {
+        let l_polygon = Polygon::new(l_shape())?;
+        assert_eq!((l_polygon.area(), l_polygon.perimeter()), (3.0, 8.0));
+        let mut clockwise = l_shape();
+        clockwise.reverse();
+        assert_eq!(Polygon::new(clockwise)?.area(), 3.0);
+        let two = vec![Point::new(0.0, 0.0), Point::new(1.0, 1.0)];
+        assert_eq!(Polygon::new(two), Err(ShapeError::TooFewSides(2)));
+        Ok(())
+    }
+
+    #[test]
+    fn centroids() -> Result<(), ShapeError> {
+        let offset_square = Polygon::new(vec![
+            Point::new(2.0, 3.0),
+            Point::new(4.0, 3.0),
+            Point::new(4.0, 5.0),
+            Point::new(2.0, 5.0),
+        ])?;
+        assert_eq!(offset_square.centroid(), Point::new(3.0, 4.0));
+        let l_center = Polygon::new(l_shape())?.centroid();
+        assert!((l_center.x - 5.0 / 6.0).abs() < 1e-12 && (l_center.y - 5.0 / 6.0).abs() < 1e-12);
+        // Collinear vertices fall back to the average of the vertices.
+        let line = Polygon::new(vec![Point::new(0.0, 0.0), Point::new(1.0, 0.0), Point::new(5.0, 0.0)])?;
+        assert_eq!(line.centroid(), Point::new(2.0, 0.0));
+        let unit_square: FixedPolygon<4> = Rectangle::new(1.0, 1.0)?.into();
+        assert_eq!(unit_square.centroid(), Point::new(0.5, 0.5));
+        Ok(())
+    }
+}
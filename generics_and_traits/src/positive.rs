@@ -0,0 +1,94 @@
+// Dimension types that can only hold valid values. Where `Rectangle::new` checks its
+// arguments at runtime on every call, a `PositiveF64` is checked once when it is created;
+// after that it can't be zero, negative or NaN, so a shape built from them can't be either.
+use std::ops::Deref;
+
+use crate::shapes::{Circle, Rectangle};
+
+// The smallest value a `PositiveF64` accepts: the square root of the smallest normal
+// `f64`. Anything smaller would let the product of two dimensions (an area) underflow
+// to 0.0, which would break the "area is positive" guarantee.
+const MIN_POSITIVE_DIMENSION: f64 = 1.4916681462400413e-154;
+
+// A finite `f64` greater than zero.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct PositiveF64(f64);
+
+impl PositiveF64 {
+    // `None` for zero, negative, non-finite and vanishingly small values.
+    pub fn try_new(value: f64) -> Option<Self> {
+        (value.is_finite() && value >= MIN_POSITIVE_DIMENSION).then_some(PositiveF64(value))
+    }
+}
+
+// A `u32` greater than zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PositiveU32(u32);
+
+impl PositiveU32 {
+    pub fn try_new(value: u32) -> Option<Self> {
+        (value > 0).then_some(PositiveU32(value))
+    }
+}
+
+// `Deref` gives read access to the wrapped number, e.g. `*side + 1.0`, without any way
+// to change it.
+impl Deref for PositiveF64 {
+    type Target = f64;
+
+    fn deref(&self) -> &f64 {
+        &self.0
+    }
+}
+
+impl Deref for PositiveU32 {
+    type Target = u32;
+
+    fn deref(&self) -> &u32 {
+        &self.0
+    }
+}
+
+// `Into<f64>` is what the generic shapes require, so `Rectangle<PositiveF64>` gets every
+// existing impl (`Area`, `Perimeter`, `Scale`, ...) for free.
+impl From<PositiveF64> for f64 {
+    fn from(value: PositiveF64) -> f64 {
+        value.0
+    }
+}
+
+impl From<PositiveU32> for f64 {
+    fn from(value: PositiveU32) -> f64 {
+        value.0.into()
+    }
+}
+
+// Shapes whose dimensions are valid by construction.
+pub type ValidRect = Rectangle<PositiveF64>;
+pub type ValidCircle = Circle<PositiveU32>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::Area;
+
+    #[test]
+    fn invalid_values_are_rejected() {
+        for value in [0.0, -2.5, f64::NAN, f64::INFINITY, 1e-300] {
+            assert_eq!(PositiveF64::try_new(value), None);
+        }
+        assert_eq!(PositiveU32::try_new(0), None);
+        assert_eq!(PositiveF64::try_new(2.5).map(|width| *width + 1.0), Some(3.5));
+    }
+
+    #[test]
+    fn the_smallest_dimensions_still_give_a_positive_area() {
+        let smallest = PositiveF64::try_new(MIN_POSITIVE_DIMENSION);
+        assert!(smallest.is_some());
+        assert_eq!(PositiveF64::try_new(MIN_POSITIVE_DIMENSION / 2.0), None);
+        if let Some(side) = smallest {
+            let tiny: ValidRect = Rectangle::from_positive(side, side);
+            assert!(tiny.area() > 0.0);
+        }
+    }
+}
@@ -0,0 +1,112 @@
+// Random shapes. `gen_shapes` takes its randomness from a closure instead of owning a
+// generator, so the caller decides where the numbers come from: a real RNG, or a fixed
+// sequence that makes the output reproducible.
+use crate::shapes::{Area, Circle, Rectangle};
+
+// Dimensions are kept within these bounds, so every generated shape is valid.
+const MIN_DIMENSION: f64 = 0.1;
+const MAX_DIMENSION: f64 = 10.0;
+
+// `rng` should return values in [0, 1). Each shape consumes one value to pick its kind
+// (below 0.5 is a rectangle, otherwise a circle) and one per dimension, scaled to
+// [0, 10) and clamped to [0.1, 10]. Out-of-range values, including negatives and NaN,
+// are clamped too, so whatever the closure returns, the dimensions stay strictly positive.
+// The boxes are `Send + Sync`, so the generated shapes can be shared between threads.
+pub fn gen_shapes(count: usize, mut rng: impl FnMut() -> f64) -> Vec<Box<dyn Area + Send + Sync>> {
+    // `clamp` passes NaN through unchanged, so NaN is mapped to the minimum separately.
+    let mut dimension = || {
+        let value = rng() * MAX_DIMENSION;
+        if value.is_nan() {
+            MIN_DIMENSION
+        } else {
+            value.clamp(MIN_DIMENSION, MAX_DIMENSION)
+        }
+    };
+    (0..count)
+        .map(|_| -> Box<dyn Area + Send + Sync> {
+            // The kind is drawn through the same closure, scaled like a dimension.
+            if dimension() < MAX_DIMENSION / 2.0 {
+                let (width, length) = (dimension(), dimension());
+                Box::new(Rectangle::new(width, length).expect("clamped dimensions are valid"))
+            } else {
+                Box::new(Circle::new(dimension()).expect("clamped dimensions are valid"))
+            }
+        })
+        .collect()
+}
+
+// The splitmix64 increment, 2^64 divided by the golden ratio. `XorShift64::new` adds it to
+// the seed before scrambling.
+const SPLITMIX_GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+// A tiny xorshift generator (Marsaglia, 2003): fast and dependency-free, but not suitable
+// for anything security-related.
+pub struct XorShift64 {
+    state: u64,
+}
+
+impl XorShift64 {
+    // The seed is scrambled by one step of splitmix64, so small seeds like 0, 1 and 2 start
+    // from unrelated states. That step never maps two seeds to the same state. An all-zero
+    // state would make xorshift return zero forever, so the one seed that scrambles to zero
+    // starts from `SPLITMIX_GAMMA` instead.
+    pub fn new(seed: u64) -> Self {
+        let mut z = seed.wrapping_add(SPLITMIX_GAMMA);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        let state = z ^ (z >> 31);
+        XorShift64 { state: if state == 0 { SPLITMIX_GAMMA } else { state } }
+    }
+
+    // A uniformly distributed value in [0, 1), built from the top 53 bits of the state
+    // (exactly the precision of an `f64` mantissa).
+    pub fn next_f64(&mut self) -> f64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        (self.state >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_fixed_sequence_gives_fixed_shapes() {
+        // 0.2 picks a rectangle of 3 x 4, then 0.7 a circle of radius 5.
+        let mut sequence = [0.2, 0.3, 0.4, 0.7, 0.5].into_iter().cycle();
+        let fixed = gen_shapes(2, || sequence.next().unwrap_or_default());
+        let areas: Vec<f64> = fixed.iter().map(|shape| shape.area()).collect();
+        assert_eq!(areas, [12.0, 25.0 * std::f64::consts::PI]);
+    }
+
+    #[test]
+    fn out_of_range_values_are_clamped() {
+        for value in [-1.0, f64::NAN, 2.0] {
+            let clamped = gen_shapes(1, || value);
+            assert!(clamped[0].area() > 0.0);
+        }
+        assert_eq!(format!("{:?}", gen_shapes(1, || -1.0)), "[Rectangle { width: 0.1, length: 0.1 }]");
+    }
+
+    #[test]
+    fn seeded_generators_repeat_and_stay_in_range() {
+        let (mut first, mut second) = (XorShift64::new(42), XorShift64::new(42));
+        for _ in 0..1000 {
+            let value = first.next_f64();
+            assert_eq!(value, second.next_f64());
+            assert!((0.0..1.0).contains(&value));
+        }
+    }
+
+    #[test]
+    fn distinct_seeds_give_distinct_streams() {
+        // Neighbouring seeds start different streams, and the seed that scrambles to zero
+        // still produces non-zero values.
+        assert_ne!(XorShift64::new(0).next_f64(), XorShift64::new(1).next_f64());
+        assert_ne!(XorShift64::new(1).next_f64(), XorShift64::new(2).next_f64());
+        let mixes_to_zero = 0u64.wrapping_sub(SPLITMIX_GAMMA);
+        assert_ne!(XorShift64::new(mixes_to_zero).next_f64(), 0.0);
+    }
+}
@@ -0,0 +1,98 @@
+// ASCII-art rendering of shapes with integer dimensions, one character per unit.
+use crate::shapes::{Circle, Rectangle, Square};
+
+// Rows wider than this are cut off and end in "..." to show they were truncated.
+const MAX_COLUMNS: usize = 60;
+
+pub trait Render {
+    // The drawing as lines separated by '\n', without a trailing newline.
+    fn render(&self) -> String;
+}
+
+// Cut a row down to `MAX_COLUMNS` characters, marking the cut.
+fn truncate_row(row: String) -> String {
+    if row.chars().count() > MAX_COLUMNS {
+        let kept: String = row.chars().take(MAX_COLUMNS).collect();
+        kept + "..."
+    } else {
+        row
+    }
+}
+
+// A filled `width` x `height` block of '#'.
+fn render_block(width: u32, height: u32) -> String {
+    let row = truncate_row("#".repeat(width as usize));
+    vec![row; height as usize].join("\n")
+}
+
+// `width` columns by `length` rows.
+impl Render for Rectangle<u32> {
+    fn render(&self) -> String {
+        render_block(self.width(), self.length())
+    }
+}
+
+impl Render for Square<u32> {
+    fn render(&self) -> String {
+        render_block(self.side(), self.side())
+    }
+}
+
+// The outline of the circle, drawn with the midpoint circle algorithm: walk one octant
+// pixel by pixel, using an integer error term to decide when to step inwards, and mirror
+// each pixel into the other seven octants. Character cells are taller than they are wide,
+// so the result looks slightly stretched vertically.
+impl Render for Circle<u32> {
+    fn render(&self) -> String {
+        let r = self.radius() as i64;
+        let size = (2 * r + 1) as usize;
+        let mut grid = vec![vec![' '; size]; size];
+        let (mut x, mut y, mut err) = (r, 0, 1 - r);
+        while x >= y {
+            let octants = [(x, y), (y, x), (-y, x), (-x, y), (-x, -y), (-y, -x), (y, -x), (x, -y)];
+            for (dx, dy) in octants {
+                grid[(r + dy) as usize][(r + dx) as usize] = '#';
+            }
+            y += 1;
+            if err < 0 {
+                err += 2 * y + 1;
+            } else {
+                x -= 1;
+                err += 2 * (y - x) + 1;
+            }
+        }
+        grid.into_iter()
+            .map(|row| truncate_row(row.into_iter().collect::<String>().trim_end().to_string()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::ShapeError;
+
+    #[test]
+    fn rectangles_and_squares_are_filled_blocks() -> Result<(), ShapeError> {
+        assert_eq!(Rectangle::new(5u32, 3u32)?.render(), "#####\n#####\n#####");
+        assert_eq!(Square::new(1u32)?.render(), "#");
+        assert_eq!(Square::new(2u32)?.render(), "##\n##");
+        Ok(())
+    }
+
+    #[test]
+    fn wide_rows_are_truncated() -> Result<(), ShapeError> {
+        let wide = Rectangle::new(100u32, 1u32)?.render();
+        assert_eq!(wide, "#".repeat(MAX_COLUMNS) + "...");
+        assert_eq!(Rectangle::new(60u32, 1u32)?.render(), "#".repeat(60));
+        Ok(())
+    }
+
+    #[test]
+    fn circles_are_outlines() -> Result<(), ShapeError> {
+        let expected = ["  ###", " #   #", "#     #", "#     #", "#     #", " #   #", "  ###"];
+        assert_eq!(Circle::new(3u32)?.render(), expected.join("\n"));
+        Ok(())
+    }
+}
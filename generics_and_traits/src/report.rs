@@ -0,0 +1,141 @@
+// A plain-text table comparing the shapes of a collection side by side.
+use crate::shapes::Shape;
+
+// Decimal places used by `comparison_table`.
+pub const DEFAULT_PRECISION: usize = 2;
+
+// `comparison_table_with_precision` with `DEFAULT_PRECISION` decimals.
+pub fn comparison_table(shapes: &[Box<dyn Shape>]) -> String {
+    comparison_table_with_precision(shapes, DEFAULT_PRECISION)
+}
+
+// One row per shape with its index, description, area, perimeter and share of the total
+// area, followed by a totals row. Every column is as wide as its longest cell; the text
+// columns are left-aligned and the numeric ones right-aligned.
+pub fn comparison_table_with_precision(shapes: &[Box<dyn Shape>], precision: usize) -> String {
+    let total_area: f64 = shapes.iter().map(|shape| shape.area()).sum();
+    let total_perimeter: f64 = shapes.iter().map(|shape| shape.perimeter()).sum();
+    // An empty (or zero-area) collection would divide by zero; every share, the total's
+    // included, is 0 instead.
+    let percent = |area: f64| {
+        if total_area > 0.0 {
+            area / total_area * 100.0
+        } else {
+            0.0
+        }
+    };
+    let number = |value: f64| format!("{:.*}", precision, value);
+
+    let mut rows: Vec<[String; 5]> = vec![[
+        "#".to_string(),
+        "Shape".to_string(),
+        "Area".to_string(),
+        "Perimeter".to_string(),
+        "% of total".to_string(),
+    ]];
+    for (index, shape) in shapes.iter().enumerate() {
+        rows.push([
+            index.to_string(),
+            format!("{:?}", shape),
+            number(shape.area()),
+            number(shape.perimeter()),
+            number(percent(shape.area())),
+        ]);
+    }
+    let totals = [
+        String::new(),
+        "Total".to_string(),
+        number(total_area),
+        number(total_perimeter),
+        number(percent(total_area)),
+    ];
+
+    let mut widths = [0; 5];
+    for row in rows.iter().chain([&totals]) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let format_row = |row: &[String; 5]| {
+        format!(
+            "{:<w0$} | {:<w1$} | {:>w2$} | {:>w3$} | {:>w4$}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            row[4],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+            w4 = widths[4]
+        )
+    };
+    // The separator lines run under the header and above the totals, `+` marking the
+    // column boundaries.
+    let separator = widths
+        .iter()
+        .map(|&width| "-".repeat(width))
+        .collect::<Vec<_>>()
+        .join("-+-");
+
+    let mut lines = vec![format_row(&rows[0]), separator.clone()];
+    lines.extend(rows[1..].iter().map(format_row));
+    lines.push(separator);
+    lines.push(format_row(&totals));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::{Circle, Ellipse, Rectangle, ShapeError, Triangle};
+
+    fn shapes() -> Result<Vec<Box<dyn Shape>>, ShapeError> {
+        Ok(vec![
+            Box::new(Rectangle::new(3, 4)?),
+            Box::new(Circle::new(1.0)?),
+            Box::new(Triangle::new(3, 4, 5)?),
+            Box::new(Ellipse::new(5.0, 3.0)?),
+        ])
+    }
+
+    #[test]
+    fn columns_fit_their_longest_cell() -> Result<(), ShapeError> {
+        let table = comparison_table(&shapes()?);
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[0].starts_with("# | Shape ") && lines[0].ends_with("| % of total"));
+        assert_eq!(lines[2], "0 | Rectangle { width: 3, length: 4 }            | 12.00 |     14.00 |      17.58");
+        assert_eq!(lines[7], "  | Total                                        | 68.27 |     57.81 |     100.00");
+        assert!(lines.iter().all(|line| line.len() == lines[0].len()));
+        Ok(())
+    }
+
+    #[test]
+    fn percentages_add_up_to_100() -> Result<(), ShapeError> {
+        let shapes = shapes()?;
+        let table = comparison_table(&shapes);
+        let percentages: f64 = table
+            .lines()
+            .skip(2)
+            .take(shapes.len())
+            .filter_map(|line| line.rsplit('|').next()?.trim().parse::<f64>().ok())
+            .sum();
+        assert!((percentages - 100.0).abs() < 0.05);
+        Ok(())
+    }
+
+    #[test]
+    fn precision_and_empty_tables() -> Result<(), ShapeError> {
+        let table = comparison_table_with_precision(&shapes()?[..1], 0);
+        assert_eq!(table.lines().nth(2), Some("0 | Rectangle { width: 3, length: 4 } |   12 |        14 |        100"));
+        // No shapes: only the header, separators and a zero total.
+        let empty = comparison_table(&[]);
+        assert_eq!(empty.lines().count(), 4);
+        assert!(empty.lines().last().is_some_and(|line| line.ends_with("0.00")));
+        // Shapes without any area share nothing, so the total isn't 100% either.
+        let flat: [Box<dyn Shape>; 2] = [Box::new((0.0, 5.0)), Box::new((2.0, 0.0))];
+        assert!(comparison_table(&flat).lines().last().is_some_and(|line| line.ends_with(" 0.00")));
+        Ok(())
+    }
+}
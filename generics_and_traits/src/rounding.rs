@@ -0,0 +1,77 @@
+// Whole-number areas, e.g. for pixel counts, with the rounding made explicit.
+use std::fmt;
+
+// How a fractional area becomes a whole number.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rounding {
+    Floor,   // Always down: 12.9 -> 12.
+    Ceil,    // Always up: 12.1 -> 13.
+    Nearest, // To the closest whole number, with halves going up: 12.5 -> 13.
+}
+
+// Areas that have no `u64` representation. Validated shapes never have a negative or
+// NaN area, but `Area` can be implemented by anything, so the conversion still checks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RoundingError {
+    Negative(f64),
+    TooLarge(f64),
+    NotANumber,
+}
+
+impl fmt::Display for RoundingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RoundingError::Negative(area) => write!(f, "area {} is negative", area),
+            RoundingError::TooLarge(area) => write!(f, "area {} does not fit in a u64", area),
+            RoundingError::NotANumber => write!(f, "area is not a number"),
+        }
+    }
+}
+
+impl std::error::Error for RoundingError {}
+
+// Round `area` according to `policy`. A plain `as u64` cast would silently saturate
+// (negative values become 0, huge ones u64::MAX), so out-of-range results are errors.
+pub fn round_area(area: f64, policy: Rounding) -> Result<u64, RoundingError> {
+    let rounded = match policy {
+        Rounding::Floor => area.floor(),
+        Rounding::Ceil => area.ceil(),
+        Rounding::Nearest => area.round(),
+    };
+    if rounded.is_nan() {
+        Err(RoundingError::NotANumber)
+    } else if rounded < 0.0 {
+        Err(RoundingError::Negative(area))
+    } else if rounded >= u64::MAX as f64 {
+        // `u64::MAX as f64` rounds up to 2^64, the first value that no longer fits.
+        Err(RoundingError::TooLarge(area))
+    } else {
+        Ok(rounded as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policies_round_halves_differently() {
+        assert_eq!(round_area(12.5, Rounding::Floor), Ok(12));
+        assert_eq!(round_area(12.5, Rounding::Ceil), Ok(13));
+        assert_eq!(round_area(12.5, Rounding::Nearest), Ok(13));
+        assert_eq!(round_area(12.1, Rounding::Nearest), Ok(12));
+    }
+
+    #[test]
+    fn unrepresentable_areas_are_errors() {
+        assert_eq!(round_area(-12.0, Rounding::Floor), Err(RoundingError::Negative(-12.0)));
+        assert_eq!(round_area(1e20, Rounding::Ceil), Err(RoundingError::TooLarge(1e20)));
+        assert_eq!(round_area(f64::NAN, Rounding::Nearest), Err(RoundingError::NotANumber));
+        // Rounds up to 0 rather than being negative.
+        assert_eq!(round_area(-0.4, Rounding::Nearest), Ok(0));
+        assert_eq!(
+            RoundingError::TooLarge(1e20).to_string(),
+            "area 100000000000000000000 does not fit in a u64"
+        );
+    }
+}
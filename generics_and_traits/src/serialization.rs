@@ -0,0 +1,129 @@
+// JSON persistence for shape definitions, compiled only with `--features serde`.
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::shapes::{
+    Annulus, Area, Circle, Ellipse, Rectangle, RegularPolygon, ShapeError, Square, Trapezoid,
+    Triangle, Validate,
+};
+
+// One entry in a shape file. `tag = "type"` makes serde store the variant name inside the
+// object itself, so a circle is written as {"type":"circle","radius":3.5}.
+// Variant names are lowercased (`RegularPolygon` becomes "regularpolygon").
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ShapeDef {
+    Rectangle(Rectangle<f64>),
+    Circle(Circle<f64>),
+    Square(Square<f64>),
+    Triangle(Triangle<f64>),
+    Ellipse(Ellipse<f64>),
+    Trapezoid(Trapezoid<f64>),
+    RegularPolygon(RegularPolygon<f64>),
+    Annulus(Annulus<f64>),
+}
+
+impl ShapeDef {
+    // Compute the area by dispatching to the concrete shape's `Area` impl.
+    pub fn area(&self) -> f64 {
+        match self {
+            ShapeDef::Rectangle(shape) => shape.area(),
+            ShapeDef::Circle(shape) => shape.area(),
+            ShapeDef::Square(shape) => shape.area(),
+            ShapeDef::Triangle(shape) => shape.area(),
+            ShapeDef::Ellipse(shape) => shape.area(),
+            ShapeDef::Trapezoid(shape) => shape.area(),
+            ShapeDef::RegularPolygon(shape) => shape.area(),
+            ShapeDef::Annulus(shape) => shape.area(),
+        }
+    }
+
+    // Re-check the dimensions that deserialization wrote without going through `new`.
+    fn validate(self) -> Result<Self, ShapeError> {
+        Ok(match self {
+            ShapeDef::Rectangle(shape) => ShapeDef::Rectangle(shape.validate()?),
+            ShapeDef::Circle(shape) => ShapeDef::Circle(shape.validate()?),
+            ShapeDef::Square(shape) => ShapeDef::Square(shape.validate()?),
+            ShapeDef::Triangle(shape) => ShapeDef::Triangle(shape.validate()?),
+            ShapeDef::Ellipse(shape) => ShapeDef::Ellipse(shape.validate()?),
+            ShapeDef::Trapezoid(shape) => ShapeDef::Trapezoid(shape.validate()?),
+            ShapeDef::RegularPolygon(shape) => ShapeDef::RegularPolygon(shape.validate()?),
+            ShapeDef::Annulus(shape) => ShapeDef::Annulus(shape.validate()?),
+        })
+    }
+}
+
+// Loading can fail because the JSON is malformed or because a shape in it is invalid.
+#[derive(Debug)]
+pub enum LoadError {
+    Json(serde_json::Error),
+    InvalidShape { index: usize, error: ShapeError },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Json(err) => write!(f, "invalid shape JSON: {}", err),
+            LoadError::InvalidShape { index, error } => write!(f, "shape #{}: {}", index, error),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+// `From` lets `?` convert a `serde_json::Error` into a `LoadError` automatically.
+impl From<serde_json::Error> for LoadError {
+    fn from(err: serde_json::Error) -> Self {
+        LoadError::Json(err)
+    }
+}
+
+// Parse a JSON array of shape definitions, validating every shape.
+pub fn load_shapes(json: &str) -> Result<Vec<ShapeDef>, LoadError> {
+    let shapes: Vec<ShapeDef> = serde_json::from_str(json)?;
+    shapes
+        .into_iter()
+        .enumerate()
+        .map(|(index, shape)| {
+            shape
+                .validate()
+                .map_err(|error| LoadError::InvalidShape { index, error })
+        })
+        .collect()
+}
+
+// Write shape definitions back out as a JSON array.
+pub fn save_shapes(shapes: &[ShapeDef]) -> Result<String, LoadError> {
+    Ok(serde_json::to_string(shapes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shapes_survive_a_round_trip() -> Result<(), LoadError> {
+        let json = r#"[{"type":"circle","radius":3.5},{"type":"rectangle","width":2,"length":4}]"#;
+        let loaded = load_shapes(json)?;
+        let saved = save_shapes(&loaded)?;
+        assert_eq!(
+            saved,
+            r#"[{"type":"circle","radius":3.5},{"type":"rectangle","width":2.0,"length":4.0}]"#
+        );
+        let reloaded = load_shapes(&saved)?;
+        let areas = |shapes: &[ShapeDef]| shapes.iter().map(ShapeDef::area).collect::<Vec<_>>();
+        assert_eq!(areas(&loaded), areas(&reloaded));
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_shapes_are_rejected() {
+        let err = load_shapes(r#"[{"type":"circle","radius":-1}]"#).err();
+        assert_eq!(
+            err.map(|err| err.to_string()),
+            Some("shape #0: radius must not be negative".to_string())
+        );
+        assert!(matches!(load_shapes("[{"), Err(LoadError::Json(_))));
+    }
+}
@@ -0,0 +1,1682 @@
+use std::any::Any;
+use std::cmp::Ordering;
+use std::fmt; // Needed to implement `Display` for `ShapeError`.
+use std::ops::{Mul, Sub};
+
+use crate::approx::ApproxEq;
+use crate::float::Float;
+use crate::geometry::{Point, Transform};
+use crate::positive::{PositiveF64, PositiveU32};
+use crate::rounding::{round_area, Rounding, RoundingError};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// Define a trait `Area` for calculating the area of shapes.
+// Any type implementing this trait must define the `area` method.
+// `Debug` is a supertrait, so every shape can be printed with `{:?}`, even behind a
+// `&dyn Area` or `Box<dyn Area>` where the concrete type is no longer known.
+// `AsAny` is one too, so a `dyn Area` can be downcast back to its concrete type.
+pub trait Area: fmt::Debug + AsAny {
+    fn area(&self) -> f64;  // The method `area` must return the area as an `f64`.
+
+    // The area as a whole number, rounded according to `policy`. Provided for every shape
+    // in terms of `area`, so implementors get it for free.
+    fn area_rounded(&self, policy: Rounding) -> Result<u64, RoundingError> {
+        round_area(self.area(), policy)
+    }
+
+    // A one-line description, e.g. "Rectangle { width: 3, length: 4 } has area 12.000".
+    // `Debug` is a supertrait, so every shape can use this default; shapes with something
+    // more useful to say (like `Circle`) override it.
+    fn describe(&self) -> String {
+        format!("{:?} has area {:.3}", self, self.area())
+    }
+}
+
+// Lets a `&dyn Area` be viewed as a `&dyn Any` and downcast back to its concrete type:
+// `shape.as_any().downcast_ref::<Rectangle<f64>>()`. The blanket impl below covers every
+// shape, so no implementation of `Area` has to write it.
+// `Any` only covers `'static` types, hence the `where` clause; it keeps `Area` usable
+// for borrowed types such as `&'a T`, which just can't call this method.
+// A `Box<dyn Area>` is a shape itself, so call it on the contents (`boxed.as_ref()`)
+// to get at the boxed type rather than at the box.
+pub trait AsAny {
+    fn as_any(&self) -> &dyn Any
+    where
+        Self: 'static;
+}
+
+impl<T: Area> AsAny for T {
+    fn as_any(&self) -> &dyn Any
+    where
+        Self: 'static,
+    {
+        self
+    }
+}
+
+// Blanket impls: a reference to a shape, or a boxed shape, is a shape too. `?Sized` lets
+// `T` be `dyn Area` itself, so `&dyn Area` and `Box<dyn Area>` are covered as well and
+// generic code over `S: Area` accepts collections of either.
+impl<T: Area + ?Sized> Area for &T {
+    fn area(&self) -> f64 {
+        (**self).area()
+    }
+
+    // Forwarded so that an override on the underlying shape isn't lost behind the pointer.
+    fn describe(&self) -> String {
+        (**self).describe()
+    }
+}
+
+impl<T: Area + ?Sized> Area for Box<T> {
+    fn area(&self) -> f64 {
+        (**self).area()
+    }
+
+    // Forwarded so that an override on the underlying shape isn't lost behind the pointer.
+    fn describe(&self) -> String {
+        (**self).describe()
+    }
+}
+
+// A parallel trait to `Area` whose result type is chosen by the implementor through the
+// associated type `Output`. A `Rectangle<u64>` can then return its area as an exact `u64`,
+// while a `Circle` (whose area involves π) still returns `f64`.
+// `Area` is kept as it is: it stays object safe and every existing caller keeps working.
+pub trait AreaOf {
+    type Output;
+    fn area_of(&self) -> Self::Output;
+}
+
+// Compatibility helper for code that wants an `f64` from any `AreaOf` shape whose
+// output converts losslessly. `u64` deliberately doesn't implement `Into<f64>` (values
+// above 2^53 would be rounded), so exact integer areas have to be converted explicitly.
+pub fn as_f64_area<S>(shape: &S) -> f64
+where
+    S: AreaOf,
+    S::Output: Into<f64>,
+{
+    shape.area_of().into()
+}
+
+// Define a trait `Perimeter` for calculating the distance around a shape.
+// It lives alongside `Area`, so a shape can opt into one or both.
+pub trait Perimeter {
+    fn perimeter(&self) -> f64; // The method `perimeter` must return the perimeter as an `f64`.
+}
+
+// `Shape` bundles the traits a "full" shape provides, so a heterogeneous collection can be
+// a single `Vec<Box<dyn Shape>>` instead of needing one trait object per capability.
+// It only adds a provided method, so it stays object safe.
+pub trait Shape: Area + Perimeter + fmt::Debug {
+    // A one-line summary, e.g. "Rectangle { width: 3, length: 4 }: area 12.00, perimeter 14.00".
+    // Named differently from `Area::describe`: with the same name, calling it on a shape
+    // would be ambiguous between the two traits.
+    fn summary(&self) -> String {
+        format!(
+            "{:?}: area {:.2}, perimeter {:.2}",
+            self,
+            self.area(),
+            self.perimeter()
+        )
+    }
+}
+
+// Blanket impl: anything with an area, a perimeter and `Debug` is a `Shape` automatically,
+// so no shape needs its own (empty) impl. `?Sized` includes trait objects.
+impl<T: Area + Perimeter + fmt::Debug + ?Sized> Shape for T {}
+
+// Define a trait `Scale` for resizing a shape uniformly by a factor.
+// `T` may be an integer type, and 10 * 0.5 is not an integer, so the scaled shape
+// can't always be `Self`. The associated type `Output` lets each implementor say
+// what it produces (e.g. `Rectangle<i32>` scales into a `Rectangle<f64>`).
+// Negative factors are treated as their absolute value, since a length can't be negative.
+// A factor of 0 is allowed and collapses the shape to zero area.
+pub trait Scale {
+    type Output;
+    fn scaled(&self, factor: f64) -> Self::Output;
+}
+
+// The same-type counterpart of `Scale` for shapes that already have `f64` dimensions:
+// a `Circle<f64>` scales into a `Circle<f64>`, so no associated type is needed.
+// Factors follow the `Scale` rules (absolute value; 0 collapses the shape).
+// `Rectangle<f64>`, `Circle<f64>`, `Square<f64>` and `Triangle<f64>` implement both traits,
+// so with both in scope `shape.scaled(k)` is ambiguous and the call has to name the trait:
+// `ScaledSelf::scaled(&shape, k)`.
+pub trait ScaledSelf: Sized {
+    fn scaled(&self, k: f64) -> Self;
+}
+
+// Define a trait `BoundingBox` returning the smallest axis-aligned rectangle that encloses
+// the shape. Comparing boxes is cheap, so it is useful as a broad-phase check before the
+// exact (and more expensive) area or intersection math.
+// A shape's area is never larger than its bounding box's; only rectangles fill theirs exactly.
+pub trait BoundingBox {
+    fn bounding_box(&self) -> Rectangle<f64>;
+}
+
+// The reasons a shape can be rejected by its constructor.
+// Each dimension variant carries the name of the offending field for the error message.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShapeError {
+    NegativeDimension(&'static str),  // e.g. a width of -3
+    ZeroDimension(&'static str),      // e.g. a radius of 0
+    NonFiniteDimension(&'static str), // NaN or infinity, which would poison every calculation
+    TriangleInequality,               // three sides that can't close into a triangle
+    TooFewSides(u32),                 // a polygon needs at least 3 sides
+    InnerRadiusTooLarge,              // an annulus whose hole is as big as the whole ring
+    MissingDimension(&'static str),   // a builder that was never given e.g. a width
+    Degenerate,                       // three points on a line, which enclose no area
+}
+
+// `Display` provides the human-readable message, e.g. "width must not be negative".
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShapeError::NegativeDimension(name) => write!(f, "{} must not be negative", name),
+            ShapeError::ZeroDimension(name) => write!(f, "{} must not be zero", name),
+            ShapeError::NonFiniteDimension(name) => write!(f, "{} must be a finite number", name),
+            ShapeError::TriangleInequality => {
+                write!(f, "the sum of any two sides must be greater than the third")
+            }
+            ShapeError::TooFewSides(sides) => {
+                write!(f, "a polygon needs at least 3 sides, got {}", sides)
+            }
+            ShapeError::InnerRadiusTooLarge => {
+                write!(f, "inner radius must be smaller than the outer radius")
+            }
+            ShapeError::MissingDimension(name) => write!(f, "{} was not set", name),
+            ShapeError::Degenerate => write!(f, "the points are collinear and enclose no area"),
+        }
+    }
+}
+
+// Implementing `Error` lets `ShapeError` be used with `?` and `Box<dyn Error>`.
+// `Debug` and `Display` are all it needs, so the body is empty.
+impl std::error::Error for ShapeError {}
+
+// Shared validation used by every constructor: a dimension must be a finite, positive number.
+// The check is done after converting to `f64`, so it works for integer and float `T` alike.
+pub(crate) fn check_dimension<T: Into<f64> + Copy>(name: &'static str, value: T) -> Result<(), ShapeError> {
+    let value = value.into();
+    if !value.is_finite() {
+        Err(ShapeError::NonFiniteDimension(name))
+    } else if value < 0.0 {
+        Err(ShapeError::NegativeDimension(name))
+    } else if value == 0.0 {
+        Err(ShapeError::ZeroDimension(name))
+    } else {
+        Ok(())
+    }
+}
+
+// Validation for the exact, `AreaOf`-based API, which can't assume `T: Into<f64>`.
+// `T::default()` is zero for every numeric type, so comparing against it tells negative,
+// zero and positive apart; `partial_cmp` returns `None` only for NaN.
+fn check_exact_dimension<T: PartialOrd + Default>(
+    name: &'static str,
+    value: &T,
+) -> Result<(), ShapeError> {
+    match value.partial_cmp(&T::default()) {
+        Some(Ordering::Greater) => Ok(()),
+        Some(Ordering::Equal) => Err(ShapeError::ZeroDimension(name)),
+        Some(Ordering::Less) => Err(ShapeError::NegativeDimension(name)),
+        None => Err(ShapeError::NonFiniteDimension(name)),
+    }
+}
+
+// A generic struct `Rectangle` that can take any type `T` for its width and length.
+// The fields are private, so the only way to build one is the validating `Rectangle::new`.
+// `PartialEq`, `Eq` and `Hash` only apply when `T` has them, so `Rectangle<f64>` gets `==`
+// but only a rectangle of hashable dimensions (e.g. `Rectangle<OrderedDim>`) can be a map key.
+#[derive(Debug, PartialEq, Eq, Hash)] // Derive the `Debug` trait to allow printing the struct using `{:?}`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rectangle<T> {
+    width: T,    // Width of the rectangle, of generic type `T`.
+    length: T,   // Length of the rectangle, also of type `T`.
+}
+
+impl<T: Into<f64> + Copy> Rectangle<T> {
+    // Create a rectangle, rejecting negative, zero and non-finite dimensions.
+    pub fn new(width: T, length: T) -> Result<Self, ShapeError> {
+        check_dimension("width", width)?;
+        check_dimension("length", length)?;
+        Ok(Rectangle { width, length })
+    }
+}
+
+// `TryFrom` is the fallible counterpart of `From`: converting a `(width, length)` tuple or a
+// `[width, length]` array goes through `Rectangle::new`, so bad dimensions are still rejected.
+impl<T: Into<f64> + Copy> TryFrom<(T, T)> for Rectangle<T> {
+    type Error = ShapeError;
+
+    fn try_from((width, length): (T, T)) -> Result<Self, ShapeError> {
+        Rectangle::new(width, length)
+    }
+}
+
+impl<T: Into<f64> + Copy> TryFrom<[T; 2]> for Rectangle<T> {
+    type Error = ShapeError;
+
+    fn try_from([width, length]: [T; 2]) -> Result<Self, ShapeError> {
+        Rectangle::new(width, length)
+    }
+}
+
+// The reverse direction can't fail: a rectangle always has a width and a length.
+impl<T> From<Rectangle<T>> for (T, T) {
+    fn from(rect: Rectangle<T>) -> (T, T) {
+        (rect.width, rect.length)
+    }
+}
+
+// Read-only accessors, since the fields themselves are private.
+// They only need `T: Copy`, so they also work for exact types such as `Rectangle<u64>`.
+impl<T: Copy> Rectangle<T> {
+    pub fn width(&self) -> T {
+        self.width
+    }
+
+    pub fn length(&self) -> T {
+        self.length
+    }
+
+    // The same rectangle turned on its side: width and length swap.
+    pub fn rotated(&self) -> Self {
+        Rectangle {
+            width: self.length,
+            length: self.width,
+        }
+    }
+}
+
+// A `Rectangle` has no position, only an orientation: a quarter turn swaps its sides and
+// a mirror image is the same rectangle again.
+impl<T: Copy> Transform for Rectangle<T> {
+    fn rotate90(&self) -> Self {
+        self.rotated()
+    }
+
+    fn flip_horizontal(&self) -> Self {
+        Rectangle { ..*self }
+    }
+
+    fn flip_vertical(&self) -> Self {
+        Rectangle { ..*self }
+    }
+}
+
+// The default rectangle is the 1 x 1 unit square. `From<u8>` is the simplest bound that
+// gives us a `1` for every integer and float type (`T::default()` would be zero, which
+// the constructors reject).
+impl<T: From<u8>> Default for Rectangle<T> {
+    fn default() -> Self {
+        Rectangle {
+            width: T::from(1),
+            length: T::from(1),
+        }
+    }
+}
+
+// Building from two opposite corners needs subtraction and comparison on `T` as well.
+impl<T: Into<f64> + Copy + PartialOrd + Sub<Output = T>> Rectangle<T> {
+    // The corners may be given in any order: taking the absolute difference of each
+    // coordinate always yields non-negative dimensions.
+    // Corners sharing an x or y coordinate would give a zero-area rectangle, which is
+    // rejected by `Rectangle::new` like any other zero dimension.
+    pub fn from_corners(p1: Point<T>, p2: Point<T>) -> Result<Self, ShapeError> {
+        // `T` might be unsigned, so subtract the smaller from the larger rather than calling `abs`.
+        let abs_diff = |a: T, b: T| if a > b { a - b } else { b - a };
+        Rectangle::new(abs_diff(p1.x, p2.x), abs_diff(p1.y, p2.y))
+    }
+}
+
+// Implement the `Area` trait for `Rectangle`, where `T` is a generic type.
+// The `T` must implement both the `Into<f64>` and `Copy` traits.
+impl<T: Into<f64> + Copy + fmt::Debug> Area for Rectangle<T> {
+    fn area(&self) -> f64 {
+        // Convert the width and length from `T` into `f64` and compute the area.
+        self.width.into() * self.length.into()
+    }
+}
+
+// Every dimension is multiplied by |factor|, so the area is multiplied by factor².
+impl<T: Into<f64> + Copy> Scale for Rectangle<T> {
+    type Output = Rectangle<f64>;
+
+    fn scaled(&self, factor: f64) -> Rectangle<f64> {
+        let factor = factor.abs();
+        Rectangle {
+            width: self.width.into() * factor,
+            length: self.length.into() * factor,
+        }
+    }
+}
+
+// A rectangle is its own bounding box.
+impl<T: Into<f64> + Copy> BoundingBox for Rectangle<T> {
+    fn bounding_box(&self) -> Rectangle<f64> {
+        Rectangle {
+            width: self.width.into(),
+            length: self.length.into(),
+        }
+    }
+}
+
+// Implement the `Perimeter` trait for `Rectangle` with the same bounds as `Area`.
+impl<T: Into<f64> + Copy> Perimeter for Rectangle<T> {
+    fn perimeter(&self) -> f64 {
+        // Two widths plus two lengths.
+        2.0 * (self.width.into() + self.length.into())
+    }
+}
+
+// For quick scripting, a `(width, length)` tuple can stand in for a rectangle:
+// `print_area(&(3, 4))`. Unlike `Rectangle::new`, a tuple is never validated, so a
+// negative dimension gives a negative area; use `Rectangle::try_from` when that matters.
+impl<T: Into<f64> + Copy + fmt::Debug> Area for (T, T) {
+    fn area(&self) -> f64 {
+        self.0.into() * self.1.into()
+    }
+}
+
+impl<T: Into<f64> + Copy> Perimeter for (T, T) {
+    fn perimeter(&self) -> f64 {
+        2.0 * (self.0.into() + self.1.into())
+    }
+}
+
+// `Display` gives a human-readable form such as "Rectangle 10 x 5".
+// It needs `T: Display` so the dimensions themselves can be written out;
+// `f64` prints without a trailing ".0", so 4.0 shows as "4".
+impl<T: fmt::Display> fmt::Display for Rectangle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Rectangle {} x {}", self.width, self.length)
+    }
+}
+
+// A rectangle whose width and length may have different types, e.g. an `i32` width with an
+// `f64` length. Each parameter is bounded independently; `Rectangle<T>` is kept as it is,
+// since all of its other impls assume a single dimension type.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rectangle2<W, L> {
+    width: W,
+    length: L,
+}
+
+impl<W: Into<f64> + Copy, L: Into<f64> + Copy> Rectangle2<W, L> {
+    pub fn new(width: W, length: L) -> Result<Self, ShapeError> {
+        check_dimension("width", width)?;
+        check_dimension("length", length)?;
+        Ok(Rectangle2 { width, length })
+    }
+}
+
+// Both dimensions are converted to `f64` before multiplying, so the mixed types never meet.
+impl<W, L> Area for Rectangle2<W, L>
+where
+    W: Into<f64> + Copy + fmt::Debug,
+    L: Into<f64> + Copy + fmt::Debug,
+{
+    fn area(&self) -> f64 {
+        self.width.into() * self.length.into()
+    }
+}
+
+impl<W: fmt::Display, L: fmt::Display> fmt::Display for Rectangle2<W, L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Rectangle {} x {}", self.width, self.length)
+    }
+}
+
+// A generic struct `Circle` that can take any type `T` for its radius.
+#[derive(Debug, PartialEq, Eq, Hash)] // Derive the `Debug` trait for the `Circle` struct as well.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Circle<T> {
+    radius: T,   // Radius of the circle, of generic type `T`.
+}
+
+impl<T: Into<f64> + Copy> Circle<T> {
+    // Create a circle, rejecting a negative, zero or non-finite radius.
+    pub fn new(radius: T) -> Result<Self, ShapeError> {
+        check_dimension("radius", radius)?;
+        Ok(Circle { radius })
+    }
+
+    pub fn radius(&self) -> T {
+        self.radius
+    }
+
+    // The unit circle has radius exactly 1 (and area π).
+    pub fn is_unit(&self) -> bool {
+        self.radius.into() == 1.0
+    }
+
+    pub fn diameter(&self) -> f64 {
+        2.0 * self.radius.into()
+    }
+
+    // Another name for the perimeter, the usual word for circles.
+    pub fn circumference(&self) -> f64 {
+        self.perimeter()
+    }
+
+    // The "pie slice" between two radii `angle_radians` apart: r² * θ / 2.
+    // Angles are clamped to [0, 2π], so negative angles give 0 and anything beyond a full
+    // turn gives the whole circle.
+    pub fn sector_area(&self, angle_radians: f64) -> f64 {
+        let r = self.radius.into();
+        r * r * clamp_angle(angle_radians) / 2.0
+    }
+
+    // `sector_area` with the angle in degrees.
+    pub fn sector_area_deg(&self, angle_degrees: f64) -> f64 {
+        self.sector_area(angle_degrees.to_radians())
+    }
+
+    // The region between a chord and its arc: the sector minus the triangle formed by
+    // the two radii, r² * (θ - sin θ) / 2. Angles are clamped like in `sector_area`.
+    pub fn segment_area(&self, angle_radians: f64) -> f64 {
+        let r = self.radius.into();
+        let angle = clamp_angle(angle_radians);
+        r * r * (angle - angle.sin()) / 2.0
+    }
+
+    // The regular polygon with `sides` vertices on the circle, along with how far its area
+    // falls short of the circle's, relative to the circle's area. Each side is a chord,
+    // 2r * sin(π / n). Fewer than 3 sides isn't a polygon, so `sides` is raised to 3.
+    pub fn approximate(&self, sides: u32) -> (RegularPolygon<f64>, f64) {
+        self.approximating_polygon(sides, f64::sin)
+    }
+
+    // The regular polygon with `sides` sides touching the circle from outside, and how far
+    // its area exceeds the circle's. Each side is a tangent segment, 2r * tan(π / n).
+    pub fn approximate_circumscribed(&self, sides: u32) -> (RegularPolygon<f64>, f64) {
+        self.approximating_polygon(sides, f64::tan)
+    }
+
+    // Shared by both approximations: `half_side` maps the half-angle π / n to half a side
+    // of the polygon for a unit radius. The radius is already validated, so the side is
+    // positive and finite and the polygon can be built directly.
+    fn approximating_polygon(
+        &self,
+        sides: u32,
+        half_side: fn(f64) -> f64,
+    ) -> (RegularPolygon<f64>, f64) {
+        let num_sides = sides.max(3);
+        let r = self.radius.into();
+        let polygon = RegularPolygon {
+            side_length: 2.0 * r * half_side(std::f64::consts::PI / num_sides as f64),
+            num_sides,
+        };
+        let circle_area = std::f64::consts::PI * r * r;
+        let error = (polygon.area() - circle_area).abs() / circle_area;
+        (polygon, error)
+    }
+}
+
+// Keep an angle within a single turn, [0, 2π].
+fn clamp_angle(angle_radians: f64) -> f64 {
+    angle_radians.clamp(0.0, 2.0 * std::f64::consts::PI)
+}
+
+// A circle centered on the origin is symmetric under every rotation and reflection about
+// it, so all three transforms give back the same circle. They exist so generic code over
+// `Transform` accepts circles too.
+impl<T: Copy> Transform for Circle<T> {
+    fn rotate90(&self) -> Self {
+        Circle { ..*self }
+    }
+
+    fn flip_horizontal(&self) -> Self {
+        Circle { ..*self }
+    }
+
+    fn flip_vertical(&self) -> Self {
+        Circle { ..*self }
+    }
+}
+
+// Halving an integer diameter would truncate (a diameter of 7 would give radius 3), so
+// `from_diameter` always builds a `Circle<f64>`, whatever numeric type the diameter has.
+impl Circle<f64> {
+    pub fn from_diameter<D: Into<f64> + Copy>(diameter: D) -> Result<Self, ShapeError> {
+        check_dimension("diameter", diameter)?;
+        Ok(Circle {
+            radius: diameter.into() / 2.0,
+        })
+    }
+}
+
+// The default circle is the unit circle, with area π.
+impl<T: From<u8>> Default for Circle<T> {
+    fn default() -> Self {
+        Circle { radius: T::from(1) }
+    }
+}
+
+// Implement the `Area` trait for `Circle`, where `T` is a generic type.
+// The `T` must implement both `Into<f64>` and `Copy` traits.
+impl<T: Into<f64> + Copy + fmt::Debug> Area for Circle<T> {
+    fn area(&self) -> f64 {
+        // Calculate the area of the circle using the formula π * r^2.
+        std::f64::consts::PI * self.radius.into() * self.radius.into()
+    }
+
+    // Overrides the default to mention the radius and circumference as well.
+    fn describe(&self) -> String {
+        format!(
+            "Circle of radius {:?} has area {:.3} and circumference {:.3}",
+            self.radius,
+            self.area(),
+            self.circumference()
+        )
+    }
+}
+
+impl<T: Into<f64> + Copy> Scale for Circle<T> {
+    type Output = Circle<f64>;
+
+    fn scaled(&self, factor: f64) -> Circle<f64> {
+        Circle {
+            radius: self.radius.into() * factor.abs(),
+        }
+    }
+}
+
+// Implement the `Perimeter` trait for `Circle`, i.e. its circumference.
+impl<T: Into<f64> + Copy> Perimeter for Circle<T> {
+    fn perimeter(&self) -> f64 {
+        // Calculate the circumference using the formula 2 * π * r.
+        2.0 * std::f64::consts::PI * self.radius.into()
+    }
+}
+
+// A circle fits in a 2r x 2r square.
+impl<T: Into<f64> + Copy> BoundingBox for Circle<T> {
+    fn bounding_box(&self) -> Rectangle<f64> {
+        let diameter = 2.0 * self.radius.into();
+        Rectangle {
+            width: diameter,
+            length: diameter,
+        }
+    }
+}
+
+// Displays as e.g. "Circle r=3.7".
+impl<T: fmt::Display> fmt::Display for Circle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Circle r={}", self.radius)
+    }
+}
+
+// A generic struct `Annulus`: the ring between two concentric circles.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Annulus<T> {
+    outer_radius: T,
+    inner_radius: T,
+}
+
+impl<T: Into<f64> + Copy> Annulus<T> {
+    // Create an annulus. Both radii must be valid and the hole must be strictly smaller
+    // than the outer circle, otherwise there is no ring left.
+    pub fn new(outer_radius: T, inner_radius: T) -> Result<Self, ShapeError> {
+        check_dimension("outer radius", outer_radius)?;
+        check_dimension("inner radius", inner_radius)?;
+        if inner_radius.into() >= outer_radius.into() {
+            return Err(ShapeError::InnerRadiusTooLarge);
+        }
+        Ok(Annulus { outer_radius, inner_radius })
+    }
+
+    // Build the ring from two existing circles. They were validated when they were
+    // created, so only the relative size still needs checking.
+    pub fn from_circles(outer: Circle<T>, inner: Circle<T>) -> Result<Self, ShapeError> {
+        Annulus::new(outer.radius, inner.radius)
+    }
+}
+
+// Area of an annulus: π * R² - π * r², written exactly like `Circle::area` so the result
+// is bit-for-bit the difference of the two circle areas.
+impl<T: Into<f64> + Copy + fmt::Debug> Area for Annulus<T> {
+    fn area(&self) -> f64 {
+        let (outer, inner) = (self.outer_radius.into(), self.inner_radius.into());
+        std::f64::consts::PI * outer * outer - std::f64::consts::PI * inner * inner
+    }
+}
+
+// The boundary of a ring is both circles: 2π(R + r).
+impl<T: Into<f64> + Copy> Perimeter for Annulus<T> {
+    fn perimeter(&self) -> f64 {
+        2.0 * std::f64::consts::PI * (self.outer_radius.into() + self.inner_radius.into())
+    }
+}
+
+// The hole doesn't change the outline, so the box is the outer circle's 2R x 2R.
+impl<T: Into<f64> + Copy> BoundingBox for Annulus<T> {
+    fn bounding_box(&self) -> Rectangle<f64> {
+        let diameter = 2.0 * self.outer_radius.into();
+        Rectangle {
+            width: diameter,
+            length: diameter,
+        }
+    }
+}
+
+// Displays as e.g. "Annulus R=5 r=3".
+impl<T: fmt::Display> fmt::Display for Annulus<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Annulus R={} r={}", self.outer_radius, self.inner_radius)
+    }
+}
+
+// A generic struct `Square` with a single side length.
+// Every square is also a rectangle, which is expressed with a `From` conversion below.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Square<T> {
+    side: T,   // Length of every side, of generic type `T`.
+}
+
+impl<T: Into<f64> + Copy> Square<T> {
+    // Create a square, rejecting a negative, zero or non-finite side.
+    pub fn new(side: T) -> Result<Self, ShapeError> {
+        check_dimension("side", side)?;
+        Ok(Square { side })
+    }
+
+    pub fn side(&self) -> T {
+        self.side
+    }
+}
+
+// The default square has side 1.
+impl<T: From<u8>> Default for Square<T> {
+    fn default() -> Self {
+        Square { side: T::from(1) }
+    }
+}
+
+impl<T: Into<f64> + Copy + fmt::Debug> Area for Square<T> {
+    fn area(&self) -> f64 {
+        self.side.into() * self.side.into()
+    }
+}
+
+impl<T: Into<f64> + Copy> Perimeter for Square<T> {
+    fn perimeter(&self) -> f64 {
+        4.0 * self.side.into()
+    }
+}
+
+impl<T: Into<f64> + Copy> Scale for Square<T> {
+    type Output = Square<f64>;
+
+    fn scaled(&self, factor: f64) -> Square<f64> {
+        Square {
+            side: self.side.into() * factor.abs(),
+        }
+    }
+}
+
+impl<T: Into<f64> + Copy> BoundingBox for Square<T> {
+    fn bounding_box(&self) -> Rectangle<f64> {
+        Rectangle {
+            width: self.side.into(),
+            length: self.side.into(),
+        }
+    }
+}
+
+// Displays as e.g. "Square 4".
+impl<T: fmt::Display> fmt::Display for Square<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Square {}", self.side)
+    }
+}
+
+// Implementing `From<Square<T>>` for `Rectangle<T>` also gives us `Into<Rectangle<T>>`
+// for free, so `let rect: Rectangle<_> = square.into();` works too.
+// The square's side was already validated, so no checks are needed here.
+impl<T> From<Square<T>> for Rectangle<T>
+where
+    T: Copy,
+{
+    fn from(square: Square<T>) -> Self {
+        Rectangle {
+            width: square.side,
+            length: square.side,
+        }
+    }
+}
+
+// Shape predicates. `is_square` compares the dimensions in `T` itself, hence the
+// `PartialEq` bound: exact for integers, but float dimensions have to match bit for bit.
+impl<T: Into<f64> + Copy + PartialEq> Rectangle<T> {
+    pub fn is_square(&self) -> bool {
+        self.width == self.length
+    }
+
+    // For float dimensions that went through arithmetic, e.g. 0.1 * 3.0 vs 0.3.
+    pub fn is_square_approx(&self, epsilon: f64) -> bool {
+        self.width.into().approx_eq(&self.length.into(), epsilon)
+    }
+
+    // Width divided by length: above 1 for landscape rectangles, below 1 for portrait ones
+    // and exactly 1 for squares. Both dimensions are positive, so this never divides by zero.
+    pub fn aspect_ratio(&self) -> f64 {
+        self.width.into() / self.length.into()
+    }
+
+    // The opposite direction of `From<Square<T>>` can fail, so it is a method returning a
+    // `Result`. On failure the original rectangle is handed back in `Err` instead of being dropped.
+    pub fn try_into_square(self) -> Result<Square<T>, Self> {
+        if self.is_square() {
+            Ok(Square { side: self.width })
+        } else {
+            Err(self)
+        }
+    }
+}
+
+// A generic struct `Ellipse` described by its two semi-axes.
+// When both axes are equal it is just a circle of that radius.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ellipse<T> {
+    semi_major: T, // Half of the longest diameter.
+    semi_minor: T, // Half of the shortest diameter.
+}
+
+impl<T: Into<f64> + Copy> Ellipse<T> {
+    // Create an ellipse, rejecting negative, zero and non-finite axes.
+    // The axes aren't required to be in order; both formulas below are symmetric in a and b.
+    pub fn new(semi_major: T, semi_minor: T) -> Result<Self, ShapeError> {
+        check_dimension("semi-major axis", semi_major)?;
+        check_dimension("semi-minor axis", semi_minor)?;
+        Ok(Ellipse { semi_major, semi_minor })
+    }
+}
+
+// Area of an ellipse: π * a * b (π * r² when a == b).
+impl<T: Into<f64> + Copy + fmt::Debug> Area for Ellipse<T> {
+    fn area(&self) -> f64 {
+        std::f64::consts::PI * self.semi_major.into() * self.semi_minor.into()
+    }
+}
+
+// The perimeter of an ellipse has no closed form, so this uses
+// Ramanujan's second approximation: π * (3(a + b) - sqrt((3a + b)(a + 3b))).
+// It is exact for circles (a == b gives 2πr) and within about 0.04% even for a 10:1 ellipse.
+impl<T: Into<f64> + Copy> Perimeter for Ellipse<T> {
+    fn perimeter(&self) -> f64 {
+        let (a, b) = (self.semi_major.into(), self.semi_minor.into());
+        std::f64::consts::PI * (3.0 * (a + b) - ((3.0 * a + b) * (a + 3.0 * b)).sqrt())
+    }
+}
+
+// An axis-aligned ellipse fits in a 2a x 2b box.
+impl<T: Into<f64> + Copy> BoundingBox for Ellipse<T> {
+    fn bounding_box(&self) -> Rectangle<f64> {
+        Rectangle {
+            width: 2.0 * self.semi_major.into(),
+            length: 2.0 * self.semi_minor.into(),
+        }
+    }
+}
+
+// Displays as e.g. "Ellipse 5 x 3".
+impl<T: fmt::Display> fmt::Display for Ellipse<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Ellipse {} x {}", self.semi_major, self.semi_minor)
+    }
+}
+
+// A generic struct `Trapezoid` with two parallel sides and the height between them.
+// The slanted legs aren't stored, so there is no `Perimeter` impl: many trapezoids
+// share the same bases and height but have different legs.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Trapezoid<T> {
+    base_a: T, // First parallel side.
+    base_b: T, // Second parallel side.
+    height: T, // Perpendicular distance between the parallel sides.
+}
+
+impl<T: Into<f64> + Copy> Trapezoid<T> {
+    // Create a trapezoid, rejecting negative, zero and non-finite dimensions.
+    pub fn new(base_a: T, base_b: T, height: T) -> Result<Self, ShapeError> {
+        check_dimension("base a", base_a)?;
+        check_dimension("base b", base_b)?;
+        check_dimension("height", height)?;
+        Ok(Trapezoid { base_a, base_b, height })
+    }
+}
+
+impl<T: PartialEq> Trapezoid<T> {
+    // With equal parallel sides the other two sides are parallel as well.
+    pub fn is_parallelogram(&self) -> bool {
+        self.base_a == self.base_b
+    }
+}
+
+// Area of a trapezoid: the average of the parallel sides times the height.
+impl<T: Into<f64> + Copy + fmt::Debug> Area for Trapezoid<T> {
+    fn area(&self) -> f64 {
+        (self.base_a.into() + self.base_b.into()) / 2.0 * self.height.into()
+    }
+}
+
+// Without the legs the horizontal offset of the shorter base is unknown, so this assumes
+// the usual drawing where the shorter base sits above the longer one (e.g. an isosceles
+// trapezoid): the box is as wide as the longer base and as tall as the height.
+impl<T: Into<f64> + Copy> BoundingBox for Trapezoid<T> {
+    fn bounding_box(&self) -> Rectangle<f64> {
+        Rectangle {
+            width: self.base_a.into().max(self.base_b.into()),
+            length: self.height.into(),
+        }
+    }
+}
+
+// Displays as e.g. "Trapezoid 3 | 5 h=4".
+impl<T: fmt::Display> fmt::Display for Trapezoid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Trapezoid {} | {} h={}", self.base_a, self.base_b, self.height)
+    }
+}
+
+// A generic struct `RegularPolygon`: `num_sides` sides, all of length `side_length`.
+// Only the side length is generic; the side count is always a plain `u32`.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RegularPolygon<T> {
+    side_length: T,
+    num_sides: u32,
+}
+
+impl<T: Into<f64> + Copy> RegularPolygon<T> {
+    // Create a regular polygon, rejecting fewer than 3 sides as well as invalid side lengths.
+    pub fn new(side_length: T, num_sides: u32) -> Result<Self, ShapeError> {
+        if num_sides < 3 {
+            return Err(ShapeError::TooFewSides(num_sides));
+        }
+        check_dimension("side length", side_length)?;
+        Ok(RegularPolygon { side_length, num_sides })
+    }
+
+    // Radius of the circle passing through every vertex: s / (2 * sin(π / n)).
+    pub fn circumradius(&self) -> f64 {
+        let angle = std::f64::consts::PI / self.num_sides as f64;
+        self.side_length.into() / (2.0 * angle.sin())
+    }
+}
+
+// Area of a regular polygon: n * s² / (4 * tan(π / n)).
+impl<T: Into<f64> + Copy + fmt::Debug> Area for RegularPolygon<T> {
+    fn area(&self) -> f64 {
+        let n = self.num_sides as f64;
+        let s = self.side_length.into();
+        n * s * s / (4.0 * (std::f64::consts::PI / n).tan())
+    }
+}
+
+// Perimeter of a regular polygon: n * s.
+impl<T: Into<f64> + Copy> Perimeter for RegularPolygon<T> {
+    fn perimeter(&self) -> f64 {
+        self.num_sides as f64 * self.side_length.into()
+    }
+}
+
+// The polygon is drawn with one side lying flat along the bottom. Its vertices sit on the
+// circumscribed circle, so the box is found from the extremes of their coordinates.
+impl<T: Into<f64> + Copy> BoundingBox for RegularPolygon<T> {
+    fn bounding_box(&self) -> Rectangle<f64> {
+        use std::f64::consts::PI;
+        let n = self.num_sides as f64;
+        let radius = self.circumradius();
+        let (mut min_x, mut max_x, mut min_y, mut max_y) = (0.0f64, 0.0f64, 0.0f64, 0.0f64);
+        for k in 0..self.num_sides {
+            // The two bottom vertices are at -π/2 ± π/n, which makes the bottom side horizontal.
+            let angle = -PI / 2.0 + PI / n + 2.0 * PI * k as f64 / n;
+            let (x, y) = (radius * angle.cos(), radius * angle.sin());
+            min_x = min_x.min(x);
+            max_x = max_x.max(x);
+            min_y = min_y.min(y);
+            max_y = max_y.max(y);
+        }
+        Rectangle {
+            width: max_x - min_x,
+            length: max_y - min_y,
+        }
+    }
+}
+
+// Displays as e.g. "RegularPolygon 6 x 2" (six sides of length 2).
+impl<T: fmt::Display> fmt::Display for RegularPolygon<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RegularPolygon {} x {}", self.num_sides, self.side_length)
+    }
+}
+
+// A generic struct `Triangle` described by the lengths of its three sides.
+#[derive(Debug)] // Derive the `Debug` trait so triangles can be printed by `print_area`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Triangle<T> {
+    a: T,   // Length of the first side, of generic type `T`.
+    b: T,   // Length of the second side.
+    c: T,   // Length of the third side.
+}
+
+impl<T: Into<f64> + Copy> Triangle<T> {
+    // Build a triangle only if the three sides satisfy the triangle inequality,
+    // i.e. the sum of any two sides is strictly greater than the third.
+    // Rejecting impossible triangles here means `area` never has to take
+    // the square root of a negative number and produce `NaN`.
+    pub fn new(a: T, b: T, c: T) -> Result<Self, ShapeError> {
+        check_dimension("side a", a)?;
+        check_dimension("side b", b)?;
+        check_dimension("side c", c)?;
+        let (x, y, z) = (a.into(), b.into(), c.into());
+        if x + y > z && x + z > y && y + z > x {
+            Ok(Triangle { a, b, c })
+        } else {
+            Err(ShapeError::TriangleInequality)
+        }
+    }
+
+    pub fn sides(&self) -> (T, T, T) {
+        (self.a, self.b, self.c)
+    }
+}
+
+// Points whose cross product is at most this far from zero count as collinear.
+const COLLINEAR_EPSILON: f64 = 1e-6;
+
+impl Triangle<f64> {
+    // Build a triangle from its corners instead of its side lengths.
+    // Collinear (or nearly collinear) points are rejected as `Degenerate`. A sliver that
+    // passes that check but is so thin that rounding breaks the triangle inequality for
+    // the computed sides is reported as `Degenerate` too.
+    pub fn from_points(a: Point<f64>, b: Point<f64>, c: Point<f64>) -> Result<Self, ShapeError> {
+        if Triangle::area_from_points(a, b, c) * 2.0 <= COLLINEAR_EPSILON {
+            return Err(ShapeError::Degenerate);
+        }
+        let distance = |p: Point<f64>, q: Point<f64>| (p.x - q.x).hypot(p.y - q.y);
+        Triangle::new(distance(b, c), distance(a, c), distance(a, b)).map_err(|err| match err {
+            ShapeError::TriangleInequality => ShapeError::Degenerate,
+            other => other,
+        })
+    }
+
+    // The area straight from the corners: half the magnitude of the cross product of two
+    // edge vectors. No square roots are involved, so this is both faster and more accurate
+    // than building the triangle and applying Heron's formula.
+    pub fn area_from_points(a: Point<f64>, b: Point<f64>, c: Point<f64>) -> f64 {
+        let cross = (b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y);
+        cross.abs() / 2.0
+    }
+}
+
+// Implement the `Area` trait for `Triangle` using Heron's formula.
+// The `T` must implement both `Into<f64>` and `Copy` traits, just like the other shapes.
+impl<T: Into<f64> + Copy + fmt::Debug> Area for Triangle<T> {
+    fn area(&self) -> f64 {
+        let (a, b, c) = (self.a.into(), self.b.into(), self.c.into());
+        // `s` is the semi-perimeter of the triangle.
+        let s = (a + b + c) / 2.0;
+        (s * (s - a) * (s - b) * (s - c)).sqrt()
+    }
+}
+
+// The perimeter of a triangle is simply the sum of its three sides.
+impl<T: Into<f64> + Copy> Perimeter for Triangle<T> {
+    fn perimeter(&self) -> f64 {
+        self.a.into() + self.b.into() + self.c.into()
+    }
+}
+
+// Scaling all three sides by the same factor keeps the triangle inequality intact.
+impl<T: Into<f64> + Copy> Scale for Triangle<T> {
+    type Output = Triangle<f64>;
+
+    fn scaled(&self, factor: f64) -> Triangle<f64> {
+        let factor = factor.abs();
+        Triangle {
+            a: self.a.into() * factor,
+            b: self.b.into() * factor,
+            c: self.c.into() * factor,
+        }
+    }
+}
+
+// Lay the longest side along the bottom. Both angles touching the longest side are acute,
+// so the opposite vertex lands above that side: the box is the longest side wide and
+// the matching height (2 * area / base) tall.
+impl<T: Into<f64> + Copy + fmt::Debug> BoundingBox for Triangle<T> {
+    fn bounding_box(&self) -> Rectangle<f64> {
+        let longest = self.a.into().max(self.b.into()).max(self.c.into());
+        Rectangle {
+            width: longest,
+            length: 2.0 * self.area() / longest,
+        }
+    }
+}
+
+// Displays as e.g. "Triangle 3, 4, 5".
+impl<T: fmt::Display> fmt::Display for Triangle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Triangle {}, {}, {}", self.a, self.b, self.c)
+    }
+}
+
+// Exact areas through `AreaOf`. The rectangle-like shapes multiply in `T` itself, so
+// integer dimensions give integer areas (overflowing panics in debug builds, like any
+// integer multiplication).
+impl<T: Copy + Mul<Output = T>> AreaOf for Rectangle<T> {
+    type Output = T;
+
+    fn area_of(&self) -> T {
+        self.width * self.length
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> AreaOf for Square<T> {
+    type Output = T;
+
+    fn area_of(&self) -> T {
+        self.side * self.side
+    }
+}
+
+// π makes a circle's area irrational, so only float radii have an exact-type area:
+// `Circle<f32>` computes in `f32` and `Circle<f64>` in `f64`.
+// Integer circles still get an `f64` area through `Area`.
+impl<T: Float> AreaOf for Circle<T> {
+    type Output = T;
+
+    fn area_of(&self) -> T {
+        T::PI * self.radius * self.radius
+    }
+}
+
+// Heron's formula in the float type of the sides, using `Float::sqrt`.
+impl<T: Float> AreaOf for Triangle<T> {
+    type Output = T;
+
+    fn area_of(&self) -> T {
+        let s = (self.a + self.b + self.c) * T::HALF;
+        (s * (s - self.a) * (s - self.b) * (s - self.c)).sqrt()
+    }
+}
+
+// Positive dimensions have already been validated, so these constructors can't fail.
+impl Rectangle<PositiveF64> {
+    pub fn from_positive(width: PositiveF64, length: PositiveF64) -> Self {
+        Rectangle { width, length }
+    }
+}
+
+impl Circle<PositiveU32> {
+    pub fn from_positive(radius: PositiveU32) -> Self {
+        Circle { radius }
+    }
+}
+
+// Aliases naming the precision of the float-backed shapes.
+pub type Circle32 = Circle<f32>;
+pub type Circle64 = Circle<f64>;
+pub type Rectangle32 = Rectangle<f32>;
+pub type Rectangle64 = Rectangle<f64>;
+
+// Constructors for integer types such as `u64` that don't implement `Into<f64>` and
+// so can't use `Rectangle::new`/`Square::new`.
+impl<T: PartialOrd + Default> Rectangle<T> {
+    pub fn new_exact(width: T, length: T) -> Result<Self, ShapeError> {
+        check_exact_dimension("width", &width)?;
+        check_exact_dimension("length", &length)?;
+        Ok(Rectangle { width, length })
+    }
+}
+
+impl<T: PartialOrd + Default> Square<T> {
+    pub fn new_exact(side: T) -> Result<Self, ShapeError> {
+        check_exact_dimension("side", &side)?;
+        Ok(Square { side })
+    }
+}
+
+// Shapes are approximately equal when every dimension is, compared as `f64`.
+// The impls are identical apart from the field names, so a macro writes them.
+macro_rules! impl_approx_eq {
+    ($($shape:ident { $($field:ident),+ }),+ $(,)?) => {
+        $(
+            impl<T: Into<f64> + Copy> ApproxEq for $shape<T> {
+                fn approx_eq(&self, other: &Self, epsilon: f64) -> bool {
+                    $(
+                        Into::<f64>::into(self.$field)
+                            .approx_eq(&Into::<f64>::into(other.$field), epsilon)
+                    )&&+
+                }
+            }
+        )+
+    };
+}
+
+impl_approx_eq!(
+    Rectangle { width, length },
+    Circle { radius },
+    Annulus { outer_radius, inner_radius },
+    Square { side },
+    Ellipse { semi_major, semi_minor },
+    Trapezoid { base_a, base_b, height },
+    RegularPolygon { side_length, num_sides },
+    Triangle { a, b, c },
+);
+
+// Every dimension of an `f64` shape is multiplied by `|k|`.
+macro_rules! impl_scaled_self {
+    ($($shape:ident { $($field:ident),+ }),+ $(,)?) => {
+        $(
+            impl ScaledSelf for $shape<f64> {
+                fn scaled(&self, k: f64) -> Self {
+                    let k = k.abs();
+                    $shape { $($field: self.$field * k),+ }
+                }
+            }
+        )+
+    };
+}
+
+impl_scaled_self!(
+    Rectangle { width, length },
+    Circle { radius },
+    Annulus { outer_radius, inner_radius },
+    Square { side },
+    Ellipse { semi_major, semi_minor },
+    Trapezoid { base_a, base_b, height },
+    Triangle { a, b, c },
+);
+
+// A polygon keeps its number of sides; only the side length grows.
+impl ScaledSelf for RegularPolygon<f64> {
+    fn scaled(&self, k: f64) -> Self {
+        RegularPolygon {
+            side_length: self.side_length * k.abs(),
+            num_sides: self.num_sides,
+        }
+    }
+}
+
+// Deserializing fills in the private fields directly and skips the constructors,
+// so `Validate` runs a deserialized shape back through its constructor's checks.
+#[cfg(feature = "serde")]
+pub trait Validate: Sized {
+    fn validate(self) -> Result<Self, ShapeError>;
+}
+
+#[cfg(feature = "serde")]
+impl<T: Into<f64> + Copy> Validate for Rectangle<T> {
+    fn validate(self) -> Result<Self, ShapeError> {
+        Rectangle::new(self.width, self.length)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Into<f64> + Copy> Validate for Circle<T> {
+    fn validate(self) -> Result<Self, ShapeError> {
+        Circle::new(self.radius)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Into<f64> + Copy> Validate for Annulus<T> {
+    fn validate(self) -> Result<Self, ShapeError> {
+        Annulus::new(self.outer_radius, self.inner_radius)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Into<f64> + Copy> Validate for Square<T> {
+    fn validate(self) -> Result<Self, ShapeError> {
+        Square::new(self.side)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Into<f64> + Copy> Validate for Ellipse<T> {
+    fn validate(self) -> Result<Self, ShapeError> {
+        Ellipse::new(self.semi_major, self.semi_minor)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Into<f64> + Copy> Validate for Trapezoid<T> {
+    fn validate(self) -> Result<Self, ShapeError> {
+        Trapezoid::new(self.base_a, self.base_b, self.height)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Into<f64> + Copy> Validate for RegularPolygon<T> {
+    fn validate(self) -> Result<Self, ShapeError> {
+        RegularPolygon::new(self.side_length, self.num_sides)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Into<f64> + Copy> Validate for Triangle<T> {
+    fn validate(self) -> Result<Self, ShapeError> {
+        Triangle::new(self.a, self.b, self.c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::approx::assert_area_eq;
+
+    // Scaling every dimension by `k` should multiply the area by k². Both traits providing
+    // `scaled` are in scope here, so the call names `ScaledSelf` explicitly.
+    // The tolerance is relative, since the areas range from tiny to large.
+    fn scales_quadratically<S: ScaledSelf + Area>(shape: &S) -> bool {
+        [0.1, 1.0, 2.5].iter().all(|&k| {
+            let expected = k * k * shape.area();
+            ScaledSelf::scaled(shape, k)
+                .area()
+                .approx_eq(&expected, 1e-12 * expected)
+        })
+    }
+
+    #[test]
+    fn constructors_reject_invalid_dimensions() {
+        let errors = [
+            (Rectangle::new(-3.0, 5.0).err(), "width must not be negative"),
+            (Rectangle::new(4, 0).err(), "length must not be zero"),
+            (Circle::new(-1).err(), "radius must not be negative"),
+            (Circle::new(0.0).err(), "radius must not be zero"),
+            (Circle::new(f64::NAN).err(), "radius must be a finite number"),
+            (Triangle::new(1, 2, 10).err(), "the sum of any two sides must be greater than the third"),
+            (Trapezoid::new(3.0, 5.0, 0.0).err(), "height must not be zero"),
+            (RegularPolygon::new(1.0, 2).err(), "a polygon needs at least 3 sides, got 2"),
+            (Annulus::new(2, 2).err(), "inner radius must be smaller than the outer radius"),
+            (Circle::from_diameter(-4).err(), "diameter must not be negative"),
+        ];
+        for (err, message) in errors {
+            assert_eq!(err.map(|err| err.to_string()).as_deref(), Some(message));
+        }
+    }
+
+    #[test]
+    fn accessors_and_display() -> Result<(), ShapeError> {
+        let rect = Rectangle::new(10, 5)?;
+        assert_eq!((rect.width(), rect.length()), (10, 5));
+        assert_eq!(Circle::new(3.7)?.radius(), 3.7);
+        assert_eq!(rect.to_string(), "Rectangle 10 x 5");
+        assert_eq!(Rectangle::new(4.5, 3.2)?.to_string(), "Rectangle 4.5 x 3.2");
+        assert_eq!(Circle::new(7)?.to_string(), "Circle r=7");
+        assert_eq!(Triangle::new(2.5, 2.5, 3.0)?.to_string(), "Triangle 2.5, 2.5, 3");
+        assert_eq!(Square::new(4)?.to_string(), "Square 4");
+        assert_eq!(Ellipse::new(5, 3)?.to_string(), "Ellipse 5 x 3");
+        assert_eq!(Trapezoid::new(3, 5, 4)?.to_string(), "Trapezoid 3 | 5 h=4");
+        assert_eq!(RegularPolygon::new(2, 6)?.to_string(), "RegularPolygon 6 x 2");
+        assert_eq!(Annulus::new(5, 3)?.to_string(), "Annulus R=5 r=3");
+        assert_eq!(Rectangle2::new(3, 4.5)?.to_string(), "Rectangle 3 x 4.5");
+        Ok(())
+    }
+
+    #[test]
+    fn describe_uses_the_default_or_the_override() -> Result<(), ShapeError> {
+        let rect = Rectangle::new(10, 5)?;
+        assert_eq!(rect.describe(), "Rectangle { width: 10, length: 5 } has area 50.000");
+        let circle = "Circle of radius 2 has area 12.566 and circumference 12.566";
+        assert_eq!(Circle::new(2)?.describe(), circle);
+        // The blanket impls forward to the override rather than using the default.
+        let boxed: Box<dyn Area> = Box::new(Circle::new(2)?);
+        assert_eq!(boxed.describe(), circle);
+        assert_eq!((&&rect).describe(), rect.describe());
+        assert_eq!((3, 4).describe(), "(3, 4) has area 12.000");
+        Ok(())
+    }
+
+    #[test]
+    fn shape_summary_includes_area_and_perimeter() -> Result<(), ShapeError> {
+        let shapes: Vec<Box<dyn Shape>> = vec![
+            Box::new(Rectangle::new(3, 4)?),
+            Box::new(Circle::new(1.0)?),
+            Box::new(Triangle::new(3, 4, 5)?),
+            Box::new(Ellipse::new(5.0, 3.0)?),
+        ];
+        let summaries: Vec<String> = shapes.iter().map(|shape| shape.summary()).collect();
+        assert_eq!(
+            summaries,
+            [
+                "Rectangle { width: 3, length: 4 }: area 12.00, perimeter 14.00",
+                "Circle { radius: 1.0 }: area 3.14, perimeter 6.28",
+                "Triangle { a: 3, b: 4, c: 5 }: area 6.00, perimeter 12.00",
+                "Ellipse { semi_major: 5.0, semi_minor: 3.0 }: area 47.12, perimeter 25.53",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn areas_and_perimeters() -> Result<(), ShapeError> {
+        assert_eq!(Rectangle::new(10, 5)?.area(), 50.0);
+        assert_eq!(Rectangle::new(10, 5)?.perimeter(), 30.0);
+        assert_eq!(Triangle::new(3, 4, 5)?.area(), 6.0);
+        assert_eq!(Triangle::new(3, 4, 5)?.perimeter(), 12.0);
+        assert_eq!(Triangle::new(2.5, 2.5, 3.0)?.area(), 3.0);
+        assert_area_eq!(Circle::new(7)?.perimeter(), 14.0 * std::f64::consts::PI);
+        assert_eq!(Square::new(4)?.area(), 16.0);
+        assert_eq!(Square::new(4)?.perimeter(), 16.0);
+        assert_eq!(Rectangle2::new(3, 4.5)?.area(), Rectangle::new(3.0, 4.5)?.area());
+        // Tuples stand in for rectangles.
+        assert_eq!((3, 4).area(), 12.0);
+        assert_eq!((2.5, 4.0).perimeter(), 13.0);
+        Ok(())
+    }
+
+    #[test]
+    fn scaling_multiplies_the_area_by_k_squared() -> Result<(), ShapeError> {
+        let rect = Rectangle::new(10, 5)?;
+        for (factor, expected) in [(2.0, "Rectangle 20 x 10"), (0.5, "Rectangle 5 x 2.5")] {
+            assert_eq!(rect.scaled(factor).to_string(), expected);
+            assert_eq!(rect.scaled(factor).area(), factor * factor * rect.area());
+        }
+        assert_eq!(rect.scaled(0.0).area(), 0.0);
+        // A negative factor scales by its absolute value.
+        assert_eq!(rect.scaled(-2.0).to_string(), "Rectangle 20 x 10");
+        assert_eq!(Circle::new(7)?.scaled(3.0).to_string(), "Circle r=21");
+        assert_eq!(Triangle::new(3, 4, 5)?.scaled(2.0).to_string(), "Triangle 6, 8, 10");
+        assert_eq!(Square::new(4)?.scaled(0.5).to_string(), "Square 2");
+        // `ScaledSelf` keeps the concrete type.
+        let doubled: Circle<f64> = ScaledSelf::scaled(&Circle::new(1.5)?, 2.0);
+        assert_eq!(doubled, Circle::new(3.0)?);
+        assert!(scales_quadratically(&Rectangle::new(4.5, 3.2)?));
+        assert!(scales_quadratically(&Circle::new(3.7)?));
+        assert!(scales_quadratically(&Annulus::new(5.0, 3.0)?));
+        assert!(scales_quadratically(&Square::new(4.0)?));
+        assert!(scales_quadratically(&Ellipse::new(5.0, 3.0)?));
+        assert!(scales_quadratically(&Trapezoid::new(3.0, 5.0, 4.0)?));
+        assert!(scales_quadratically(&RegularPolygon::new(2.0, 6)?));
+        assert!(scales_quadratically(&Triangle::new(3.0, 4.0, 5.0)?));
+        Ok(())
+    }
+
+    #[test]
+    fn squares_convert_to_rectangles_and_back() -> Result<(), ShapeError> {
+        let square_as_rect: Rectangle<i32> = Square::new(4)?.into();
+        assert_eq!(square_as_rect.area(), 16.0);
+        assert_eq!(square_as_rect.try_into_square().map(|square| square.side()), Ok(4));
+        let not_square = Rectangle::new(2, 3)?.try_into_square().map(|square| square.side());
+        assert_eq!(not_square, Err(Rectangle::new(2, 3)?));
+        Ok(())
+    }
+
+    #[test]
+    fn ellipses() -> Result<(), ShapeError> {
+        let ellipse = Ellipse::new(5, 3)?;
+        assert_area_eq!(ellipse.area(), 15.0 * std::f64::consts::PI);
+        // Ramanujan's approximation is within about 5e-7, relative, of the exact elliptic
+        // integral, 25.526999.
+        assert_area_eq!(ellipse.perimeter(), 25.526999, 25.526999 * 1e-6);
+        // With equal axes the ellipse is a circle.
+        let (round, circle) = (Ellipse::new(3.0, 3.0)?, Circle::new(3.0)?);
+        assert_area_eq!(round.area(), circle.area());
+        assert_area_eq!(round.perimeter(), circle.perimeter());
+        Ok(())
+    }
+
+    #[test]
+    fn trapezoids() -> Result<(), ShapeError> {
+        assert_eq!(Trapezoid::new(3, 5, 4)?.area(), 16.0);
+        assert_eq!(Trapezoid::new(2.5, 1.5, 3.0)?.area(), 6.0);
+        // Equal bases make a parallelogram, with the area of the matching rectangle.
+        let parallelogram = Trapezoid::new(6, 6, 2)?;
+        assert!(parallelogram.is_parallelogram());
+        assert_eq!(parallelogram.area(), Rectangle::new(6, 2)?.area());
+        assert!(!Trapezoid::new(3, 5, 4)?.is_parallelogram());
+        Ok(())
+    }
+
+    #[test]
+    fn regular_polygons() -> Result<(), ShapeError> {
+        let hexagon = RegularPolygon::new(2, 6)?;
+        assert_eq!(hexagon.area(), 10.392304845413264);
+        assert_eq!(hexagon.perimeter(), 12.0);
+        assert_area_eq!(RegularPolygon::new(3.0, 4)?.area(), Square::new(3.0)?.area());
+        // With the circumradius fixed at 1, more sides get closer to the circle's π.
+        let mut previous_gap = f64::INFINITY;
+        for sides in [6, 60, 600] {
+            let side_length = 2.0 * (std::f64::consts::PI / sides as f64).sin();
+            let polygon = RegularPolygon::new(side_length, sides)?;
+            assert_area_eq!(polygon.circumradius(), 1.0);
+            let gap = std::f64::consts::PI - polygon.area();
+            assert!(0.0 < gap && gap < previous_gap);
+            previous_gap = gap;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn annulus_is_the_difference_of_two_circles() -> Result<(), ShapeError> {
+        let (outer, inner) = (Circle::new(5.0)?, Circle::new(3.0)?);
+        let difference = outer.area() - inner.area();
+        assert_eq!(Annulus::from_circles(outer, inner)?.area(), difference);
+        Ok(())
+    }
+
+    #[test]
+    fn rectangles_from_corners() -> Result<(), ShapeError> {
+        let from_corners = Rectangle::from_corners(Point::new(1, 1), Point::new(4, 3))?;
+        assert_eq!(from_corners, Rectangle::new(3, 2)?);
+        assert_eq!(Rectangle::from_corners(Point::new(4, 3), Point::new(1, 1))?, from_corners);
+        let negative = Rectangle::from_corners(Point::new(-2.5, 1.0), Point::new(1.5, -2.0))?;
+        assert_eq!(negative.area(), 12.0);
+        let same = Rectangle::from_corners(Point::new(2, 2), Point::new(2, 2));
+        assert_eq!(same, Err(ShapeError::ZeroDimension("width")));
+        Ok(())
+    }
+
+    #[test]
+    fn conversions_from_tuples_and_arrays() -> Result<(), ShapeError> {
+        let from_tuple = Rectangle::try_from((6, 2))?;
+        let from_array: Rectangle<f64> = [1.5, 4.0].try_into()?;
+        assert_eq!(from_array, Rectangle::new(1.5, 4.0)?);
+        assert_eq!(Rectangle::try_from((-1.0, 2.0)), Err(ShapeError::NegativeDimension("width")));
+        assert_eq!(Rectangle::try_from([3, 0]), Err(ShapeError::ZeroDimension("length")));
+        assert_eq!(<(i32, i32)>::from(from_tuple), (6, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn approximately_equal_shapes() -> Result<(), ShapeError> {
+        // 0.3 - 0.2 is 0.09999999999999998, not 0.1.
+        let (tenth, almost_tenth) = (Circle::new(0.1)?, Circle::new(0.3 - 0.2)?);
+        assert_ne!(tenth.area(), almost_tenth.area());
+        assert!(tenth.area().approx_eq(&almost_tenth.area(), 1e-12));
+        assert!(!tenth.area().approx_eq(&almost_tenth.area(), 1e-20));
+        assert!(tenth.approx_eq(&almost_tenth, 1e-12));
+        assert_area_eq!(tenth, almost_tenth, 1e-12);
+        assert_area_eq!(Rectangle::new(2.0, 3.0)?.area(), 6.0);
+        Ok(())
+    }
+
+    #[test]
+    fn predicates() -> Result<(), ShapeError> {
+        assert!(Rectangle::new(7, 7)?.is_square());
+        assert!(!Rectangle::new(7, 8)?.is_square());
+        // 0.1 * 3.0 is 0.30000000000000004, so this is only a square within an epsilon.
+        let near_square = Rectangle::new(0.1 * 3.0, 0.3)?;
+        assert!(!near_square.is_square());
+        assert!(near_square.is_square_approx(1e-9));
+        assert_eq!(Rectangle::new(16, 9)?.aspect_ratio(), 16.0 / 9.0);
+        assert_eq!(Rectangle::new(2.0, 4.0)?.aspect_ratio(), 0.5);
+        assert_eq!(Rectangle::new(3, 3)?.aspect_ratio(), 1.0);
+        assert!(Circle::new(1)?.is_unit() && Circle::new(1.0)?.is_unit());
+        assert!(!Circle::new(3.7)?.is_unit());
+        Ok(())
+    }
+
+    #[test]
+    fn circles_from_a_diameter() -> Result<(), ShapeError> {
+        assert_eq!(Circle::from_diameter(10)?.area(), Circle::new(5)?.area());
+        // An odd integer diameter isn't truncated.
+        let from_seven = Circle::from_diameter(7)?;
+        assert_eq!(from_seven.radius(), 3.5);
+        assert_eq!(from_seven.diameter(), 7.0);
+        assert_area_eq!(from_seven.circumference(), 7.0 * std::f64::consts::PI);
+        Ok(())
+    }
+
+    #[test]
+    fn rounded_areas() -> Result<(), ShapeError> {
+        // This radius makes the area exactly 12.5 in `f64`.
+        let half_way = Circle::new((12.5 / std::f64::consts::PI).sqrt())?;
+        assert_eq!(half_way.area(), 12.5);
+        assert_eq!(half_way.area_rounded(Rounding::Floor), Ok(12));
+        assert_eq!(half_way.area_rounded(Rounding::Ceil), Ok(13));
+        assert_eq!(half_way.area_rounded(Rounding::Nearest), Ok(13));
+        assert_eq!(Circle::new(3.7)?.area_rounded(Rounding::Nearest), Ok(43));
+        // Unvalidated tuples can have areas with no `u64` representation.
+        assert_eq!((-3.0, 4.0).area_rounded(Rounding::Floor), Err(RoundingError::Negative(-12.0)));
+        assert_eq!((1e10, 1e10).area_rounded(Rounding::Ceil), Err(RoundingError::TooLarge(1e20)));
+        assert_eq!((f64::NAN, 1.0).area_rounded(Rounding::Nearest), Err(RoundingError::NotANumber));
+        Ok(())
+    }
+
+    #[test]
+    fn sectors_and_segments() -> Result<(), ShapeError> {
+        let unit = Circle::new(1.0)?;
+        let (full, half) = (2.0 * std::f64::consts::PI, std::f64::consts::PI);
+        assert_area_eq!(unit.sector_area(full), unit.area());
+        assert_area_eq!(unit.segment_area(full), unit.area());
+        assert_area_eq!(unit.segment_area(half), unit.area() / 2.0);
+        assert_area_eq!(unit.sector_area(1e-12), 0.0);
+        assert_area_eq!(unit.segment_area(1e-6), 0.0);
+        assert_area_eq!(unit.sector_area_deg(90.0), unit.sector_area(half / 2.0));
+        assert_area_eq!(unit.segment_area(half / 2.0), (half / 2.0 - 1.0) / 2.0);
+        // Angles outside [0, 2π] are clamped.
+        assert_eq!(unit.sector_area(-1.0), 0.0);
+        assert_eq!(unit.sector_area(3.0 * full), unit.sector_area(full));
+        Ok(())
+    }
+
+    #[test]
+    fn polygons_close_in_on_a_circle() -> Result<(), ShapeError> {
+        let unit = Circle::new(1.0)?;
+        let mut previous = (f64::INFINITY, f64::INFINITY);
+        for sides in [6, 12, 64, 360] {
+            let (inscribed, inner_error) = unit.approximate(sides);
+            let (circumscribed, outer_error) = unit.approximate_circumscribed(sides);
+            assert!(inscribed.area() < unit.area() && unit.area() < circumscribed.area());
+            assert!(inner_error < previous.0 && outer_error < previous.1);
+            previous = (inner_error, outer_error);
+        }
+        assert!(previous.0 < 0.001 && previous.1 < 0.001);
+        // Fewer than 3 sides is raised to a triangle.
+        assert_eq!(unit.approximate(1).0.to_string(), unit.approximate(3).0.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn exact_integer_areas() -> Result<(), ShapeError> {
+        // 2^53 + 1 can't be represented as an `f64`, so only the `u64` path is exact.
+        let huge = Rectangle::new_exact(9_007_199_254_740_993u64, 3)?;
+        assert_eq!(huge.area_of(), 27_021_597_764_222_979);
+        assert_eq!(huge.width() as f64 * huge.length() as f64, 27_021_597_764_222_976.0);
+        assert_eq!(Square::new_exact(12u64)?.area_of(), 144);
+        assert_eq!(Rectangle::new_exact(0u64, 3), Err(ShapeError::ZeroDimension("width")));
+        assert_eq!(as_f64_area(&Rectangle::new(10, 5)?), 50.0);
+        assert_eq!(as_f64_area(&Circle::new(3.7)?), Circle::new(3.7)?.area());
+        Ok(())
+    }
+
+    #[test]
+    fn f32_and_f64_precision() -> Result<(), ShapeError> {
+        let small: f32 = Circle32::new(1.5)?.area_of();
+        let precise: f64 = Circle64::new(1.5)?.area_of();
+        assert_eq!(small, 7.0685835);
+        assert_eq!(precise, 7.0685834705770345);
+        assert!(((small as f64) - precise).abs() / precise < f32::EPSILON as f64);
+        let rect32: f32 = Rectangle32::new(0.1, 0.2)?.area_of();
+        let rect64: f64 = Rectangle64::new(0.1, 0.2)?.area_of();
+        assert_eq!((rect32, rect64), (0.020000001, 0.020000000000000004));
+        assert_eq!(Triangle::new(3.0f32, 4.0, 5.0)?.area_of(), 6.0);
+        // `Area` still works in `f64` for an `f32` circle.
+        assert_eq!(Circle32::new(1.5)?.area(), precise);
+        Ok(())
+    }
+
+    #[test]
+    fn default_shapes_are_unit_shapes() -> Result<(), ShapeError> {
+        let unit_rect: Rectangle<i32> = Rectangle::default();
+        assert_eq!(unit_rect, Rectangle::new(1, 1)?);
+        assert_eq!(Circle::<f64>::default().area(), std::f64::consts::PI);
+        assert_eq!(Circle::<i32>::default().area(), std::f64::consts::PI);
+        let mut unit_squares: [Square<f64>; 3] = Default::default();
+        unit_squares[0] = Square::new(3.0)?;
+        let taken = std::mem::take(&mut unit_squares[0]);
+        assert_eq!((taken.side(), unit_squares[0].side()), (3.0, 1.0));
+        Ok(())
+    }
+
+    #[test]
+    fn bounding_boxes_enclose_the_shape() -> Result<(), ShapeError> {
+        let rect = Rectangle::new(10, 5)?;
+        assert_eq!(rect.bounding_box(), Rectangle::new(10.0, 5.0)?);
+        assert_eq!(Circle::new(7)?.bounding_box(), Rectangle::new(14.0, 14.0)?);
+        let triangle_box = Triangle::new(3, 4, 5)?.bounding_box();
+        assert_area_eq!(triangle_box, Rectangle::new(5.0, 2.4)?);
+        assert_eq!(Annulus::new(5, 3)?.bounding_box(), Rectangle::new(10.0, 10.0)?);
+        // Only rectangles fill their box; every other shape is smaller.
+        let boxed: [(f64, Rectangle<f64>); 4] = [
+            (Ellipse::new(5, 3)?.area(), Ellipse::new(5, 3)?.bounding_box()),
+            (Trapezoid::new(3, 5, 4)?.area(), Trapezoid::new(3, 5, 4)?.bounding_box()),
+            (RegularPolygon::new(2, 6)?.area(), RegularPolygon::new(2, 6)?.bounding_box()),
+            (Annulus::new(5, 3)?.area(), Annulus::new(5, 3)?.bounding_box()),
+        ];
+        for (area, bounds) in boxed {
+            assert!(area < bounds.area());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn triangles_from_points() -> Result<(), ShapeError> {
+        let (origin, p, q) = (Point::new(0.0, 0.0), Point::new(4.0, 0.0), Point::new(0.0, 3.0));
+        let cornered = Triangle::from_points(origin, p, q)?;
+        assert_eq!(cornered.to_string(), "Triangle 5, 3, 4");
+        assert_eq!(cornered.area(), 6.0);
+        assert_eq!(Triangle::area_from_points(origin, p, q), 6.0);
+        // Nearly collinear points are degenerate below a cross product of 1e-6.
+        let sliver = |height| Triangle::from_points(origin, Point::new(1.0, 0.0), Point::new(0.5, height));
+        assert_eq!(sliver(0.0).err(), Some(ShapeError::Degenerate));
+        assert_eq!(sliver(5e-7).err(), Some(ShapeError::Degenerate));
+        assert_area_eq!(sliver(2e-6)?.area(), 1e-6, 1e-9);
+        Ok(())
+    }
+
+    #[test]
+    fn rotating_a_rectangle_swaps_its_sides() -> Result<(), ShapeError> {
+        assert_eq!(Rectangle::new(10, 5)?.rotated(), Rectangle::new(5, 10)?);
+        Ok(())
+    }
+
+    #[test]
+    fn positive_dimensions_build_valid_shapes() {
+        let (width, length) = (PositiveF64::try_new(2.5), PositiveF64::try_new(4.0));
+        let rect = Rectangle::from_positive(width.unwrap(), length.unwrap());
+        assert_eq!(rect.area(), 10.0);
+        // Even the smallest accepted dimension gives a positive area.
+        let smallest = PositiveF64::try_new(1.5e-154).unwrap();
+        assert!(Rectangle::from_positive(smallest, smallest).area() > 0.0);
+        let circles = (1..=5).filter_map(PositiveU32::try_new).map(Circle::from_positive);
+        assert!(circles.into_iter().all(|circle| circle.area() > 0.0));
+    }
+}
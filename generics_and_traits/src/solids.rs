@@ -0,0 +1,159 @@
+// Three-dimensional shapes, following the same pattern as the 2D ones in `shapes`:
+// generic over `T: Into<f64> + Copy`, private fields, validating constructors.
+use std::f64::consts::PI;
+use std::fmt;
+
+use crate::shapes::{check_dimension, Area, Circle, Perimeter, ShapeError};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// The 3D counterpart of `Area`.
+pub trait Volume: fmt::Debug {
+    fn volume(&self) -> f64; // The space enclosed by the solid, as an `f64`.
+}
+
+// The total area of a solid's outer faces.
+pub trait SurfaceArea {
+    fn surface_area(&self) -> f64;
+}
+
+// A sphere described by its radius.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Sphere<T> {
+    radius: T,
+}
+
+impl<T: Into<f64> + Copy> Sphere<T> {
+    pub fn new(radius: T) -> Result<Self, ShapeError> {
+        check_dimension("radius", radius)?;
+        Ok(Sphere { radius })
+    }
+}
+
+// V = 4/3 * π * r³
+impl<T: Into<f64> + Copy + fmt::Debug> Volume for Sphere<T> {
+    fn volume(&self) -> f64 {
+        let r = self.radius.into();
+        4.0 / 3.0 * PI * r * r * r
+    }
+}
+
+// A = 4 * π * r²
+impl<T: Into<f64> + Copy> SurfaceArea for Sphere<T> {
+    fn surface_area(&self) -> f64 {
+        let r = self.radius.into();
+        4.0 * PI * r * r
+    }
+}
+
+// A rectangular box: a `Rectangle` extruded by a height.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Cuboid<T> {
+    width: T,
+    length: T,
+    height: T,
+}
+
+impl<T: Into<f64> + Copy> Cuboid<T> {
+    pub fn new(width: T, length: T, height: T) -> Result<Self, ShapeError> {
+        check_dimension("width", width)?;
+        check_dimension("length", length)?;
+        check_dimension("height", height)?;
+        Ok(Cuboid {
+            width,
+            length,
+            height,
+        })
+    }
+}
+
+// V = w * l * h
+impl<T: Into<f64> + Copy + fmt::Debug> Volume for Cuboid<T> {
+    fn volume(&self) -> f64 {
+        self.width.into() * self.length.into() * self.height.into()
+    }
+}
+
+// Six faces in three pairs: A = 2 * (wl + wh + lh)
+impl<T: Into<f64> + Copy> SurfaceArea for Cuboid<T> {
+    fn surface_area(&self) -> f64 {
+        let (w, l, h) = (self.width.into(), self.length.into(), self.height.into());
+        2.0 * (w * l + w * h + l * h)
+    }
+}
+
+// A cylinder is composed from a circular base and a height, so it reuses the `Circle`
+// code (and its validation) rather than storing a bare radius.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Cylinder<T> {
+    base: Circle<T>,
+    height: T,
+}
+
+impl<T: Into<f64> + Copy> Cylinder<T> {
+    // The base was validated when it was built. A height of zero is allowed and gives a
+    // flat disc with no volume; negative and non-finite heights are still rejected.
+    pub fn new(base: Circle<T>, height: T) -> Result<Self, ShapeError> {
+        if height.into() != 0.0 {
+            check_dimension("height", height)?;
+        }
+        Ok(Cylinder { base, height })
+    }
+}
+
+// V = base area * h
+impl<T: Into<f64> + Copy + fmt::Debug> Volume for Cylinder<T> {
+    fn volume(&self) -> f64 {
+        self.base.area() * self.height.into()
+    }
+}
+
+// Top and bottom discs plus the side, which unrolls into a rectangle of
+// circumference x height. With a height of 0 only the two discs remain.
+impl<T: Into<f64> + Copy + fmt::Debug> SurfaceArea for Cylinder<T> {
+    fn surface_area(&self) -> f64 {
+        2.0 * self.base.area() + self.base.perimeter() * self.height.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::approx::assert_area_eq;
+
+    #[test]
+    fn volumes_and_surface_areas() -> Result<(), ShapeError> {
+        let unit_sphere = Sphere::new(1.0)?;
+        assert_area_eq!(unit_sphere.volume(), 4.0 / 3.0 * PI);
+        assert_area_eq!(unit_sphere.surface_area(), 4.0 * PI);
+        let cuboid = Cuboid::new(2, 3, 4)?;
+        assert_eq!((cuboid.volume(), cuboid.surface_area()), (24.0, 52.0));
+        let cylinder = Cylinder::new(Circle::new(2.0)?, 5.0)?;
+        assert_area_eq!(cylinder.volume(), 20.0 * PI);
+        assert_area_eq!(cylinder.surface_area(), 28.0 * PI);
+        Ok(())
+    }
+
+    #[test]
+    fn a_flat_cylinder_is_two_discs() -> Result<(), ShapeError> {
+        let disc = Cylinder::new(Circle::new(2.0)?, 0.0)?;
+        assert_eq!(disc.volume(), 0.0);
+        assert_eq!(disc.surface_area(), 2.0 * Circle::new(2.0)?.area());
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_dimensions_are_rejected() -> Result<(), ShapeError> {
+        assert_eq!(Cuboid::new(2, 3, -4).err(), Some(ShapeError::NegativeDimension("height")));
+        assert_eq!(Sphere::new(0.0).err(), Some(ShapeError::ZeroDimension("radius")));
+        assert_eq!(
+            Cylinder::new(Circle::new(1.0)?, -1.0).err(),
+            Some(ShapeError::NegativeDimension("height"))
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+// Summary statistics over the areas of a collection of shapes.
+use std::iter::Sum;
+
+use crate::shapes::Area;
+
+// The total area of a sequence of shapes. Implementing `Sum` for it lets any iterator of
+// shapes be summed directly: `shapes.iter().sum::<TotalArea>()`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TotalArea(pub f64);
+
+impl<S: Area> Sum<S> for TotalArea {
+    fn sum<I: Iterator<Item = S>>(iter: I) -> Self {
+        // `fold` from 0.0 rather than `f64::sum`, whose empty sum is -0.0.
+        TotalArea(iter.fold(0.0, |total, shape| total + shape.area()))
+    }
+}
+
+// Sum the areas of anything iterable whose items are shapes. With the blanket impls for
+// `&T` and `Box<T>`, that covers owned shapes, references and boxes alike.
+// An empty iterator sums to 0.
+pub fn sum_areas<I, S>(shapes: I) -> f64
+where
+    I: IntoIterator<Item = S>,
+    S: Area,
+{
+    shapes.into_iter().sum::<TotalArea>().0
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AreaStats {
+    pub count: usize,
+    pub total: f64,
+    pub mean: f64,
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+// Returns `None` for an empty collection, which has no mean, median, min or max.
+// Generic like `total_area`, so it takes `Box<dyn Area>`, `&dyn Area` or concrete shapes.
+pub fn area_stats<S: Area>(shapes: &[S]) -> Option<AreaStats> {
+    let mut areas: Vec<f64> = shapes.iter().map(|shape| shape.area()).collect();
+    if areas.is_empty() {
+        return None;
+    }
+    // Sorting gives min, max and median in one go; `total_cmp` because `f64` isn't `Ord`.
+    areas.sort_by(f64::total_cmp);
+    let count = areas.len();
+    let total: f64 = areas.iter().sum();
+    let mid = count / 2;
+    // An even count has two middle values, so the median is their average.
+    let median = if count.is_multiple_of(2) {
+        (areas[mid - 1] + areas[mid]) / 2.0
+    } else {
+        areas[mid]
+    };
+    Some(AreaStats {
+        count,
+        total,
+        mean: total / count as f64,
+        median,
+        min: areas[0],
+        max: areas[count - 1],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::{Circle, Rectangle, ShapeError, Square};
+
+    #[test]
+    fn sums_owned_borrowed_and_boxed_shapes() -> Result<(), ShapeError> {
+        let owned = vec![Rectangle::new(1.0, 2.0)?, Rectangle::new(3.0, 4.0)?];
+        assert_eq!(sum_areas(&owned), 14.0);
+        let boxed: Vec<Box<dyn Area>> = vec![Box::new((3.0, 4.0)), Box::new((5, 5))];
+        assert_eq!(sum_areas(&boxed), 37.0);
+        let TotalArea(summed) = boxed.iter().sum();
+        assert_eq!(summed, 37.0);
+        assert_eq!(sum_areas(owned), 14.0);
+        // Positive zero, not the -0.0 that `f64::sum` gives for nothing.
+        assert!(sum_areas(Vec::<Circle<f64>>::new()).is_sign_positive());
+        Ok(())
+    }
+
+    #[test]
+    fn odd_and_even_medians() -> Result<(), ShapeError> {
+        let three = [Square::new(3)?, Square::new(1)?, Square::new(2)?];
+        assert_eq!(
+            area_stats(&three),
+            Some(AreaStats { count: 3, total: 14.0, mean: 14.0 / 3.0, median: 4.0, min: 1.0, max: 9.0 })
+        );
+        let four = [Square::new(1)?, Square::new(4)?, Square::new(2)?, Square::new(3)?];
+        assert_eq!(area_stats(&four).map(|stats| stats.median), Some(6.5));
+        assert_eq!(area_stats::<Box<dyn Area>>(&[]), None);
+        Ok(())
+    }
+}
@@ -0,0 +1,222 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Add, Sub};
+
+use crate::shapes::{Area, Circle, Rectangle};
+
+// Newtype wrappers around `f64` that record which unit a length is measured in.
+// A `Rectangle<Feet>` and a `Rectangle<Meters>` are different types, so they can't be mixed up by accident.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Meters(pub f64);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Feet(pub f64);
+
+// Implementing `From<Meters> for f64` provides `Into<f64>` for `Meters`,
+// which is exactly the bound the generic shapes require.
+impl From<Meters> for f64 {
+    fn from(meters: Meters) -> f64 {
+        meters.0
+    }
+}
+
+impl From<Feet> for f64 {
+    fn from(feet: Feet) -> f64 {
+        feet.0
+    }
+}
+
+// Explicit conversions between the two units (1 ft = 0.3048 m).
+impl Feet {
+    pub fn to_meters(self) -> Meters {
+        Meters(self.0 * Feet::METERS_PER_UNIT)
+    }
+}
+
+impl Meters {
+    pub fn to_feet(self) -> Feet {
+        Feet(self.0 / Feet::METERS_PER_UNIT)
+    }
+}
+
+impl fmt::Display for Meters {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} m", self.0)
+    }
+}
+
+impl fmt::Display for Feet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ft", self.0)
+    }
+}
+
+// A length unit knows how many meters one of it is.
+// Associated constants let generic code get that factor from the type alone.
+pub trait LengthUnit: Into<f64> + Copy + fmt::Debug {
+    const METERS_PER_UNIT: f64;
+    // The area unit a shape measured in this length unit has, e.g. `SquareMeters` for `Meters`.
+    type Squared: AreaUnit;
+}
+
+impl LengthUnit for Meters {
+    const METERS_PER_UNIT: f64 = 1.0;
+    type Squared = SquareMeters;
+}
+
+impl LengthUnit for Feet {
+    const METERS_PER_UNIT: f64 = 0.3048;
+    type Squared = SquareFeet;
+}
+
+// Ask for the area of a unit-tagged shape in a different unit, e.g.
+// `rect_in_feet.area_in::<Meters>()` for square meters.
+// Area is two-dimensional, so the linear conversion factor is applied squared.
+pub trait AreaIn {
+    fn area_in<U: LengthUnit>(&self) -> f64;
+}
+
+// The factor that converts an area measured in `Src`² into `Dst`².
+fn area_factor<Src: LengthUnit, Dst: LengthUnit>() -> f64 {
+    let linear = Src::METERS_PER_UNIT / Dst::METERS_PER_UNIT;
+    linear * linear
+}
+
+impl<L: LengthUnit> AreaIn for Rectangle<L> {
+    fn area_in<U: LengthUnit>(&self) -> f64 {
+        self.area() * area_factor::<L, U>()
+    }
+}
+
+impl<L: LengthUnit> AreaIn for Circle<L> {
+    fn area_in<U: LengthUnit>(&self) -> f64 {
+        self.area() * area_factor::<L, U>()
+    }
+}
+
+// Area units as zero-sized marker types: they carry no data, only a type and a few constants.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SquareMeters;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SquareFeet;
+
+pub trait AreaUnit {
+    const SQUARE_METERS_PER_UNIT: f64;
+    const SYMBOL: &'static str;
+}
+
+impl AreaUnit for SquareMeters {
+    const SQUARE_METERS_PER_UNIT: f64 = 1.0;
+    const SYMBOL: &'static str = "m²";
+}
+
+impl AreaUnit for SquareFeet {
+    const SQUARE_METERS_PER_UNIT: f64 = Feet::METERS_PER_UNIT * Feet::METERS_PER_UNIT;
+    const SYMBOL: &'static str = "ft²";
+}
+
+// An area tagged with its unit at the type level. `PhantomData<U>` tells the compiler the
+// struct "uses" `U` without storing one, so a `Quantity<SquareFeet>` is still just an `f64`
+// at runtime but can't be mixed with a `Quantity<SquareMeters>`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quantity<U>(f64, PhantomData<U>);
+
+impl<U: AreaUnit> Quantity<U> {
+    pub fn new(value: f64) -> Self {
+        Quantity(value, PhantomData)
+    }
+
+    pub fn value(self) -> f64 {
+        self.0
+    }
+
+    // Units only change through this explicit conversion, e.g. `area.convert::<SquareFeet>()`.
+    pub fn convert<V: AreaUnit>(self) -> Quantity<V> {
+        Quantity::new(self.0 * U::SQUARE_METERS_PER_UNIT / V::SQUARE_METERS_PER_UNIT)
+    }
+}
+
+// Addition and subtraction are only implemented between quantities of the same unit, so
+// `Quantity::<SquareMeters>::new(1.0) + Quantity::<SquareFeet>::new(1.0)` doesn't compile.
+impl<U: AreaUnit> Add for Quantity<U> {
+    type Output = Quantity<U>;
+
+    fn add(self, rhs: Quantity<U>) -> Quantity<U> {
+        Quantity::new(self.0 + rhs.0)
+    }
+}
+
+impl<U: AreaUnit> Sub for Quantity<U> {
+    type Output = Quantity<U>;
+
+    fn sub(self, rhs: Quantity<U>) -> Quantity<U> {
+        Quantity::new(self.0 - rhs.0)
+    }
+}
+
+impl<U: AreaUnit> fmt::Display for Quantity<U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.0, U::SYMBOL)
+    }
+}
+
+// The area of a unit-tagged shape as a unit-tagged quantity: a `Rectangle<Meters>` gives
+// a `Quantity<SquareMeters>` and a `Rectangle<Feet>` a `Quantity<SquareFeet>`.
+pub trait TypedArea {
+    type Unit: AreaUnit;
+    fn typed_area(&self) -> Quantity<Self::Unit>;
+}
+
+impl<L: LengthUnit> TypedArea for Rectangle<L> {
+    type Unit = L::Squared;
+
+    fn typed_area(&self) -> Quantity<L::Squared> {
+        Quantity::new(self.area())
+    }
+}
+
+impl<L: LengthUnit> TypedArea for Circle<L> {
+    type Unit = L::Squared;
+
+    fn typed_area(&self) -> Quantity<L::Squared> {
+        Quantity::new(self.area())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::ShapeError;
+
+    #[test]
+    fn lengths_convert_between_units() {
+        assert_eq!(Feet(10.0).to_meters(), Meters(3.048));
+        assert!((Meters(1.0).to_feet().0 - 3.2808).abs() < 1e-4);
+        assert_eq!(Feet(10.0).to_string(), "10 ft");
+    }
+
+    #[test]
+    fn areas_convert_with_the_squared_factor() -> Result<(), ShapeError> {
+        let room = Rectangle::new(Feet(10.0), Feet(12.0))?;
+        assert_eq!(room.to_string(), "Rectangle 10 ft x 12 ft");
+        assert_eq!(room.area_in::<Feet>(), 120.0);
+        assert!((room.area_in::<Meters>() - 11.1484).abs() < 1e-4);
+        let pool = Circle::new(Meters(2.0))?;
+        assert!((pool.area_in::<Feet>() - 135.2633).abs() < 1e-4);
+        Ok(())
+    }
+
+    #[test]
+    fn typed_areas_add_within_a_unit() -> Result<(), ShapeError> {
+        let floor: Quantity<SquareFeet> = Rectangle::new(Feet(10.0), Feet(12.0))?.typed_area();
+        let hallway = Rectangle::new(Feet(3.0), Feet(10.0))?.typed_area();
+        assert_eq!((floor + hallway).to_string(), "150 ft²");
+        assert_eq!((floor - hallway).to_string(), "90 ft²");
+        let pool: Quantity<SquareMeters> = Circle::new(Meters(2.0))?.typed_area();
+        assert!(((floor.convert::<SquareMeters>() + pool).value() - 23.7147).abs() < 1e-4);
+        let square_meter = Quantity::<SquareMeters>::new(1.0).convert::<SquareFeet>();
+        assert!((square_meter.value() - 10.7639).abs() < 1e-4);
+        Ok(())
+    }
+}
@@ -0,0 +1,219 @@
+// A small benchmark harness: run every multiply implementation several times on the same
+// inputs and summarize the timings.
+use std::hint::black_box;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::blocked::{multiply_blocked, DEFAULT_BLOCK_SIZE};
+use crate::chunked::multiply_chunked;
+use crate::error::MatMulError;
+use crate::matrix::{multiply_matrix, multiply_matrix_parallel, Matrix};
+use crate::mutex::multiply_mutex;
+use crate::pool::multiply_pooled;
+use crate::random::{gen_matrix, gen_sparse_matrix};
+use crate::reduce::dot_unrolled;
+use crate::scoped::multiply_scoped;
+use crate::sparse::{multiply_csr_dense, CsrMatrix};
+use crate::strassen::multiply_strassen;
+use crate::transpose::multiply_transposed;
+
+// The inputs for one matrix size, in both representations, so that converting between
+// them isn't part of any timing.
+pub struct BenchInputs {
+    // In `Arc`s for `multiply_matrix_parallel`; the other kernels borrow them as `&Matrix`.
+    a: Arc<Matrix>,
+    b: Arc<Matrix>,
+    nested_a: Vec<Vec<u64>>,
+    nested_b: Vec<Vec<u64>>,
+    // A with about 90% of its cells zeroed, stored sparsely.
+    sparse_a: CsrMatrix,
+    // For the variants with a configurable number of threads.
+    threads: usize,
+}
+
+impl BenchInputs {
+    // Seeded random matrices, so every run over a given size multiplies exactly the same
+    // numbers.
+    pub fn new(size: usize, threads: usize) -> BenchInputs {
+        let (nested_a, nested_b) = (gen_matrix(size, size, 1), gen_matrix(size, size, 2));
+        let (a, b) = (Matrix::from_nested(nested_a.clone()), Matrix::from_nested(nested_b.clone()));
+        let (a, b) = (Arc::new(a), Arc::new(b));
+        let sparse_a = CsrMatrix::from_dense(&gen_sparse_matrix(size, size, 90, 1));
+        BenchInputs { a, b, nested_a, nested_b, sparse_a, threads }
+    }
+}
+
+// Below this size `multiply_strassen` switches to the plain kernel.
+const STRASSEN_THRESHOLD: usize = 64;
+
+// A named implementation. The result is passed through `black_box` so the work
+// can't be optimized away.
+type Variant = (&'static str, fn(&BenchInputs) -> Result<(), MatMulError>);
+
+// Every implementation the harness knows about, in the order they are reported.
+pub const VARIANTS: &[Variant] = &[
+    ("sequential", |inputs| {
+        black_box(multiply_matrix(&inputs.a, &inputs.b)?);
+        Ok(())
+    }),
+    ("thread per row", |inputs| {
+        black_box(multiply_matrix_parallel(&inputs.a, &inputs.b)?);
+        Ok(())
+    }),
+    ("pooled", |inputs| {
+        black_box(multiply_pooled(&inputs.a, &inputs.b, inputs.threads)?);
+        Ok(())
+    }),
+    ("scoped", |inputs| {
+        black_box(multiply_scoped(&inputs.nested_a, &inputs.nested_b, inputs.threads)?);
+        Ok(())
+    }),
+    ("chunked", |inputs| {
+        black_box(multiply_chunked(&inputs.a, &inputs.b, inputs.threads)?);
+        Ok(())
+    }),
+    ("mutex", |inputs| {
+        black_box(multiply_mutex(&inputs.a, &inputs.b, inputs.threads)?);
+        Ok(())
+    }),
+    ("transposed", |inputs| {
+        black_box(multiply_transposed(&inputs.nested_a, &inputs.nested_b)?);
+        Ok(())
+    }),
+    ("blocked", |inputs| {
+        black_box(multiply_blocked(&inputs.nested_a, &inputs.nested_b, DEFAULT_BLOCK_SIZE)?);
+        Ok(())
+    }),
+    ("strassen", |inputs| {
+        black_box(multiply_strassen(&inputs.nested_a, &inputs.nested_b, STRASSEN_THRESHOLD)?);
+        Ok(())
+    }),
+    // The sparse kernel only does work for the non-zero cells, so at 90% zeros it should
+    // take around a tenth of the time of "sequential".
+    ("csr, 90% zeros", |inputs| {
+        black_box(multiply_csr_dense(&inputs.sparse_a, &inputs.nested_b)?);
+        Ok(())
+    }),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchResult {
+    pub size: usize,
+    pub variant: &'static str,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+// For each size, run every variant `iterations` times (at least once) on square
+// matrices of that size, with `threads` threads where the variant lets it be chosen.
+// Sizes of 0 give `MatMulError::EmptyMatrix`.
+pub fn bench_multiply(
+    sizes: &[usize],
+    iterations: usize,
+    threads: usize,
+) -> Result<Vec<BenchResult>, MatMulError> {
+    let iterations = iterations.max(1);
+    let mut results = Vec::new();
+    for &size in sizes {
+        let inputs = BenchInputs::new(size, threads);
+        for &(variant, run) in VARIANTS {
+            let mut times = Vec::with_capacity(iterations);
+            for _ in 0..iterations {
+                let start = Instant::now();
+                run(&inputs)?;
+                times.push(start.elapsed());
+            }
+            results.push(summarize(size, variant, times));
+        }
+    }
+    Ok(results)
+}
+
+// Min, median and mean of a non-empty list of timings.
+fn summarize(size: usize, variant: &'static str, mut times: Vec<Duration>) -> BenchResult {
+    times.sort();
+    BenchResult {
+        size,
+        variant,
+        min: times[0],
+        // For an even count, the upper of the two middle values.
+        median: times[times.len() / 2],
+        mean: times.iter().sum::<Duration>() / times.len() as u32,
+    }
+}
+
+type DotVariant = (&'static str, fn(&[u64], &[u64]) -> u64);
+
+const DOT_VARIANTS: &[DotVariant] = &[
+    ("dot, naive", |a, b| a.iter().zip(b).map(|(a, b)| a * b).sum()),
+    ("dot, unrolled", dot_unrolled),
+];
+
+// The dot product on its own: the plain `zip`/`sum` loop against `dot_unrolled`, on two
+// random slices of `len` elements, `iterations` times each (at least once). `size` in the
+// results is `len`.
+pub fn bench_dot(len: usize, iterations: usize) -> Vec<BenchResult> {
+    let a = gen_matrix(1, len, 1).remove(0);
+    let b = gen_matrix(1, len, 2).remove(0);
+    DOT_VARIANTS
+        .iter()
+        .map(|&(variant, dot)| {
+            let times = (0..iterations.max(1))
+                .map(|_| {
+                    let start = Instant::now();
+                    black_box(dot(black_box(&a), black_box(&b)));
+                    start.elapsed()
+                })
+                .collect();
+            summarize(len, variant, times)
+        })
+        .collect()
+}
+
+// Print the results as a table, one line per (size, variant).
+pub fn print_bench_table(results: &[BenchResult]) {
+    println!("{:>8}  {:<16}{:>12}{:>12}{:>12}", "size", "variant", "min", "median", "mean");
+    for result in results {
+        println!(
+            "{:>8}  {:<16}{:>12}{:>12}{:>12}",
+            result.size,
+            result.variant,
+            format!("{:.2?}", result.min),
+            format!("{:.2?}", result.median),
+            format!("{:.2?}", result.mean)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_result_for_every_size_and_variant() -> Result<(), MatMulError> {
+        let sizes = [10, 20];
+        let results = bench_multiply(&sizes, 3, 2)?;
+        let expected: Vec<_> = sizes
+            .iter()
+            .flat_map(|&size| VARIANTS.iter().map(move |&(variant, _)| (size, variant)))
+            .collect();
+        let reported: Vec<_> = results.iter().map(|result| (result.size, result.variant)).collect();
+        assert_eq!(reported, expected);
+        assert!(results.iter().all(|result| result.min <= result.median && result.min <= result.mean));
+        assert_eq!(bench_dot(1000, 2).len(), DOT_VARIANTS.len());
+        Ok(())
+    }
+
+    #[test]
+    fn size_zero_is_an_error() {
+        assert_eq!(bench_multiply(&[0], 1, 2), Err(MatMulError::EmptyMatrix));
+    }
+
+    #[test]
+    fn summarizes_timings() {
+        let ms = Duration::from_millis;
+        let summary = summarize(5, "x", vec![ms(4), ms(1), ms(7), ms(4)]);
+        assert_eq!((summary.min, summary.median, summary.mean), (ms(1), ms(4), ms(4)));
+    }
+}
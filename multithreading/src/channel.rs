@@ -0,0 +1,114 @@
+// Producer/consumer with channels. The main thread sends every row index down one channel;
+// a fixed set of workers take indices from it, and send each finished row back down a
+// second channel as `(row_index, result_row)`. Rows arrive in whatever order they finish,
+// so the main thread puts each one into place by its index.
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::thread;
+
+use crate::error::MatMulError;
+use crate::generic::Numeric;
+use crate::matrix::{check_dimensions, multiply_row, Matrix};
+
+// Multiply with `workers` threads (0 counts as 1, and there are never more than rows).
+// The rows are sent between threads, so the element type has to be `Send`.
+pub fn multiply_mpsc<T: Numeric + Send + Sync>(
+    matrix_a: &Matrix<T>,
+    matrix_b: &Matrix<T>,
+    workers: usize,
+) -> Result<Matrix<T>, MatMulError> {
+    multiply_mpsc_with_hook(matrix_a, matrix_b, workers, |_| {})
+}
+
+// `multiply_mpsc`, with `before_send(row)` called by the worker that computed `row` just
+// before sending it. Only the tests pass a hook that does anything: they hold rows up by
+// different amounts, so that they finish out of order. Everywhere else it is the no-op
+// above, which compiles away.
+fn multiply_mpsc_with_hook<T: Numeric + Send + Sync>(
+    matrix_a: &Matrix<T>,
+    matrix_b: &Matrix<T>,
+    workers: usize,
+    before_send: impl Fn(usize) + Sync,
+) -> Result<Matrix<T>, MatMulError> {
+    check_dimensions(matrix_a.shape(), matrix_b.shape())?;
+    let matrix_a = &matrix_a.row_major();
+    let rows = matrix_a.rows();
+    let (row_sender, row_receiver) = mpsc::channel::<usize>();
+    let (result_sender, result_receiver) = mpsc::channel::<(usize, Vec<T>)>();
+    // A `Receiver` can't be shared by itself, so the workers take turns at it through a
+    // `Mutex`. The lock is only held while waiting for the next index.
+    let row_receiver = Mutex::new(row_receiver);
+    for row in 0..rows {
+        row_sender.send(row).expect("the receiver is still alive");
+    }
+    // Once the queued indices run out, `recv` fails instead of waiting for more, which is
+    // how the workers know to stop.
+    drop(row_sender);
+
+    let mut ans = Matrix::new(rows, matrix_b.cols());
+    thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, rows) {
+            let (row_receiver, result_sender) = (&row_receiver, result_sender.clone());
+            let before_send = &before_send;
+            scope.spawn(move || loop {
+                let next = row_receiver.lock().unwrap().recv();
+                let Ok(row) = next else { break };
+                let mut result_row = vec![T::default(); matrix_b.cols()];
+                multiply_row(matrix_a.row(row), matrix_b, &mut result_row);
+                before_send(row);
+                result_sender.send((row, result_row)).expect("the main thread is still receiving");
+            });
+        }
+        // Only the workers' clones are left, so the loop below ends when the last worker does.
+        drop(result_sender);
+        for (row, result_row) in result_receiver {
+            ans.row_mut(row).copy_from_slice(&result_row);
+        }
+    });
+    Ok(ans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::multiply_matrix;
+    use crate::random::gen_matrix;
+
+    #[test]
+    fn any_worker_count_gives_the_same_product() -> Result<(), MatMulError> {
+        let a = Matrix::from_nested(gen_matrix(40, 30, 1));
+        let b = Matrix::from_nested(gen_matrix(30, 20, 2));
+        let expected = multiply_matrix(&a, &b)?;
+        for workers in [0, 1, 4, 64] {
+            assert_eq!(multiply_mpsc(&a, &b, workers)?, expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn rows_finishing_out_of_order_go_back_in_place() -> Result<(), MatMulError> {
+        let a = Matrix::from_nested(gen_matrix(8, 6, 3));
+        let b = Matrix::from_nested(gen_matrix(6, 5, 4));
+        // Holding each row up longer the earlier it is makes them finish roughly in reverse.
+        let finished = Mutex::new(Vec::new());
+        let delayed = multiply_mpsc_with_hook(&a, &b, 8, |row| {
+            thread::sleep(std::time::Duration::from_millis(5 * (8 - row as u64)));
+            finished.lock().unwrap().push(row);
+        })?;
+        assert_eq!(delayed, multiply_matrix(&a, &b)?);
+        let mut finished = finished.into_inner().unwrap();
+        assert_ne!(finished, (0..8).collect::<Vec<_>>());
+        finished.sort();
+        assert_eq!(finished, (0..8).collect::<Vec<_>>());
+        Ok(())
+    }
+
+    #[test]
+    fn works_for_other_numeric_types() -> Result<(), MatMulError> {
+        let signed = Matrix::from_nested(vec![vec![1i64, -2], vec![-3, 4]]);
+        assert_eq!(multiply_mpsc(&signed, &signed, 2)?.to_nested(), vec![vec![7, -10], vec![-15, 22]]);
+        let float = Matrix::from_nested(vec![vec![0.5, 2.0], vec![1.5, -1.0]]);
+        assert_eq!(multiply_mpsc(&float, &float, 2)?.to_nested(), vec![vec![3.25, -1.0], vec![-0.75, 4.0]]);
+        Ok(())
+    }
+}
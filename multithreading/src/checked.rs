@@ -0,0 +1,51 @@
+// Overflow-checked multiplication. The other multiply functions use plain `+` and `*`,
+// which panic on overflow in debug builds but silently wrap around in release builds.
+// This version checks every step instead, at some cost in speed, so it stays opt-in.
+use crate::error::MatMulError;
+use crate::generic::validate_matrix;
+use crate::matrix::check_dimensions;
+
+// Like `multiply`, but reports the first output cell whose value doesn't fit in a `u64`.
+// Each cell is computed as a whole (row, col, then k), so the error can name it exactly.
+pub fn multiply_checked(
+    matrix_a: &[Vec<u64>],
+    matrix_b: &[Vec<u64>],
+) -> Result<Vec<Vec<u64>>, MatMulError> {
+    let (a_shape, b_shape) = (validate_matrix(matrix_a)?, validate_matrix(matrix_b)?);
+    check_dimensions(a_shape, b_shape)?;
+    let mut ans = vec![vec![0; b_shape.1]; a_shape.0];
+    for (row, (a_row, ans_row)) in matrix_a.iter().zip(&mut ans).enumerate() {
+        for (col, cell) in ans_row.iter_mut().enumerate() {
+            *cell = a_row
+                .iter()
+                .zip(matrix_b)
+                .try_fold(0u64, |sum, (&a, b_row)| sum.checked_add(a.checked_mul(b_row[col])?))
+                .ok_or(MatMulError::Overflow { row, col })?;
+        }
+    }
+    Ok(ans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generic::multiply_generic;
+
+    #[test]
+    fn names_the_cell_that_overflows() -> Result<(), MatMulError> {
+        // Row 1, column 0 is u64::MAX * 1 + 1 * 1.
+        let huge = vec![vec![1, 0], vec![u64::MAX, 1]];
+        let ones = vec![vec![1, 0], vec![1, 1]];
+        assert_eq!(multiply_checked(&huge, &ones), Err(MatMulError::Overflow { row: 1, col: 0 }));
+        assert_eq!(multiply_checked(&huge, &[vec![1], vec![0]])?, vec![vec![1], vec![u64::MAX]]);
+        Ok(())
+    }
+
+    #[test]
+    fn matches_multiply_when_nothing_overflows() -> Result<(), MatMulError> {
+        let a: Vec<Vec<u64>> = (0..20).map(|i| (0..30).map(|j| (i * 7 + j) % 11 * 1000).collect()).collect();
+        let b: Vec<Vec<u64>> = (0..30).map(|i| (0..10).map(|j| (i + j * 3) % 13 * 1000).collect()).collect();
+        assert_eq!(multiply_checked(&a, &b)?, multiply_generic(&a, &b)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+// Static partitioning: the rows are split up front into one contiguous block per thread,
+// so each thread is handed its whole share of the work once, instead of asking for rows
+// one at a time like the pool does.
+use std::ops::Range;
+use std::thread;
+
+use crate::error::MatMulError;
+use crate::generic::Numeric;
+use crate::matrix::{check_dimensions, multiply_row, Matrix};
+
+// Split `0..rows` into `threads` contiguous ranges, in order, whose lengths differ by at
+// most one: when `rows % threads != 0`, the first `rows % threads` ranges get an extra row.
+// With more threads than rows the trailing ranges are empty. 0 threads counts as 1.
+pub fn chunk_ranges(rows: usize, threads: usize) -> Vec<Range<usize>> {
+    let threads = threads.max(1);
+    let (base, extra) = (rows / threads, rows % threads);
+    let mut start = 0;
+    (0..threads)
+        .map(|i| {
+            let len = base + usize::from(i < extra);
+            let range = start..start + len;
+            start += len;
+            range
+        })
+        .collect()
+}
+
+// Multiply with one thread per block of rows from `chunk_ranges`. Each thread computes its
+// block into a buffer of its own, and the blocks are appended to the result in order.
+// Empty blocks don't get a thread. Works for any `Numeric` type the threads can share.
+pub fn multiply_chunked<T: Numeric + Send + Sync>(
+    matrix_a: &Matrix<T>,
+    matrix_b: &Matrix<T>,
+    num_threads: usize,
+) -> Result<Matrix<T>, MatMulError> {
+    check_dimensions(matrix_a.shape(), matrix_b.shape())?;
+    let matrix_a = &matrix_a.row_major();
+    let cols = matrix_b.cols();
+    let blocks: Vec<Vec<T>> = thread::scope(|scope| {
+        let handles: Vec<_> = chunk_ranges(matrix_a.rows(), num_threads)
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .map(|range| {
+                scope.spawn(move || {
+                    let mut block = vec![T::default(); range.len() * cols];
+                    for (row, out) in range.zip(block.chunks_mut(cols)) {
+                        multiply_row(matrix_a.row(row), matrix_b, out);
+                    }
+                    block
+                })
+            })
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+    Ok(Matrix::from_flat(matrix_a.rows(), cols, blocks.concat()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::multiply_matrix;
+
+    #[test]
+    fn ranges_cover_the_rows_in_order() {
+        assert_eq!(chunk_ranges(200, 3), [0..67, 67..134, 134..200]);
+        assert_eq!(chunk_ranges(2, 4), [0..1, 1..2, 2..2, 2..2]);
+        assert_eq!(chunk_ranges(5, 0), vec![(0..5)]);
+        assert_eq!(chunk_ranges(0, 2), [0..0, 0..0]);
+    }
+
+    #[test]
+    fn any_thread_count_gives_the_same_product() -> Result<(), MatMulError> {
+        let a = Matrix::from_nested((0..50).map(|i| (0..30).map(|j| (i * 7 + j) % 11).collect()).collect());
+        let b = Matrix::from_nested((0..30).map(|i| (0..20).map(|j| (i + j * 3) % 13).collect()).collect());
+        let expected = multiply_matrix(&a, &b)?;
+        for threads in [0, 1, 3, 4, 7, 500] {
+            assert_eq!(multiply_chunked(&a, &b, threads)?, expected);
+        }
+        let one_row = Matrix::from_nested(vec![vec![1, 2, 3]]);
+        let column = Matrix::from_nested(vec![vec![4], vec![5], vec![6]]);
+        for threads in [1, 2, 8] {
+            assert_eq!(multiply_chunked(&one_row, &column, threads)?.to_nested(), vec![vec![32]]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn works_for_other_numeric_types() -> Result<(), MatMulError> {
+        let signed = Matrix::from_nested(vec![vec![1i64, -2], vec![-3, 4]]);
+        assert_eq!(multiply_chunked(&signed, &signed, 2)?.to_nested(), vec![vec![7, -10], vec![-15, 22]]);
+        let float = Matrix::from_nested(vec![vec![0.5, 2.0], vec![1.5, -1.0]]);
+        assert_eq!(multiply_chunked(&float, &float, 2)?.to_nested(), vec![vec![3.25, -1.0], vec![-0.75, 4.0]]);
+        Ok(())
+    }
+}
@@ -0,0 +1,272 @@
+// Command-line options, and how many threads the parallel functions should use. In order
+// of precedence, the thread count comes from:
+//
+//   1. `--threads N` on the command line,
+//   2. the `MATMUL_THREADS` environment variable,
+//   3. `std::thread::available_parallelism`, when neither is given.
+use std::fmt;
+
+use crate::pool::default_threads;
+use crate::verify::NESTED_VARIANTS;
+
+// The environment variable read by `main`.
+pub const THREADS_ENV_VAR: &str = "MATMUL_THREADS";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ThreadConfig {
+    // `None` means "as many as the machine has".
+    pub count: Option<usize>,
+}
+
+// A thread count that isn't a positive integer. `source` says where it came from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThreadConfigError {
+    pub source: &'static str,
+    pub value: String,
+}
+
+impl fmt::Display for ThreadConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid thread count {:?} from {}: expected a whole number above 0",
+            self.value, self.source
+        )
+    }
+}
+
+impl std::error::Error for ThreadConfigError {}
+
+impl ThreadConfig {
+    // Pick the count from the command line's `--threads` (already checked by `parse_args`)
+    // and the environment variable's value `env`, either of which may be missing. The
+    // inputs are passed in rather than read here, so the precedence can be checked without
+    // touching the real environment. A broken `MATMUL_THREADS` doesn't matter when
+    // `--threads` is given.
+    pub fn resolve(
+        cli: Option<usize>,
+        env: Option<&str>,
+    ) -> Result<ThreadConfig, ThreadConfigError> {
+        let count = match (cli, env) {
+            (Some(count), _) => Some(count),
+            (None, Some(value)) => Some(parse_count(THREADS_ENV_VAR, value)?),
+            (None, None) => None,
+        };
+        Ok(ThreadConfig { count })
+    }
+
+    // The number of threads to use, with `available` standing in for the machine's count
+    // when none was configured.
+    pub fn threads_or(&self, available: usize) -> usize {
+        self.count.unwrap_or(available)
+    }
+
+    pub fn threads(&self) -> usize {
+        self.threads_or(default_threads())
+    }
+}
+
+fn parse_count(source: &'static str, value: &str) -> Result<usize, ThreadConfigError> {
+    match value.trim().parse() {
+        Ok(count) if count > 0 => Ok(count),
+        _ => Err(ThreadConfigError {
+            source,
+            value: value.to_string(),
+        }),
+    }
+}
+
+// Everything in the usage message up to the list of implementations.
+const USAGE_HEAD: &str = "\
+usage: multithreading [--threads N] [--size N] [--seed N] [--impl NAME]
+       multithreading [--threads N] A.csv B.csv
+
+With no arguments, runs the full demo. --size, --seed or --impl multiply two seeded random
+size x size matrices with one implementation instead (defaults: --size 100, --seed 1,
+--impl sequential). Given two CSV files, multiplies the matrices in them.
+
+  --threads N   worker threads, above 0 (default: MATMUL_THREADS, or one per core)
+  --size N      rows and columns of the random matrices, above 0
+  --seed N      seed for the random matrices
+  --impl NAME   the implementation to use, one of:";
+
+// The usage message. The `--impl` names are taken from `NESTED_VARIANTS`, with - for
+// spaces, six to a line, so the list always matches what `main` accepts.
+pub fn usage() -> String {
+    let names: Vec<String> = NESTED_VARIANTS.iter().map(|(name, _)| name.replace(' ', "-")).collect();
+    let lines: Vec<String> = names.chunks(6).map(|line| line.join(", ")).collect();
+    format!(
+        "{}\n                {}\n  --help        print this message",
+        USAGE_HEAD,
+        lines.join(",\n                ")
+    )
+}
+
+// Everything the command line can ask for. `None` means the flag wasn't given.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Config {
+    pub threads: Option<usize>,
+    pub size: Option<usize>,
+    pub seed: Option<u64>,
+    pub implementation: Option<String>,
+    // Either none or two CSV files.
+    pub files: Vec<String>,
+    pub help: bool,
+}
+
+impl Config {
+    // Whether to run a single random multiplication instead of the demo.
+    pub fn is_single_run(&self) -> bool {
+        self.size.is_some() || self.seed.is_some() || self.implementation.is_some()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgsError {
+    UnknownFlag(String),
+    // The flag was the last argument, with no value after it.
+    MissingValue(&'static str),
+    // The value after `flag` isn't a number in the allowed range.
+    InvalidValue { flag: &'static str, value: String },
+    // Anything that isn't a flag counts as a file, but only exactly two make sense.
+    WrongFileCount(usize),
+}
+
+impl fmt::Display for ArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArgsError::UnknownFlag(flag) => write!(f, "unknown flag {}", flag),
+            ArgsError::MissingValue(flag) => write!(f, "{} needs a value", flag),
+            ArgsError::InvalidValue { flag, value } => {
+                write!(f, "invalid value {:?} for {}", value, flag)
+            }
+            ArgsError::WrongFileCount(count) => {
+                write!(f, "expected two CSV files, got {}", count)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ArgsError {}
+
+// Parse the command-line arguments, without the program name. This only looks at `args`
+// (no environment, no files), so every combination can be tried directly. Whether an
+// `--impl` name exists is left to the caller, which knows the implementations.
+pub fn parse_args(args: Vec<String>) -> Result<Config, ArgsError> {
+    let mut config = Config::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &'static str| args.next().ok_or(ArgsError::MissingValue(flag));
+        match arg.as_str() {
+            "--threads" => config.threads = Some(parse_positive("--threads", value("--threads")?)?),
+            "--size" => config.size = Some(parse_positive("--size", value("--size")?)?),
+            "--seed" => {
+                let seed = value("--seed")?;
+                match seed.parse() {
+                    Ok(parsed) => config.seed = Some(parsed),
+                    Err(_) => return Err(ArgsError::InvalidValue { flag: "--seed", value: seed }),
+                }
+            }
+            "--impl" => config.implementation = Some(value("--impl")?),
+            "--help" | "-h" => config.help = true,
+            flag if flag.starts_with('-') => return Err(ArgsError::UnknownFlag(arg)),
+            _ => config.files.push(arg),
+        }
+    }
+    if !config.files.is_empty() && config.files.len() != 2 {
+        return Err(ArgsError::WrongFileCount(config.files.len()));
+    }
+    Ok(config)
+}
+
+fn parse_positive(flag: &'static str, value: String) -> Result<usize, ArgsError> {
+    match value.parse() {
+        Ok(count) if count > 0 => Ok(count),
+        _ => Err(ArgsError::InvalidValue { flag, value }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    fn resolve(cli: Option<usize>, env: Option<&str>) -> Result<usize, ThreadConfigError> {
+        ThreadConfig::resolve(cli, env).map(|config| config.threads_or(6))
+    }
+
+    #[test]
+    fn explicit_beats_environment_beats_machine() {
+        assert_eq!(resolve(Some(3), Some("5")), Ok(3));
+        assert_eq!(resolve(None, Some("5")), Ok(5));
+        assert_eq!(resolve(None, None), Ok(6));
+        assert_eq!(resolve(Some(2), Some("junk")), Ok(2));
+        assert_eq!(ThreadConfig::default().threads(), default_threads());
+    }
+
+    #[test]
+    fn bad_thread_counts_are_rejected() {
+        for value in ["0", "four", "-1"] {
+            let err = ThreadConfigError { source: THREADS_ENV_VAR, value: value.to_string() };
+            assert_eq!(resolve(None, Some(value)), Err(err));
+        }
+        let junk = ThreadConfigError { source: THREADS_ENV_VAR, value: "four".to_string() };
+        assert_eq!(
+            junk.to_string(),
+            "invalid thread count \"four\" from MATMUL_THREADS: expected a whole number above 0"
+        );
+    }
+
+    #[test]
+    fn every_flag_fills_in_its_field() -> Result<(), ArgsError> {
+        assert_eq!(parse_args(args(""))?, Config::default());
+        assert!(!Config::default().is_single_run());
+        let full = parse_args(args("--size 512 --threads 8 --impl scoped --seed 42"))?;
+        let expected = Config {
+            threads: Some(8),
+            size: Some(512),
+            seed: Some(42),
+            implementation: Some("scoped".to_string()),
+            ..Config::default()
+        };
+        assert_eq!(full, expected);
+        assert!(full.is_single_run());
+        let lines = [("--size 3", true), ("--seed 0", true), ("--impl mpsc", true), ("--threads 2", false)];
+        for (line, single_run) in lines {
+            assert_eq!(parse_args(args(line))?.is_single_run(), single_run, "{}", line);
+        }
+        let with_files = parse_args(args("a.csv --threads 2 b.csv"))?;
+        assert_eq!((with_files.files, with_files.threads), (args("a.csv b.csv"), Some(2)));
+        assert!(parse_args(args("--help"))?.help);
+        assert!(parse_args(args("-h"))?.help);
+        Ok(())
+    }
+
+    #[test]
+    fn every_way_of_getting_it_wrong() {
+        let invalid = |flag, value: &str| ArgsError::InvalidValue { flag, value: value.to_string() };
+        assert_eq!(parse_args(args("--size")), Err(ArgsError::MissingValue("--size")));
+        assert_eq!(parse_args(args("--size big")), Err(invalid("--size", "big")));
+        assert_eq!(parse_args(args("--size 0")), Err(invalid("--size", "0")));
+        assert_eq!(parse_args(args("--threads 0")), Err(invalid("--threads", "0")));
+        assert_eq!(parse_args(args("--threads -3")), Err(invalid("--threads", "-3")));
+        assert_eq!(parse_args(args("--seed 1.5")), Err(invalid("--seed", "1.5")));
+        assert_eq!(parse_args(args("--impl")), Err(ArgsError::MissingValue("--impl")));
+        assert_eq!(parse_args(args("--fast")), Err(ArgsError::UnknownFlag("--fast".to_string())));
+        assert_eq!(parse_args(args("a.csv")), Err(ArgsError::WrongFileCount(1)));
+        assert_eq!(parse_args(args("a.csv b.csv c.csv")), Err(ArgsError::WrongFileCount(3)));
+        assert_eq!(invalid("--seed", "x").to_string(), "invalid value \"x\" for --seed");
+    }
+
+    #[test]
+    fn usage_lists_every_implementation() {
+        let usage = usage();
+        for (name, _) in NESTED_VARIANTS {
+            assert!(usage.contains(&name.replace(' ', "-")), "{} is missing", name);
+        }
+        assert!(usage.lines().all(|line| line.len() <= 90));
+    }
+}
@@ -0,0 +1,164 @@
+// Reading and writing matrices as CSV files: one row per line, the cells separated by commas, e.g.
+//
+//   1, 2, 3
+//   4, 5, 6
+//
+// Whitespace around a cell is ignored, and so are blank lines (such as a trailing newline).
+// Lines and columns in errors are counted from 1, the way an editor shows them.
+use std::fmt;
+use std::fs;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// Why a matrix couldn't be loaded.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LoadError {
+    // The file couldn't be read at all.
+    Io { path: PathBuf, kind: io::ErrorKind },
+    // There isn't a single row of numbers.
+    Empty,
+    // `line` has `found` cells, but the rows before it have `expected`.
+    Ragged { line: usize, expected: usize, found: usize },
+    // The cell at `line`, `column` isn't a `u64`.
+    InvalidCell { line: usize, column: usize, cell: String },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Io { path, kind } => {
+                write!(f, "could not read {}: {}", path.display(), kind)
+            }
+            LoadError::Empty => write!(f, "no matrix rows found"),
+            LoadError::Ragged { line, expected, found } => write!(
+                f,
+                "line {} has {} columns, but the first row has {}",
+                line, found, expected
+            ),
+            LoadError::InvalidCell { line, column, cell } => {
+                write!(f, "line {}, column {}: {:?} is not a number", line, column, cell)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+// Parse CSV text into a nested matrix. Kept separate from `load_matrix_csv` so the parsing
+// can be used (and tried out) without a file.
+pub fn parse_matrix_csv(text: &str) -> Result<Vec<Vec<u64>>, LoadError> {
+    let mut matrix: Vec<Vec<u64>> = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line_number = index + 1;
+        let row = line
+            .split(',')
+            .enumerate()
+            .map(|(column, cell)| {
+                cell.trim().parse().map_err(|_| LoadError::InvalidCell {
+                    line: line_number,
+                    column: column + 1,
+                    cell: cell.trim().to_string(),
+                })
+            })
+            .collect::<Result<Vec<u64>, LoadError>>()?;
+        if let Some(first) = matrix.first() {
+            if row.len() != first.len() {
+                return Err(LoadError::Ragged {
+                    line: line_number,
+                    expected: first.len(),
+                    found: row.len(),
+                });
+            }
+        }
+        matrix.push(row);
+    }
+    if matrix.is_empty() {
+        return Err(LoadError::Empty);
+    }
+    Ok(matrix)
+}
+
+pub fn load_matrix_csv(path: &Path) -> Result<Vec<Vec<u64>>, LoadError> {
+    let text = fs::read_to_string(path).map_err(|err| LoadError::Io {
+        path: path.to_path_buf(),
+        kind: err.kind(),
+    })?;
+    parse_matrix_csv(&text)
+}
+
+// Write a matrix in the format `parse_matrix_csv` reads, without spaces.
+pub fn save_matrix_csv(matrix: &[Vec<u64>], path: &Path) -> io::Result<()> {
+    let mut out = BufWriter::new(fs::File::create(path)?);
+    for row in matrix {
+        let cells: Vec<String> = row.iter().map(u64::to_string).collect();
+        writeln!(out, "{}", cells.join(","))?;
+    }
+    out.flush()
+}
+
+// A few `key: value` lines describing a result, instead of the result itself:
+//
+//   rows: 100
+//   cols: 100
+//   duration: 1.23ms
+//   checksum: 1234567
+pub fn save_summary(
+    path: &Path,
+    dims: (usize, usize),
+    duration: Duration,
+    checksum: u64,
+) -> io::Result<()> {
+    let summary = format!(
+        "rows: {}\ncols: {}\nduration: {:.2?}\nchecksum: {}\n",
+        dims.0, dims.1, duration, checksum
+    );
+    fs::write(path, summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process;
+
+    use super::*;
+
+    #[test]
+    fn parses_rows_of_numbers() {
+        assert_eq!(parse_matrix_csv("1, 2, 3\n4, 5, 6\n\n"), Ok(vec![vec![1, 2, 3], vec![4, 5, 6]]));
+        assert_eq!(parse_matrix_csv("1,2\n3,4"), Ok(vec![vec![1, 2], vec![3, 4]]));
+    }
+
+    #[test]
+    fn every_kind_of_bad_input_has_its_own_error() {
+        let ragged = parse_matrix_csv("1,2,3\n4,5\n");
+        assert_eq!(ragged, Err(LoadError::Ragged { line: 2, expected: 3, found: 2 }));
+        let stray_letter = parse_matrix_csv("1,2\n3,x4\n");
+        let invalid_cell = LoadError::InvalidCell { line: 2, column: 2, cell: "x4".to_string() };
+        assert_eq!(stray_letter, Err(invalid_cell.clone()));
+        assert_eq!(invalid_cell.to_string(), "line 2, column 2: \"x4\" is not a number");
+        assert_eq!(parse_matrix_csv(" \n\n"), Err(LoadError::Empty));
+        let empty_cell = LoadError::InvalidCell { line: 1, column: 2, cell: String::new() };
+        assert_eq!(parse_matrix_csv("1,,2"), Err(empty_cell));
+        let missing = load_matrix_csv(Path::new("no/such/matrix.csv"));
+        assert!(matches!(missing, Err(LoadError::Io { kind: io::ErrorKind::NotFound, .. })));
+    }
+
+    #[test]
+    fn a_saved_matrix_loads_back_unchanged() -> io::Result<()> {
+        // A fresh directory under the system temp dir, named after the process so that
+        // runs don't collide.
+        let dir = std::env::temp_dir().join(format!("multithreading-csv-{}", process::id()));
+        fs::create_dir_all(&dir)?;
+        let (csv_path, summary_path) = (dir.join("result.csv"), dir.join("summary.txt"));
+        let matrix = vec![vec![1, 20, 3], vec![400, 5, 6]];
+        save_matrix_csv(&matrix, &csv_path)?;
+        save_summary(&summary_path, (2, 3), Duration::from_millis(5), 435)?;
+        assert_eq!(load_matrix_csv(&csv_path), Ok(matrix));
+        let summary = fs::read_to_string(&summary_path)?;
+        assert_eq!(summary, "rows: 2\ncols: 3\nduration: 5.00ms\nchecksum: 435\n");
+        fs::remove_dir_all(dir)
+    }
+}
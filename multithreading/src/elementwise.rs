@@ -0,0 +1,192 @@
+// Element-wise addition and subtraction. Unlike multiplication, both operands must have
+// exactly the same shape, and every cell of the result only depends on the same cell of
+// the inputs. The arithmetic is checked: a sum above `u64::MAX` or a difference below zero
+// is `MatMulError::Overflow` for that cell, since there are no negative `u64`s to wrap to.
+use std::ops::{Add, Sub};
+use std::thread;
+
+use crate::chunked::chunk_ranges;
+use crate::error::MatMulError;
+use crate::generic::validate_matrix;
+use crate::matrix::Matrix;
+
+fn check_same_shape(a: (usize, usize), b: (usize, usize)) -> Result<(), MatMulError> {
+    if a != b {
+        return Err(MatMulError::ShapeMismatch { a, b });
+    }
+    Ok(())
+}
+
+// Combine two rows cell by cell with a checked operation, reporting the first cell that
+// fails. `row` is only used for the error.
+fn combine_row(
+    a_row: &[u64],
+    b_row: &[u64],
+    row: usize,
+    op: fn(u64, u64) -> Option<u64>,
+    out: &mut [u64],
+) -> Result<(), MatMulError> {
+    for (col, ((cell, a), b)) in out.iter_mut().zip(a_row).zip(b_row).enumerate() {
+        *cell = op(*a, *b).ok_or(MatMulError::Overflow { row, col })?;
+    }
+    Ok(())
+}
+
+fn combine_nested(
+    matrix_a: &[Vec<u64>],
+    matrix_b: &[Vec<u64>],
+    op: fn(u64, u64) -> Option<u64>,
+) -> Result<Vec<Vec<u64>>, MatMulError> {
+    let a_shape = validate_matrix(matrix_a)?;
+    check_same_shape(a_shape, validate_matrix(matrix_b)?)?;
+    let mut ans = vec![vec![0; a_shape.1]; a_shape.0];
+    for (row, out) in ans.iter_mut().enumerate() {
+        combine_row(&matrix_a[row], &matrix_b[row], row, op, out)?;
+    }
+    Ok(ans)
+}
+
+fn combine(
+    matrix_a: &Matrix,
+    matrix_b: &Matrix,
+    op: fn(u64, u64) -> Option<u64>,
+) -> Result<Matrix, MatMulError> {
+    check_same_shape(matrix_a.shape(), matrix_b.shape())?;
+    let (matrix_a, matrix_b) = (&matrix_a.row_major(), &matrix_b.row_major());
+    let mut ans = Matrix::new(matrix_a.rows(), matrix_a.cols());
+    for row in 0..matrix_a.rows() {
+        combine_row(matrix_a.row(row), matrix_b.row(row), row, op, ans.row_mut(row))?;
+    }
+    Ok(ans)
+}
+
+pub fn add_nested(
+    matrix_a: &[Vec<u64>],
+    matrix_b: &[Vec<u64>],
+) -> Result<Vec<Vec<u64>>, MatMulError> {
+    combine_nested(matrix_a, matrix_b, u64::checked_add)
+}
+
+pub fn sub_nested(
+    matrix_a: &[Vec<u64>],
+    matrix_b: &[Vec<u64>],
+) -> Result<Vec<Vec<u64>>, MatMulError> {
+    combine_nested(matrix_a, matrix_b, u64::checked_sub)
+}
+
+// `&a + &b` and `&a - &b`. Like the multiply functions they return a `Result` instead of
+// panicking when the shapes differ, so the sum has to be unwrapped (or `?`-ed) before it
+// can be used in the next operation.
+impl Add for &Matrix {
+    type Output = Result<Matrix, MatMulError>;
+
+    fn add(self, other: &Matrix) -> Self::Output {
+        combine(self, other, u64::checked_add)
+    }
+}
+
+impl Sub for &Matrix {
+    type Output = Result<Matrix, MatMulError>;
+
+    fn sub(self, other: &Matrix) -> Self::Output {
+        combine(self, other, u64::checked_sub)
+    }
+}
+
+// Addition split into blocks of rows from `chunk_ranges`, one scoped thread per block.
+// There's very little work per cell, so this only pays off for large matrices. If several
+// cells overflow, the one reported is in the earliest block that has one.
+pub fn add_parallel(
+    matrix_a: &Matrix,
+    matrix_b: &Matrix,
+    num_threads: usize,
+) -> Result<Matrix, MatMulError> {
+    check_same_shape(matrix_a.shape(), matrix_b.shape())?;
+    let (matrix_a, matrix_b) = (&matrix_a.row_major(), &matrix_b.row_major());
+    let cols = matrix_a.cols();
+    // `chunks_mut(0)` panics, and with no columns there is nothing to add anyway.
+    if cols == 0 {
+        return Ok(Matrix::new(matrix_a.rows(), 0));
+    }
+    let blocks: Vec<Result<Vec<u64>, MatMulError>> = thread::scope(|scope| {
+        let handles: Vec<_> = chunk_ranges(matrix_a.rows(), num_threads)
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .map(|range| {
+                scope.spawn(move || {
+                    let mut block = vec![0; range.len() * cols];
+                    for (row, out) in range.zip(block.chunks_mut(cols)) {
+                        let (a_row, b_row) = (matrix_a.row(row), matrix_b.row(row));
+                        combine_row(a_row, b_row, row, u64::checked_add, out)?;
+                    }
+                    Ok(block)
+                })
+            })
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+    let blocks = blocks.into_iter().collect::<Result<Vec<_>, _>>()?;
+    Ok(Matrix::from_flat(matrix_a.rows(), cols, blocks.concat()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::multiply_matrix;
+
+    #[test]
+    fn addition_and_subtraction_undo_each_other() -> Result<(), MatMulError> {
+        let nested_a: Vec<Vec<u64>> = (0..30).map(|i| (0..20).map(|j| i * 20 + j).collect()).collect();
+        let nested_b: Vec<Vec<u64>> = (0..30).map(|i| (0..20).map(|j| (i * j) % 17).collect()).collect();
+        let (a, b) = (Matrix::from_nested(nested_a.clone()), Matrix::from_nested(nested_b.clone()));
+        let sum = (&a + &b)?;
+        assert_eq!((&sum - &b)?, a);
+        assert_eq!(add_parallel(&a, &b, 3)?, sum);
+        assert_eq!(add_parallel(&a, &b, 1000)?, sum);
+        assert_eq!(add_nested(&nested_a, &nested_b)?, sum.to_nested());
+        assert_eq!(sub_nested(&add_nested(&nested_a, &nested_b)?, &nested_b)?, nested_a);
+        Ok(())
+    }
+
+    #[test]
+    fn shapes_must_match() {
+        let one_by_two = Matrix::from_nested(vec![vec![1, 2]]);
+        let two_by_one = Matrix::from_nested(vec![vec![1], vec![2]]);
+        let shapes = MatMulError::ShapeMismatch { a: (1, 2), b: (2, 1) };
+        assert_eq!(&one_by_two + &two_by_one, Err(shapes.clone()));
+        assert_eq!(&one_by_two - &two_by_one, Err(shapes.clone()));
+        assert_eq!(add_parallel(&one_by_two, &two_by_one, 2), Err(shapes.clone()));
+        assert_eq!(add_nested(&one_by_two.to_nested(), &two_by_one.to_nested()), Err(shapes));
+        let ragged = vec![vec![1, 2, 3], vec![4, 5]];
+        let ragged_err = MatMulError::Ragged { row: 1, expected: 3, found: 2 };
+        assert_eq!(add_nested(&ragged, &ragged), Err(ragged_err));
+    }
+
+    #[test]
+    fn rows_without_columns_add_up_to_rows_without_columns() -> Result<(), MatMulError> {
+        let empty_rows = Matrix::from_nested(vec![vec![], vec![]]);
+        let sum = add_parallel(&empty_rows, &empty_rows, 2)?;
+        assert_eq!(sum.shape(), (2, 0));
+        assert_eq!(sum, (&empty_rows + &empty_rows)?);
+        Ok(())
+    }
+
+    #[test]
+    fn a_difference_below_zero_is_an_overflow() {
+        let two_by_one = Matrix::from_nested(vec![vec![1], vec![2]]);
+        let below_zero = &two_by_one - &Matrix::from_nested(vec![vec![0], vec![3]]);
+        assert_eq!(below_zero, Err(MatMulError::Overflow { row: 1, col: 0 }));
+    }
+
+    #[test]
+    fn multiplication_distributes_over_addition() -> Result<(), MatMulError> {
+        let a = Matrix::from_nested(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let b = Matrix::from_nested(vec![vec![7, 8], vec![9, 10], vec![11, 12]]);
+        let c = Matrix::from_nested(vec![vec![1, 0], vec![2, 5], vec![0, 3]]);
+        let left = multiply_matrix(&a, &(&b + &c)?)?;
+        let right = (&multiply_matrix(&a, &b)? + &multiply_matrix(&a, &c)?)?;
+        assert_eq!(left, right);
+        assert_eq!(left.to_nested(), vec![vec![63, 83], vec![153, 197]]);
+        Ok(())
+    }
+}
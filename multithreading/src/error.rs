@@ -0,0 +1,63 @@
+use std::fmt;
+
+// Why two matrices couldn't be multiplied.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatMulError {
+    // A x B needs as many columns in A as there are rows in B.
+    DimensionMismatch { a_cols: usize, b_rows: usize },
+    // Row `row` of a nested matrix has `found` elements, but the first row has `expected`.
+    Ragged { row: usize, expected: usize, found: usize },
+    // A dot product needs two vectors of the same length.
+    LengthMismatch { a: usize, b: usize },
+    // A + B and A - B need A and B to have the same (rows, cols).
+    ShapeMismatch { a: (usize, usize), b: (usize, usize) },
+    // Only a square matrix can be multiplied by itself.
+    NotSquare { rows: usize, cols: usize },
+    // The matrix has no inverse.
+    Singular,
+    // One of the matrices has no rows or no columns, so it has no first row to size the
+    // result from.
+    EmptyMatrix,
+    // An integer result doesn't fit its type at (`row`, `col`): a sum or product of
+    // `multiply_checked` in `u64`, a difference below zero in `A - B`, or a value computed
+    // by `determinant_i64`, named by the entry of the matrix it was being stored in.
+    Overflow { row: usize, col: usize },
+    // A worker thread panicked before finishing its rows, so the result is incomplete.
+    WorkerPanicked,
+}
+
+impl fmt::Display for MatMulError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MatMulError::DimensionMismatch { a_cols, b_rows } => write!(
+                f,
+                "incompatible multiplication: A has {} columns but B has {} rows",
+                a_cols, b_rows
+            ),
+            MatMulError::Ragged { row, expected, found } => write!(
+                f,
+                "ragged matrix: row {} has {} elements, but row 0 has {}",
+                row, found, expected
+            ),
+            MatMulError::LengthMismatch { a, b } => {
+                write!(f, "incompatible lengths: {} and {}", a, b)
+            }
+            MatMulError::ShapeMismatch { a, b } => write!(
+                f,
+                "incompatible shapes: A is {}x{} but B is {}x{}",
+                a.0, a.1, b.0, b.1
+            ),
+            MatMulError::NotSquare { rows, cols } => {
+                write!(f, "expected a square matrix, found {}x{}", rows, cols)
+            }
+            MatMulError::Singular => write!(f, "the matrix is singular"),
+            MatMulError::EmptyMatrix => write!(f, "cannot multiply an empty matrix"),
+            MatMulError::Overflow { row, col } => {
+                write!(f, "overflow computing the result at row {}, column {}", row, col)
+            }
+            MatMulError::WorkerPanicked => write!(f, "a worker thread panicked"),
+        }
+    }
+}
+
+impl std::error::Error for MatMulError {}
@@ -0,0 +1,68 @@
+// Floating-point matrices. The integer functions are exact: any order of additions gives
+// the same `u64` (as long as nothing overflows), so their results can be compared with
+// `==`. Float addition isn't associative, so a variant that sums in a different order
+// (another blocking, another split between threads) may differ in the last bits, and
+// float results are compared with `matrices_approx_eq` instead.
+use crate::error::MatMulError;
+use crate::generic::multiply_generic;
+use crate::scoped::multiply_scoped;
+
+// The generic functions instantiated for `f64`.
+pub fn multiply_f64(
+    matrix_a: &[Vec<f64>],
+    matrix_b: &[Vec<f64>],
+) -> Result<Vec<Vec<f64>>, MatMulError> {
+    multiply_generic(matrix_a, matrix_b)
+}
+
+pub fn multiply_parallel_f64(
+    matrix_a: &[Vec<f64>],
+    matrix_b: &[Vec<f64>],
+    num_threads: usize,
+) -> Result<Vec<Vec<f64>>, MatMulError> {
+    multiply_scoped(matrix_a, matrix_b, num_threads)
+}
+
+// True when both matrices have the same shape and every pair of elements is within
+// `epsilon` of each other.
+pub fn matrices_approx_eq(a: &[Vec<f64>], b: &[Vec<f64>], epsilon: f64) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|(row_a, row_b)| {
+            row_a.len() == row_b.len()
+                && row_a.iter().zip(row_b).all(|(x, y)| (x - y).abs() <= epsilon)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::gen_matrix_f64;
+
+    #[test]
+    fn sequential_and_parallel_agree() -> Result<(), MatMulError> {
+        let (a, b) = (gen_matrix_f64(40, 30, 7), gen_matrix_f64(30, 20, 8));
+        let sequential = multiply_f64(&a, &b)?;
+        assert!(matrices_approx_eq(&sequential, &multiply_parallel_f64(&a, &b, 3)?, 1e-12));
+        assert!(!matrices_approx_eq(&a, &gen_matrix_f64(40, 30, 9), 1e-12));
+        Ok(())
+    }
+
+    #[test]
+    fn a_matrix_times_its_inverse_is_the_identity() -> Result<(), MatMulError> {
+        // The 2x2 inverse is [[d, -b], [-c, a]] / (ad - bc).
+        let m = [[0.3, 1.7], [2.9, -0.4]];
+        let det = m[0][0] * m[1][1] - m[0][1] * m[1][0];
+        let inverse = vec![vec![m[1][1] / det, -m[0][1] / det], vec![-m[1][0] / det, m[0][0] / det]];
+        let product = multiply_f64(&m.map(Vec::from), &inverse)?;
+        assert!(matrices_approx_eq(&product, &[vec![1.0, 0.0], vec![0.0, 1.0]], 1e-12));
+        Ok(())
+    }
+
+    #[test]
+    fn shapes_must_match_to_be_equal() {
+        let a = vec![vec![1.0, 2.0]];
+        assert!(matrices_approx_eq(&a, &a, 0.0));
+        assert!(!matrices_approx_eq(&a, &[vec![1.0]], 1.0));
+        assert!(!matrices_approx_eq(&a, &[vec![1.0, 2.0], vec![3.0, 4.0]], 1.0));
+    }
+}
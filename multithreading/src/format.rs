@@ -0,0 +1,106 @@
+// Printing matrices readably: a header with the dimensions, then one line per row with the
+// columns right-aligned. Large matrices are shortened by leaving out the middle rows and
+// columns, marked with "…", so that the corners are still visible.
+use std::fmt;
+
+use crate::generic::nested_shape;
+use crate::matrix::Matrix;
+
+// How much of a `Matrix` its `Display` impl shows.
+const DISPLAY_ROWS: usize = 10;
+const DISPLAY_COLS: usize = 10;
+
+// Show at most `max_rows` rows and `max_cols` columns of `matrix`: the first half (rounded
+// up) and the last half of each, e.g. for a 3x3 matrix with no elision:
+//
+//   3x3 matrix
+//   1 2 3
+//   4 5 6
+//   7 8 9
+//
+// Every cell is padded to the width of the widest one shown.
+pub fn format_matrix(matrix: &[Vec<u64>], max_rows: usize, max_cols: usize) -> String {
+    let (rows, cols) = nested_shape(matrix);
+    format_cells(rows, cols, |row, col| matrix[row][col], max_rows, max_cols)
+}
+
+impl fmt::Display for Matrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (rows, cols) = self.shape();
+        let cell = |row, col| self.get(row, col);
+        write!(f, "{}", format_cells(rows, cols, cell, DISPLAY_ROWS, DISPLAY_COLS))
+    }
+}
+
+// The indices to show out of `0..len`, with `None` where the elided ones would be.
+fn shown(len: usize, max: usize) -> Vec<Option<usize>> {
+    if len <= max {
+        return (0..len).map(Some).collect();
+    }
+    let (head, tail) = (max.div_ceil(2), max / 2);
+    let mut indices: Vec<Option<usize>> = (0..head).map(Some).collect();
+    indices.push(None);
+    indices.extend((len - tail..len).map(Some));
+    indices
+}
+
+// The formatting behind both `format_matrix` and `Display`, reading cells through `cell`
+// so neither representation has to be converted to the other first.
+fn format_cells(
+    rows: usize,
+    cols: usize,
+    cell: impl Fn(usize, usize) -> u64,
+    max_rows: usize,
+    max_cols: usize,
+) -> String {
+    let (shown_rows, shown_cols) = (shown(rows, max_rows), shown(cols, max_cols));
+    let text: Vec<Vec<String>> = shown_rows
+        .iter()
+        .map(|row| {
+            shown_cols
+                .iter()
+                .map(|col| match (row, col) {
+                    (Some(row), Some(col)) => cell(*row, *col).to_string(),
+                    _ => "…".to_string(),
+                })
+                .collect()
+        })
+        .collect();
+    let width = text.iter().flatten().map(|cell| cell.chars().count()).max().unwrap_or(0);
+    let mut out = format!("{}x{} matrix", rows, cols);
+    for line in text {
+        let cells: Vec<String> = line.iter().map(|cell| format!("{:>width$}", cell)).collect();
+        out.push('\n');
+        out.push_str(&cells.join(" "));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::gen_matrix;
+
+    #[test]
+    fn columns_are_right_aligned() {
+        let three_by_three = vec![vec![1, 20, 3], vec![400, 5, 6], vec![7, 8, 9]];
+        let expected = "3x3 matrix\n  1  20   3\n400   5   6\n  7   8   9";
+        assert_eq!(format_matrix(&three_by_three, 5, 5), expected);
+        assert_eq!(Matrix::from_nested(three_by_three).to_string(), expected);
+        assert_eq!(format_matrix(&[], 5, 5), "0x0 matrix");
+    }
+
+    #[test]
+    fn large_matrices_are_shortened_in_the_middle() {
+        let matrix = gen_matrix(100, 100, 1);
+        let shortened = format_matrix(&matrix, 4, 3);
+        let lines: Vec<&str> = shortened.lines().collect();
+        assert_eq!(lines.len(), 1 + 4 + 1);
+        assert_eq!(lines[0], "100x100 matrix");
+        assert!(lines[3].split_whitespace().all(|cell| cell == "…"));
+        assert!(lines.iter().skip(1).all(|line| line.split_whitespace().nth(2) == Some("…")));
+        let first = matrix[0][0].to_string();
+        assert_eq!(lines[1].split_whitespace().next(), Some(first.as_str()));
+        assert_eq!(Matrix::from_nested(matrix).to_string().lines().count(), 1 + DISPLAY_ROWS + 1);
+    }
+}
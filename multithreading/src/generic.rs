@@ -0,0 +1,109 @@
+// Multiplication for any numeric element type, not just `u64`. All the algorithm needs is
+// a zero to start each sum from (`Default`), `+` and `*`, and values it can copy.
+use std::ops::{Add, Mul};
+
+use crate::error::MatMulError;
+use crate::matrix::check_dimensions;
+
+// The element types a matrix can be multiplied over. The blanket impl makes every type
+// with the right operators `Numeric` automatically: `i32`, `i64`, `u64`, `f32`, `f64`, ...
+pub trait Numeric: Copy + Default + Add<Output = Self> + Mul<Output = Self> {}
+
+impl<T: Copy + Default + Add<Output = T> + Mul<Output = T>> Numeric for T {}
+
+// `(rows, cols)` of a nested matrix, taking the column count from the first row, so an
+// empty matrix is `(0, 0)` rather than a panic.
+pub fn nested_shape<T>(matrix: &[Vec<T>]) -> (usize, usize) {
+    (matrix.len(), matrix.first().map_or(0, Vec::len))
+}
+
+// `nested_shape`, after checking that every row is as long as the first one. Indexing
+// assumes that, so a ragged matrix would otherwise give wrong results or panic halfway
+// through. Every multiply function on nested matrices calls this before doing any work;
+// it only looks at each row's length, not at the values, so it costs O(rows).
+pub fn validate_matrix<T>(matrix: &[Vec<T>]) -> Result<(usize, usize), MatMulError> {
+    let (rows, cols) = nested_shape(matrix);
+    match matrix.iter().position(|row| row.len() != cols) {
+        Some(row) => Err(MatMulError::Ragged { row, expected: cols, found: matrix[row].len() }),
+        None => Ok((rows, cols)),
+    }
+}
+
+// Sequential multiplication of nested matrices of any `Numeric` type.
+pub fn multiply_generic<T: Numeric>(
+    matrix_a: &[Vec<T>],
+    matrix_b: &[Vec<T>],
+) -> Result<Vec<Vec<T>>, MatMulError> {
+    let (a_shape, b_shape) = (validate_matrix(matrix_a)?, validate_matrix(matrix_b)?);
+    check_dimensions(a_shape, b_shape)?;
+    let mut ans = vec![vec![T::default(); b_shape.1]; a_shape.0];
+    for (a_row, ans_row) in matrix_a.iter().zip(&mut ans) {
+        multiply_nested_row(a_row, matrix_b, ans_row);
+    }
+    Ok(ans)
+}
+
+// One row of A x B into `out`, which must start out as zeros; the nested counterpart of
+// `matrix::multiply_row`, with the same k, col loop order.
+pub fn multiply_nested_row<T: Numeric>(a_row: &[T], matrix_b: &[Vec<T>], out: &mut [T]) {
+    for (&a, b_row) in a_row.iter().zip(matrix_b) {
+        for (cell, &b) in out.iter_mut().zip(b_row) {
+            *cell = *cell + a * b;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+    use crate::random::gen_matrix;
+
+    #[test]
+    fn works_for_any_numeric_type() -> Result<(), MatMulError> {
+        let signed: Vec<Vec<i32>> = vec![vec![1, -2], vec![-3, 4]];
+        assert_eq!(multiply_generic(&signed, &signed)?, vec![vec![7, -10], vec![-15, 22]]);
+        let by_hand = vec![vec![58, 64], vec![139, 154]];
+        let left: Vec<Vec<u64>> = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        assert_eq!(multiply_generic(&left, &[vec![7, 8], vec![9, 10], vec![11, 12]])?, by_hand);
+        // 0.1 has no exact binary form, so floats are compared with a tolerance.
+        let float_a: Vec<Vec<f64>> = vec![vec![0.1, 0.2], vec![0.3, 0.4]];
+        let float_b = vec![vec![0.5, 0.6], vec![0.7, 0.8]];
+        let product = multiply_generic(&float_a, &float_b)?;
+        for (row, expected_row) in product.iter().zip([[0.19, 0.22], [0.43, 0.5]]) {
+            for (value, expected_value) in row.iter().zip(expected_row) {
+                assert!((value - expected_value).abs() < 1e-12);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn ragged_and_empty_matrices_are_rejected() {
+        let ragged = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8], vec![9, 10, 11]];
+        let ragged_err = MatMulError::Ragged { row: 2, expected: 3, found: 2 };
+        assert_eq!(validate_matrix(&ragged), Err(ragged_err.clone()));
+        assert_eq!(multiply_generic(&ragged, &ragged), Err(ragged_err));
+        assert_eq!(validate_matrix(&gen_matrix(200, 200, 1)), Ok((200, 200)));
+        assert_eq!(validate_matrix::<u64>(&[]), Ok((0, 0)));
+        assert_eq!(nested_shape(&[vec![1, 2], vec![3]]), (2, 2));
+        assert_eq!(multiply_generic::<u64>(&[], &[vec![1]]), Err(MatMulError::EmptyMatrix));
+    }
+
+    // Compares wall-clock times, so a busy machine can fail it; run it with
+    // `cargo test -- --ignored`.
+    #[test]
+    #[ignore = "timing-dependent"]
+    fn validating_costs_nothing_next_to_multiplying() -> Result<(), MatMulError> {
+        // Validating only reads the length of every row.
+        let (a, b) = (gen_matrix(200, 200, 1), gen_matrix(200, 200, 2));
+        let start = Instant::now();
+        validate_matrix(&a)?;
+        let validate_time = start.elapsed();
+        let start = Instant::now();
+        multiply_generic(&a, &b)?;
+        assert!(validate_time * 100 < start.elapsed());
+        Ok(())
+    }
+}
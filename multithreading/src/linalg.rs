@@ -0,0 +1,257 @@
+// Determinants and inverses by elimination. Subtracting a multiple of one row from another
+// doesn't change the determinant, and swapping two rows flips its sign, so reducing the
+// matrix to upper triangular form leaves the determinant as the product of the diagonal,
+// times -1 for every swap.
+use crate::error::MatMulError;
+use crate::generic::validate_matrix;
+
+fn check_square<T>(matrix: &[Vec<T>]) -> Result<usize, MatMulError> {
+    let (rows, cols) = validate_matrix(matrix)?;
+    if rows != cols {
+        return Err(MatMulError::NotSquare { rows, cols });
+    }
+    Ok(rows)
+}
+
+// For every column, the size below which its pivot counts as 0: rounding errors of a few
+// ulps of the largest entry of that column in the input are all that is left of it. Each
+// column is measured against itself, so a column that is small throughout (say 1e-10 next
+// to 1e10 elsewhere) is still a perfectly good pivot.
+fn negligible_pivots(matrix: &[Vec<f64>]) -> Vec<f64> {
+    let n = matrix.len();
+    (0..n)
+        .map(|k| {
+            let scale = matrix.iter().fold(0.0f64, |max, row| max.max(row[k].abs()));
+            scale * n as f64 * f64::EPSILON
+        })
+        .collect()
+}
+
+// The row at or below `k` with the largest entry in column `k`.
+fn pivot_row(m: &[Vec<f64>], k: usize) -> usize {
+    (k..m.len()).max_by(|&i, &j| m[i][k].abs().total_cmp(&m[j][k].abs())).expect("k < n")
+}
+
+// Gaussian elimination (the U of an LU decomposition) with partial pivoting: each column's
+// pivot is the remaining entry with the largest magnitude, which keeps the multipliers at
+// most 1 and the rounding errors small. A singular matrix gives 0.0, not an error; because
+// of rounding, that means a pivot that is negligible next to the largest entry of its
+// column in the input, not just one that is exactly 0. The 0 x 0 matrix has determinant 1.
+pub fn determinant(matrix: &[Vec<f64>]) -> Result<f64, MatMulError> {
+    let n = check_square(matrix)?;
+    let mut m = matrix.to_vec();
+    let negligible = negligible_pivots(matrix);
+    let mut det = 1.0;
+    for k in 0..n {
+        let pivot_row = pivot_row(&m, k);
+        if m[pivot_row][k].abs() <= negligible[k] {
+            return Ok(0.0);
+        }
+        if pivot_row != k {
+            m.swap(pivot_row, k);
+            det = -det;
+        }
+        let (top, bottom) = m.split_at_mut(k + 1);
+        let pivot = &top[k];
+        for row in bottom {
+            let factor = row[k] / pivot[k];
+            for (cell, p) in row[k..].iter_mut().zip(&pivot[k..]) {
+                *cell -= factor * p;
+            }
+        }
+        det *= m[k][k];
+    }
+    Ok(det)
+}
+
+// The inverse by Gauss-Jordan elimination: the same row operations that turn the matrix
+// into the identity, starting from the identity instead, produce the inverse. Each column
+// is pivoted like in `determinant`, and a singular matrix (a pivot negligible next to its
+// own column) is `MatMulError::Singular`.
+pub fn invert(matrix: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, MatMulError> {
+    let n = check_square(matrix)?;
+    let negligible = negligible_pivots(matrix);
+    // Every row of the input with the matching row of the identity appended: [A | I].
+    let mut m: Vec<Vec<f64>> = matrix
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let mut augmented = row.clone();
+            augmented.extend((0..n).map(|j| if i == j { 1.0 } else { 0.0 }));
+            augmented
+        })
+        .collect();
+    for k in 0..n {
+        let pivot_row = pivot_row(&m, k);
+        if m[pivot_row][k].abs() <= negligible[k] {
+            return Err(MatMulError::Singular);
+        }
+        m.swap(pivot_row, k);
+        // Scale the pivot row so the pivot is 1, then clear column `k` in every other row,
+        // above as well as below.
+        let pivot = m[k][k];
+        for cell in &mut m[k] {
+            *cell /= pivot;
+        }
+        let pivot = m[k].clone();
+        for (i, row) in m.iter_mut().enumerate() {
+            let factor = row[k];
+            if i == k || factor == 0.0 {
+                continue;
+            }
+            for (cell, p) in row.iter_mut().zip(&pivot) {
+                *cell -= factor * p;
+            }
+        }
+    }
+    // [I | A^-1]: the right half is the inverse.
+    Ok(m.into_iter().map(|row| row[n..].to_vec()).collect())
+}
+
+// The exact determinant of an integer matrix, by Bareiss's fraction-free elimination. Plain
+// elimination would need fractions; Bareiss divides every updated entry by the previous
+// pivot, a division that is always exact, so every intermediate value is itself the
+// determinant of a submatrix and stays an integer. Those are computed in `i128`. A value
+// that doesn't fit anyway is `MatMulError::Overflow` at the entry being updated, as is a
+// determinant outside the range of `i64`, reported at the last diagonal entry.
+pub fn determinant_i64(matrix: &[Vec<i64>]) -> Result<i64, MatMulError> {
+    let n = check_square(matrix)?;
+    if n == 0 {
+        return Ok(1);
+    }
+    let mut m: Vec<Vec<i128>> = matrix
+        .iter()
+        .map(|row| row.iter().map(|&value| i128::from(value)).collect())
+        .collect();
+    let mut sign = 1;
+    let mut previous_pivot = 1;
+    for k in 0..n - 1 {
+        // Any non-zero pivot works: nothing is rounded, so there is no need for the largest.
+        let Some(pivot_row) = (k..n).find(|&i| m[i][k] != 0) else {
+            return Ok(0);
+        };
+        if pivot_row != k {
+            m.swap(pivot_row, k);
+            sign = -sign;
+        }
+        for i in k + 1..n {
+            for j in k + 1..n {
+                let overflow = MatMulError::Overflow { row: i, col: j };
+                let left = m[i][j].checked_mul(m[k][k]).ok_or(overflow.clone())?;
+                let right = m[i][k].checked_mul(m[k][j]).ok_or(overflow.clone())?;
+                m[i][j] = left.checked_sub(right).ok_or(overflow)? / previous_pivot;
+            }
+            m[i][k] = 0;
+        }
+        previous_pivot = m[k][k];
+    }
+    let overflow = MatMulError::Overflow { row: n - 1, col: n - 1 };
+    i64::try_from(sign * m[n - 1][n - 1]).map_err(|_| overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::float::{matrices_approx_eq, multiply_f64, multiply_parallel_f64};
+    use crate::generic::multiply_generic;
+    use crate::random::gen_matrix_f64;
+
+    #[test]
+    fn known_determinants() -> Result<(), MatMulError> {
+        let known = vec![vec![6, 1, 1], vec![4, -2, 5], vec![2, 8, 7]];
+        let known_f64: Vec<Vec<f64>> =
+            known.iter().map(|row| row.iter().map(|&x| x as f64).collect()).collect();
+        assert_eq!(determinant_i64(&known), Ok(-306));
+        assert!((determinant(&known_f64)? + 306.0).abs() < 1e-9);
+        assert_eq!(determinant(&[vec![1.0, 0.0], vec![0.0, 1.0]]), Ok(1.0));
+        assert_eq!(determinant_i64(&[vec![1, 0, 0], vec![0, 1, 0], vec![0, 0, 1]]), Ok(1));
+        Ok(())
+    }
+
+    #[test]
+    fn singular_matrices_have_determinant_zero() {
+        let singular = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0], vec![7.0, 8.0, 9.0]];
+        assert_eq!(determinant(&singular), Ok(0.0));
+        assert_eq!(determinant_i64(&[vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]), Ok(0));
+        assert_eq!(determinant_i64(&[vec![0, 0], vec![0, 5]]), Ok(0));
+    }
+
+    #[test]
+    fn small_pivots_are_only_negligible_next_to_their_own_column() {
+        // 1e-10 is tiny next to 1e10, but it is the whole of its column.
+        let badly_scaled = vec![vec![1e10, 0.0], vec![0.0, 1e-10]];
+        assert_eq!(determinant(&badly_scaled), Ok(1.0));
+    }
+
+    #[test]
+    fn swapping_rows_flips_the_sign() {
+        assert_eq!(determinant(&[vec![0.0, 1.0], vec![1.0, 0.0]]), Ok(-1.0));
+        assert_eq!(determinant_i64(&[vec![0, 1], vec![1, 0]]), Ok(-1));
+        // A cycle of three rows is two swaps, so the sign comes back to +1.
+        assert_eq!(determinant_i64(&[vec![0, 1, 0], vec![0, 0, 1], vec![1, 0, 0]]), Ok(1));
+        let anti_diagonal = vec![vec![0.0, 0.0, 2.0], vec![0.0, 3.0, 0.0], vec![4.0, 0.0, 0.0]];
+        assert_eq!(determinant(&anti_diagonal), Ok(-24.0));
+    }
+
+    #[test]
+    fn non_square_and_overflowing_determinants_are_errors() {
+        let not_square = MatMulError::NotSquare { rows: 1, cols: 2 };
+        assert_eq!(determinant(&[vec![1.0, 2.0]]), Err(not_square.clone()));
+        assert_eq!(determinant_i64(&[vec![1, 2]]), Err(not_square.clone()));
+        assert_eq!(invert(&[vec![1.0, 2.0]]), Err(not_square));
+        let too_big = determinant_i64(&[vec![i64::MAX, 0], vec![0, 2]]);
+        assert_eq!(too_big, Err(MatMulError::Overflow { row: 1, col: 1 }));
+    }
+
+    #[test]
+    fn the_determinant_of_a_product_is_the_product_of_determinants() -> Result<(), MatMulError> {
+        for seed in 0..5 {
+            let (a, b) = (gen_matrix_f64(4, 4, 50 + seed), gen_matrix_f64(4, 4, 60 + seed));
+            let (det_a, det_b) = (determinant(&a)?, determinant(&b)?);
+            let det_ab = determinant(&multiply_f64(&a, &b)?)?;
+            assert!((det_ab - det_a * det_b).abs() <= 1e-9 * (1.0 + det_ab.abs()));
+        }
+        let int_a = vec![vec![2, -1, 3], vec![0, 4, 1], vec![5, 2, -2]];
+        let int_b = vec![vec![1, 0, 2], vec![3, -1, 1], vec![0, 2, 4]];
+        let int_ab = multiply_generic(&int_a, &int_b)?;
+        assert_eq!(determinant_i64(&int_ab)?, determinant_i64(&int_a)? * determinant_i64(&int_b)?);
+        Ok(())
+    }
+
+    #[test]
+    fn inverses_multiply_to_the_identity() -> Result<(), MatMulError> {
+        let m = vec![vec![0.3, 1.7], vec![2.9, -0.4]];
+        let det = m[0][0] * m[1][1] - m[0][1] * m[1][0];
+        let by_formula = vec![vec![m[1][1] / det, -m[0][1] / det], vec![-m[1][0] / det, m[0][0] / det]];
+        assert!(matrices_approx_eq(&invert(&m)?, &by_formula, 1e-12));
+        // Adding n to the diagonal of a random n x n matrix keeps it far from singular.
+        for (n, seed) in [(3, 70), (10, 71), (40, 72)] {
+            let mut a = gen_matrix_f64(n, n, seed);
+            for (i, row) in a.iter_mut().enumerate() {
+                row[i] += n as f64;
+            }
+            let identity: Vec<Vec<f64>> =
+                (0..n).map(|i| (0..n).map(|j| f64::from(u8::from(i == j))).collect()).collect();
+            assert!(matrices_approx_eq(&multiply_f64(&a, &invert(&a)?)?, &identity, 1e-9));
+            assert!(matrices_approx_eq(&multiply_parallel_f64(&invert(&a)?, &a, 3)?, &identity, 1e-9));
+        }
+        assert_eq!(invert(&[vec![4.0]]), Ok(vec![vec![0.25]]));
+        let swap_rows = vec![vec![0.0, 2.0], vec![1.0, 0.0]];
+        assert_eq!(invert(&swap_rows), Ok(vec![vec![0.0, 1.0], vec![0.5, 0.0]]));
+        Ok(())
+    }
+
+    #[test]
+    fn singular_matrices_have_no_inverse() {
+        assert_eq!(invert(&[vec![1.0, 2.0], vec![2.0, 4.0]]), Err(MatMulError::Singular));
+        assert_eq!(invert(&[vec![0.0]]), Err(MatMulError::Singular));
+        let singular = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0], vec![7.0, 8.0, 9.0]];
+        assert_eq!(invert(&singular), Err(MatMulError::Singular));
+    }
+
+    #[test]
+    fn badly_scaled_matrices_still_have_an_inverse() {
+        let badly_scaled = vec![vec![1e10, 0.0], vec![0.0, 1e-10]];
+        assert_eq!(invert(&badly_scaled), Ok(vec![vec![1e-10, 0.0], vec![0.0, 1e10]]));
+    }
+}
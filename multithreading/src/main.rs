@@ -1,18 +1,128 @@
-use std::{thread, time::Instant};
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::Path,
+    process,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+mod bench; // `bench_multiply`, timing every multiply implementation side by side.
+mod blocked; // `multiply_blocked`, cache-friendly tiled multiplication.
+mod channel; // `multiply_mpsc`, workers fed row indices over a channel.
+mod checked; // `multiply_checked`, which reports overflow instead of wrapping.
+mod chunked; // `multiply_chunked`, one contiguous block of rows per thread.
+mod elementwise; // `+` and `-` on `Matrix`, `add_nested`, `sub_nested` and `add_parallel`.
+mod config; // `ThreadConfig`, the thread count from the command line or environment.
+mod csv; // `load_matrix_csv` and `parse_matrix_csv`, matrices from comma-separated text.
+mod error; // `MatMulError`, returned instead of panicking on bad input.
+mod float; // `f64` matrices and `matrices_approx_eq` for comparing them.
+mod format; // `format_matrix` and `Display` for `Matrix`, aligned and shortened output.
+mod generic; // `multiply_generic` and the `Numeric` trait for any element type.
+mod linalg; // `determinant`, `determinant_i64` and `invert`, by elimination.
+mod matrix; // `Matrix`, a matrix stored in one flat `Vec<u64>`, row- or column-major.
+mod mutex; // `multiply_mutex`, workers storing rows into one shared `Mutex`.
+mod pool; // `multiply_pooled`, a fixed-size pool of workers sharing the rows.
+mod power; // `matrix_pow`, integer powers of a square matrix by repeated squaring.
+mod progress; // `multiply_with_progress`, reporting finished rows while it runs.
+mod random; // `XorShift64` and seeded random matrices.
+mod reduce; // `dot`, `row_sums`, `col_sums`, `matrix_stats` and `checksum`.
+mod scoped; // `multiply_scoped`, scoped threads borrowing the inputs.
+mod sparse; // `CsrMatrix`, compressed sparse rows, and sparse x dense multiplication.
+mod speedup; // `SpeedupReport`, speedup and efficiency against the sequential kernel.
+mod strassen; // `multiply_strassen`, 7 multiplications per split instead of 8.
+mod transpose; // `transpose` and `multiply_transposed`, which reads B as rows.
+mod vector; // `multiply_vec`, matrix x vector, sequential and parallel.
+mod verify; // Checking every implementation against the sequential one.
+
+use bench::{bench_dot, bench_multiply, print_bench_table};
+use blocked::multiply_blocked;
+use channel::multiply_mpsc;
+use checked::multiply_checked;
+use chunked::{chunk_ranges, multiply_chunked};
+use config::{parse_args, usage, Config, ThreadConfig, THREADS_ENV_VAR};
+use csv::{load_matrix_csv, parse_matrix_csv, save_matrix_csv, save_summary};
+use elementwise::{add_nested, add_parallel, sub_nested};
+use error::MatMulError;
+use float::{matrices_approx_eq, multiply_f64, multiply_parallel_f64};
+use format::format_matrix;
+use generic::{multiply_generic, multiply_nested_row, validate_matrix, Numeric};
+use linalg::{determinant, determinant_i64, invert};
+use matrix::{
+    check_dimensions, multiply_matrix, multiply_matrix_parallel, multiply_row, Layout, Matrix,
+};
+use mutex::multiply_mutex;
+use pool::{default_threads, multiply_pooled};
+use power::matrix_pow;
+use progress::multiply_with_progress;
+use random::{gen_matrix, gen_matrix_f64, gen_sparse_matrix};
+use reduce::{
+    checksum, col_sums, col_sums_parallel, dot, dot_parallel, dot_unrolled, matrix_stats,
+    row_sums, row_sums_parallel,
+};
+use scoped::multiply_scoped;
+use sparse::{multiply_csr_dense, multiply_csr_dense_parallel, CsrMatrix};
+use speedup::{measure_speedup, SpeedupReport};
+use strassen::multiply_strassen;
+use transpose::{multiply_transposed, transpose};
+use vector::{multiply_vec, multiply_vec_parallel};
+use verify::{random_pair, NESTED_VARIANTS};
+
+fn main() -> Result<(), MatMulError> {
+    // Command-line options, see `usage`, e.g.
+    //   cargo run --release -- --size 512 --threads 8 --impl scoped --seed 42
+    // The thread count can also come from the environment:
+    //   MATMUL_THREADS=4 cargo run
+    let config = parse_args(env::args().skip(1).collect()).unwrap_or_else(|err| {
+        eprintln!("Error: {}\n\n{}", err, usage());
+        process::exit(2);
+    });
+    if config.help {
+        println!("{}", usage());
+        return Ok(());
+    }
+    let env_threads = env::var(THREADS_ENV_VAR).ok();
+    let threads = ThreadConfig::resolve(config.threads, env_threads.as_deref())
+        .unwrap_or_else(|err| {
+            eprintln!("Error: {}", err);
+            process::exit(1);
+        })
+        .threads();
+
+    // Given two CSV files, multiply the matrices in them instead of running the demo:
+    //   cargo run -- a.csv b.csv
+    if let [path_a, path_b] = config.files.as_slice() {
+        return multiply_files(Path::new(path_a), Path::new(path_b), threads);
+    }
+    if config.is_single_run() {
+        return run_single(&config, threads);
+    }
+    println!("Using {} thread(s)", threads);
 
-fn main() {
     // Define a large matrix A (100x100 matrix)
     let matrix_a: Vec<Vec<u64>> = (0..100).map(|i| (0..100).map(|j| (i + j) as u64).collect()).collect();
 
     // Define a large matrix B (100x100 matrix)
     let matrix_b: Vec<Vec<u64>> = (0..100).map(|i| (0..100).map(|j| (i * j) as u64).collect()).collect();
 
+    // Both versions borrow the matrices, so they can run one after the other on the same
+    // data. The sequential one goes first.
+    let start = Instant::now();
+    let sequential = multiply(&matrix_a, &matrix_b)?;
+    let sequential_duration = start.elapsed();
+
     // Start the timer
     let start = Instant::now();
 
     // Call the multiply function
-    // let result = multiply(matrix_a, matrix_b);
-    let result = multiply_parallel(matrix_a, matrix_b);
+    let result = match multiply_parallel(&matrix_a, &matrix_b) {
+        Ok(result) => result,
+        Err(err) => {
+            println!("Matrix multiplication failed: {}", err);
+            return Err(err);
+        }
+    };
 
 
     // Stop the timer
@@ -20,53 +130,358 @@ fn main() {
 
     // Print the result
     println!("Result of matrix multiplication:");
-    for row in result.iter().take(5) {  // Print only the first 5 rows to avoid flooding the console
-        println!("{:?}", row);
+    // Show only the corners to avoid flooding the console
+    println!("{}", format_matrix(&result, 6, 6));
+
+    // How the two compare. `multiply_parallel` starts one thread per row of the result, but
+    // no more of them can run at once than there are cores, so that is what the efficiency
+    // is measured against.
+    let baseline = ("sequential", sequential_duration);
+    let running_at_once = result.len().min(default_threads());
+    let report = SpeedupReport::from_durations(baseline, running_at_once, &[("thread per row", duration)]);
+    print!("{}", report.to_table());
+
+    // The sequential and parallel versions give exactly the same numbers.
+    println!("{}", sequential == result); // Output: true
+
+    // Bad input is reported as an error instead of a panic.
+    let two_by_three = vec![vec![1, 2, 3], vec![4, 5, 6]];
+    let errors = [
+        multiply(&two_by_three, &two_by_three),
+        multiply(&[], &two_by_three),
+    ];
+    for err in errors.into_iter().filter_map(Result::err) {
+        println!("Rejected: {}", err);
+    }
+    // Output: Rejected: incompatible multiplication: A has 3 columns but B has 2 rows
+    // Output: Rejected: cannot multiply an empty matrix
+    // A 2x3 times a 3x2 is fine, and gives a 2x2.
+    let three_by_two = vec![vec![1, 2], vec![3, 4], vec![5, 6]];
+    println!("{:?}", multiply(&two_by_three, &three_by_two)?); // Output: [[22, 28], [49, 64]]
+    println!("{}", Matrix::from_nested(vec![vec![1, 20, 3], vec![400, 5, 6], vec![7, 8, 9]]));
+    // Output: 3x3 matrix
+    // Output:   1  20   3
+    // Output: 400   5   6
+    // Output:   7   8   9
+
+    // Converting to a `Matrix` and back is lossless, and `get`/`set` address single elements.
+    let mut flat = Matrix::from_nested(matrix_a.clone());
+    println!("{}", flat.to_nested() == matrix_a); // Output: true
+    flat.set(3, 7, 0);
+    println!("{} {}", flat.get(3, 6), flat.get(3, 7)); // Output: 9 0
+
+    // Nested vs flat on a bigger input. The nested version follows a pointer to a separate
+    // allocation for every element of B it reads; the flat one walks through a single
+    // buffer in order, so it is usually the faster of the two (try `cargo run --release`
+    // for more stable numbers). With B column-major the kernels read its columns directly.
+    let size = 200;
+    let big_a: Vec<Vec<u64>> = (0..size).map(|i| (0..size).map(|j| (i + j) as u64).collect()).collect();
+    let big_b: Vec<Vec<u64>> = (0..size).map(|i| (0..size).map(|j| (i * j) as u64).collect()).collect();
+    let (flat_a, flat_b) = (Matrix::from_nested(big_a.clone()), Matrix::from_nested(big_b.clone()));
+    let start = Instant::now();
+    multiply_nested(&big_a, &big_b)?;
+    let nested_time = start.elapsed();
+    let start = Instant::now();
+    multiply_matrix(&flat_a, &flat_b)?;
+    let flat_time = start.elapsed();
+    println!("{}x{} nested: {:?}, flat: {:?}", size, size, nested_time, flat_time);
+    let col_major_b = flat_b.to_layout(Layout::ColMajor);
+    let start = Instant::now();
+    multiply_matrix(&flat_a, &col_major_b)?;
+    println!("{}x{} with B column-major: {:?}", size, size, start.elapsed());
+    println!("{:?}", Matrix::from_nested(three_by_two).to_layout(Layout::ColMajor).col(1)); // Output: [2, 4, 6]
+
+    // A pool of workers gives the same result with any number of threads; more threads
+    // only help up to the number of cores.
+    let mut thread_counts = vec![1, 2, 4, threads];
+    thread_counts.sort();
+    thread_counts.dedup();
+    for threads in thread_counts {
+        let start = Instant::now();
+        multiply_pooled(&flat_a, &flat_b, threads)?;
+        println!("Pool of {} thread(s): {:?}", threads, start.elapsed());
+    }
+    // Contiguous blocks of rows: 200 rows over 3 threads is 67 + 67 + 66, and with more
+    // threads than rows the extra blocks are empty.
+    println!("{:?}", chunk_ranges(200, 3)); // Output: [0..67, 67..134, 134..200]
+    println!("{:?}", chunk_ranges(2, 4)); // Output: [0..1, 1..2, 2..2, 2..2]
+    let start = Instant::now();
+    multiply_chunked(&flat_a, &flat_b, threads)?;
+    println!("Chunked over {} thread(s): {:?}", threads, start.elapsed());
+    let start = Instant::now();
+    multiply_mpsc(&flat_a, &flat_b, threads)?;
+    println!("Over a channel: {:?}", start.elapsed());
+    let start = Instant::now();
+    multiply_mutex(&flat_a, &flat_b, threads)?;
+    println!("Into a Mutex: {:?}", start.elapsed());
+
+    // Progress while multiplying: the line is rewritten in place (`\r` goes back to the
+    // start of it) each time the count changes.
+    multiply_with_progress(&flat_a, &flat_b, threads, |done, total| {
+        print!("\rrows {}/{}", done, total);
+        io::stdout().flush().ok();
+    })?;
+    println!();
+    // Output: rows 200/200
+
+    // Thread per row, cloning B for every thread vs sharing it through an `Arc`. Scoped
+    // threads borrow the inputs, so there's nothing to clone or share at all.
+    let start = Instant::now();
+    multiply_matrix_parallel_cloning(&flat_a, &flat_b)?;
+    let cloning_time = start.elapsed();
+    let (shared_a, shared_b) = (Arc::new(flat_a.clone()), Arc::new(flat_b.clone()));
+    let start = Instant::now();
+    multiply_matrix_parallel(&shared_a, &shared_b)?;
+    let sharing_time = start.elapsed();
+    let start = Instant::now();
+    multiply_scoped(&big_a, &big_b, threads)?;
+    let scoped_time = start.elapsed();
+    println!("{}x{} cloning B per thread: {:?}, sharing it with Arc: {:?}", size, size, cloning_time, sharing_time);
+    println!("{}x{} borrowing with scoped threads: {:?}", size, size, scoped_time);
+
+    // Other kernels: checked, against a transposed B, tiled, and Strassen's. Checked multiplication names the
+    // cell that overflows: row 1, column 0 is u64::MAX * 1 + 1 * 1.
+    let huge = vec![vec![1, 0], vec![u64::MAX, 1]];
+    if let Err(err) = multiply_checked(&huge, &huge) {
+        println!("Checked multiply: {}", err); // Output: Checked multiply: overflow computing the result at row 1, column 0
+    }
+    println!("{:?}", transpose(&two_by_three)); // Output: [[1, 4], [2, 5], [3, 6]]
+    let start = Instant::now();
+    let transposed = multiply_transposed(&big_a, &big_b)?;
+    println!("{}x{} against a transposed B: {:?}", size, size, start.elapsed());
+    let start = Instant::now();
+    let tiled = multiply_blocked(&big_a, &big_b, 0)?;
+    println!("{}x{} tiled: {:?}", size, size, start.elapsed());
+    let start = Instant::now();
+    multiply_strassen(&big_a, &big_b, 64)?;
+    println!("{}x{} Strassen: {:?}", size, size, start.elapsed());
+    println!("{}", transposed == tiled && tiled == multiply_generic(&big_a, &big_b)?); // Output: true
+
+    // Addition and subtraction need matching shapes, and multiplication distributes over
+    // addition: A x (B + C) == A x B + A x C.
+    let dist_a = Matrix::from_nested(two_by_three.clone());
+    let dist_b = Matrix::from_nested(vec![vec![7, 8], vec![9, 10], vec![11, 12]]);
+    let dist_c = Matrix::from_nested(vec![vec![1, 0], vec![2, 5], vec![0, 3]]);
+    let left = multiply_matrix(&dist_a, &(&dist_b + &dist_c)?)?;
+    let right = (&multiply_matrix(&dist_a, &dist_b)? + &multiply_matrix(&dist_a, &dist_c)?)?;
+    println!("{:?} == {:?}", left.to_nested(), right.to_nested()); // Output: [[63, 83], [153, 197]] == [[63, 83], [153, 197]]
+    if let Err(err) = &dist_a - &dist_b {
+        println!("Rejected: {}", err); // Output: Rejected: incompatible shapes: A is 2x3 but B is 3x2
+    }
+    let doubled = add_nested(&big_a, &big_a)?;
+    println!("{}", add_parallel(&flat_a, &flat_a, threads)?.to_nested() == doubled); // Output: true
+    println!("{}", sub_nested(&doubled, &big_a)? == big_a); // Output: true
+
+    // Powers of a matrix. [[1, 1], [1, 0]]^n is [[fib(n + 1), fib(n)], [fib(n), fib(n - 1)]],
+    // which gives fib(n) in O(log n) multiplications.
+    let fib_matrix = Matrix::from_nested(vec![vec![1, 1], vec![1, 0]]);
+    println!("fib(30) = {}", matrix_pow(&fib_matrix, 30)?.get(0, 1)); // Output: fib(30) = 832040
+    if let Err(err) = matrix_pow(&dist_a, 0) {
+        println!("Rejected: {}", err); // Output: Rejected: expected a square matrix, found 2x3
+    }
+
+    // Parsing CSV text, and saving a result and loading it back. The files go into a fresh
+    // directory under the system temp dir, named after the process so runs don't collide.
+    if let Err(err) = parse_matrix_csv("1,2\n3,x4\n") {
+        println!("Rejected: {}", err); // Output: Rejected: line 2, column 2: "x4" is not a number
+    }
+    let out_dir = env::temp_dir().join(format!("multithreading-{}", process::id()));
+    match save_and_reload(&out_dir, &result, duration) {
+        Ok(reloaded) => println!("Saved and reloaded: {}", reloaded == result), // Output: Saved and reloaded: true
+        Err(err) => println!("Could not save the result: {}", err),
     }
 
-    // Print the duration in milliseconds
-    println!("Time taken: {} ms", duration.as_millis());
+    // The generic versions work for any `Numeric` element type.
+    let signed: Vec<Vec<i32>> = vec![vec![1, -2], vec![-3, 4]];
+    println!("{:?}", multiply_generic(&signed, &signed)?); // Output: [[7, -10], [-15, 22]]
+    let single: Vec<Vec<f32>> = vec![vec![1.5, 2.0]];
+    println!("{:?}", multiply_scoped(&single, &[vec![2.0], vec![0.25]], threads)?); // Output: [[3.5]]
+    let flat_signed = Matrix::from_nested(signed);
+    println!("{:?}", multiply_pooled(&flat_signed, &flat_signed, 2)?.to_nested()); // Output: [[7, -10], [-15, 22]]
+
+    // Floats pick up rounding error, so they are compared with a tolerance. A matrix times
+    // its inverse is the identity, up to rounding.
+    let m = vec![vec![0.3, 1.7], vec![2.9, -0.4]];
+    let identity = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+    let sequential = multiply_f64(&m, &invert(&m)?)?;
+    let parallel = multiply_parallel_f64(&invert(&m)?, &m, threads)?;
+    let is_identity = |product| matrices_approx_eq(product, &identity, 1e-12);
+    println!("M x M^-1 is the identity: {}", is_identity(&sequential) && is_identity(&parallel)); // Output: M x M^-1 is the identity: true
+    let random_f64 = gen_matrix_f64(40, 30, 7);
+    println!("{}", matrices_approx_eq(&random_f64, &gen_matrix_f64(40, 30, 7), 0.0)); // Output: true
+    // Random integer matrices are reproducible too, with every entry below `MAX_ENTRY`.
+    println!("{:?}", gen_matrix(2, 3, 1)); // Output: [[152, 920, 705], [982, 960, 229]]
+
+    // Determinants and inverses. A singular matrix has determinant 0 rather than an error,
+    // but no inverse; a non-square one has neither.
+    let known = vec![vec![6, 1, 1], vec![4, -2, 5], vec![2, 8, 7]];
+    let known_f64: Vec<Vec<f64>> =
+        known.iter().map(|row| row.iter().map(|&x| x as f64).collect()).collect();
+    println!("{} {}", determinant_i64(&known)?, determinant(&known_f64)?); // Output: -306 -306
+    if let Err(err) = determinant(&[vec![1.0, 2.0]]) {
+        println!("Rejected: {}", err); // Output: Rejected: expected a square matrix, found 1x2
+    }
+    println!("{:?}", invert(&[vec![4.0]])?); // Output: [[0.25]]
+    if let Err(err) = invert(&[vec![1.0, 2.0], vec![2.0, 4.0]]) {
+        println!("Rejected: {}", err); // Output: Rejected: the matrix is singular
+    }
+
+    // A ragged matrix is rejected up front, naming the first row whose length differs.
+    let ragged = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8], vec![9, 10, 11]];
+    if let Err(err) = validate_matrix(&ragged) {
+        println!("Rejected: {}", err); // Output: Rejected: ragged matrix: row 2 has 2 elements, but row 0 has 3
+    }
+    // The flat and sparse constructors have fallible versions for input that may be ragged.
+    println!("{}", Matrix::try_from_nested(ragged.clone()).is_err()); // Output: true
+    println!("{}", CsrMatrix::try_from_dense(&ragged).is_err()); // Output: true
+
+    // Sparse matrices keep only their non-zero cells.
+    let tiny = CsrMatrix::from_dense(&[vec![0, 2, 0], vec![0, 0, 0], vec![1, 0, 3]]);
+    println!("{:?}", tiny); // Output: CsrMatrix { values: [2, 1, 3], col_indices: [1, 0, 2], row_ptr: [0, 1, 1, 3], cols: 3 }
+    let csr = CsrMatrix::from_dense(&gen_sparse_matrix(120, 80, 90, 5));
+    println!("{} of {} cells stored", csr.nnz(), csr.rows() * csr.cols());
+    let dense_b = gen_matrix(80, 60, 6);
+    let sparse_product = multiply_csr_dense(&csr, &dense_b)?;
+    println!("{}", multiply_csr_dense_parallel(&csr, &dense_b, threads)? == sparse_product); // Output: true
+    println!("{}", csr.to_dense() == gen_sparse_matrix(120, 80, 90, 5)); // Output: true
+
+    // Matrix x vector, dot products and sums. The parallel versions add up the same numbers
+    // in a different order, which for integers gives exactly the same result.
+    let v = vec![3, 1, 4];
+    let m = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 10]];
+    println!("{:?} {:?}", multiply_vec(&m, &v)?, multiply_vec_parallel(&m, &v, threads)?); // Output: [17, 41, 69] [17, 41, 69]
+    println!("{} {} {}", dot(&[1, 2, 3], &[4, 5, 6])?, dot_parallel(&v, &v, threads)?, dot_unrolled(&v, &v)); // Output: 32 26 26
+    println!("{:?} {:?}", row_sums(&m)?, col_sums(&m)?); // Output: [6, 15, 25] [12, 15, 19]
+    println!("{:?} {:?}", row_sums_parallel(&m, threads)?, col_sums_parallel(&m, threads)?); // Output: [6, 15, 25] [12, 15, 19]
+    println!("{:?}", matrix_stats(&m, 2)); // Output: Some(MatrixStats { sum: 46, min: 1, max: 10 })
+
+    // Every implementation side by side: min, median and mean of 3 runs per size.
+    print_bench_table(&bench_multiply(&[50, 100, 200], 3, threads)?);
+    // The dot product kernels alone, on a million elements.
+    print_bench_table(&bench_dot(1_000_000, 5));
+
+    // Speedup and efficiency of the parallel implementations on a 200x200 product.
+    let parallel: Vec<_> = NESTED_VARIANTS
+        .iter()
+        .copied()
+        .filter(|(name, _)| ["pooled", "chunked", "mpsc", "mutex"].contains(name))
+        .collect();
+    let report = measure_speedup(&big_a, &big_b, threads, NESTED_VARIANTS[0], &parallel)?;
+    print!("{}", report.to_table());
+    Ok(())
 }
 
-fn multiply(matrix_a: Vec<Vec<u64>>, matrix_b: Vec<Vec<u64>>) -> Vec<Vec<u64>> {
-    let row_lena = matrix_a.len();
-    let col_lena = matrix_a[0].len();
-    let row_lenb = matrix_b.len();
-    if col_lena != row_lenb {
-        panic!("Incompatible multiplication");
+// Multiply two seeded random square matrices with the implementation named by `--impl`,
+// and print how long it took, the corners of the result and its checksum. An unknown name
+// prints the usage and exits with an error.
+fn run_single(config: &Config, threads: usize) -> Result<(), MatMulError> {
+    let requested = config.implementation.as_deref().unwrap_or("sequential");
+    let name = requested.replace('-', " ");
+    let Some(&(name, multiply)) = NESTED_VARIANTS.iter().find(|(variant, _)| *variant == name) else {
+        eprintln!("Error: unknown implementation {:?}\n\n{}", requested, usage());
+        process::exit(2);
+    };
+    let (size, seed) = (config.size.unwrap_or(100), config.seed.unwrap_or(1));
+    let (matrix_a, matrix_b) = random_pair((size, size, size), seed);
+    let start = Instant::now();
+    let result = multiply(&matrix_a, &matrix_b, threads)?;
+    let duration = start.elapsed();
+    println!("{}, {} thread(s), seed {}: {:?}", name, threads, seed, duration);
+    println!("{}", format_matrix(&result, 6, 6));
+    println!("checksum: {}", checksum(&result));
+    Ok(())
+}
+
+// Load A and B from CSV files and print A x B, one row per line, computed with `threads`
+// threads. A file that can't be loaded ends the program with an error message and a
+// non-zero exit code.
+fn multiply_files(path_a: &Path, path_b: &Path, threads: usize) -> Result<(), MatMulError> {
+    let load = |path: &Path| {
+        load_matrix_csv(path).unwrap_or_else(|err| {
+            eprintln!("Error: {}", err);
+            process::exit(1);
+        })
+    };
+    let (matrix_a, matrix_b) = (load(path_a), load(path_b));
+    let (matrix_a, matrix_b) = (Matrix::from_nested(matrix_a), Matrix::from_nested(matrix_b));
+    for row in multiply_chunked(&matrix_a, &matrix_b, threads)?.to_nested() {
+        println!("{:?}", row);
     }
-    let col_lenb = matrix_b[0].len();
+    Ok(())
+}
+
+// Save `result` and a summary of it into `dir`, print the summary, and load the result back
+// before removing the directory again.
+fn save_and_reload(dir: &Path, result: &[Vec<u64>], duration: Duration) -> io::Result<Vec<Vec<u64>>> {
+    fs::create_dir_all(dir)?;
+    let (csv_path, summary_path) = (dir.join("result.csv"), dir.join("summary.txt"));
+    let dims = (result.len(), result.first().map_or(0, Vec::len));
+    save_matrix_csv(result, &csv_path)?;
+    save_summary(&summary_path, dims, duration, checksum(result))?;
+    print!("{}", fs::read_to_string(&summary_path)?);
+    let reloaded = load_matrix_csv(&csv_path).map_err(io::Error::other)?;
+    fs::remove_dir_all(dir)?;
+    Ok(reloaded)
+}
+
+// The original algorithm on `Vec<Vec<u64>>`, kept as the reference the flat versions are
+// checked and timed against.
+fn multiply_nested(matrix_a: &[Vec<u64>], matrix_b: &[Vec<u64>]) -> Result<Vec<Vec<u64>>, MatMulError> {
+    // `validate_matrix` instead of `[0].len()`, so an empty or ragged matrix is an error
+    // rather than a panic.
+    let (row_lena, col_lena) = validate_matrix(matrix_a)?;
+    let (row_lenb, col_lenb) = validate_matrix(matrix_b)?;
+    check_dimensions((row_lena, col_lena), (row_lenb, col_lenb))?;
     let mut ans = vec![vec![0; col_lenb]; row_lena];
-    for row in 0..row_lena {
-        for col in 0..col_lenb {
+    for (row, ans_row) in ans.iter_mut().enumerate() {
+        for (col, cell) in ans_row.iter_mut().enumerate() {
             for k in 0..col_lena {
-                ans[row][col] += matrix_a[row][k] * matrix_b[k][col];
+                *cell += matrix_a[row][k] * matrix_b[k][col];
             }
         }
     }
-    ans
+    Ok(ans)
 }
 
-fn multiply_parallel(matrix_a: Vec<Vec<u64>>, matrix_b: Vec<Vec<u64>>) -> Vec<Vec<u64>> {
-    let row_lena = matrix_a.len();
-    let col_lena = matrix_a[0].len();
-    let row_lenb = matrix_b.len();
-    if col_lena != row_lenb {
-        panic!("Incompatible multiplication");
-    }
-    let col_lenb = matrix_b[0].len();
-    let mut ans = vec![vec![0; col_lenb]; row_lena];
+// The nested API on borrowed matrices, so the caller keeps them. `multiply` is the
+// sequential kernel on nested rows.
+fn multiply(matrix_a: &[Vec<u64>], matrix_b: &[Vec<u64>]) -> Result<Vec<Vec<u64>>, MatMulError> {
+    multiply_generic(matrix_a, matrix_b)
+}
+
+// One thread per row of the result, as before. The threads only borrow the inputs, which
+// `thread::spawn` doesn't allow (the caller could drop them while a thread still runs), so
+// they are scoped threads: `thread::scope` waits for all of them before returning. Like
+// `multiply_generic`, it works for any `Numeric` type, here one the threads can share.
+fn multiply_parallel<T: Numeric + Send + Sync>(
+    matrix_a: &[Vec<T>],
+    matrix_b: &[Vec<T>],
+) -> Result<Vec<Vec<T>>, MatMulError> {
+    let (a_shape, b_shape) = (validate_matrix(matrix_a)?, validate_matrix(matrix_b)?);
+    check_dimensions(a_shape, b_shape)?;
+    let mut ans = vec![vec![T::default(); b_shape.1]; a_shape.0];
+    thread::scope(|scope| {
+        for (a_row, ans_row) in matrix_a.iter().zip(&mut ans) {
+            scope.spawn(move || multiply_nested_row(a_row, matrix_b, ans_row));
+        }
+    });
+    Ok(ans)
+}
+
+// The previous version of `multiply_matrix_parallel`, kept for comparison: every thread
+// gets its own copy of its row of A and of the whole of B, so the copying grows with
+// rows x size of B.
+fn multiply_matrix_parallel_cloning(matrix_a: &Matrix, matrix_b: &Matrix) -> Result<Matrix, MatMulError> {
+    check_dimensions(matrix_a.shape(), matrix_b.shape())?;
+    let matrix_a = &matrix_a.row_major();
+    let mut ans = Matrix::new(matrix_a.rows(), matrix_b.cols());
     let mut handles = vec![];
-    for row in 0..row_lena {
-        let matrix_a_row = matrix_a[row].clone();
+    for row in 0..matrix_a.rows() {
+        let matrix_a_row = matrix_a.row(row).to_vec();
         let matrix_b_clone = matrix_b.clone();
         let handle = thread::spawn(move || {
-            let mut result_row = vec![0; matrix_b_clone[0].len()];
-            for col in 0..matrix_b_clone[0].len() {
-                for k in 0..matrix_a_row.len() {
-                    result_row[col] += matrix_a_row[k] * matrix_b_clone[k][col];
-                }
-            }
+            let mut result_row = vec![0; matrix_b_clone.cols()];
+            multiply_row(&matrix_a_row, &matrix_b_clone, &mut result_row);
             result_row
         });
         handles.push(handle);
@@ -74,7 +489,77 @@ fn multiply_parallel(matrix_a: Vec<Vec<u64>>, matrix_b: Vec<Vec<u64>>) -> Vec<Ve
 
     // Collect results from all threads
     for (i, handle) in handles.into_iter().enumerate() {
-        ans[i] = handle.join().unwrap();
+        ans.row_mut(i).copy_from_slice(&handle.join().unwrap());
+    }
+    Ok(ans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify::{assert_same_result, random_pair, SHAPES};
+
+    #[test]
+    fn every_nested_version_agrees() -> Result<(), MatMulError> {
+        let (a, b) = random_pair((30, 20, 10), 1);
+        let expected = multiply_nested(&a, &b)?;
+        assert_eq!(multiply_generic(&a, &b)?, expected);
+        assert_eq!(multiply(&a, &b)?, expected);
+        assert_eq!(multiply_parallel(&a, &b)?, expected);
+        let (flat_a, flat_b) = (Matrix::from_nested(a), Matrix::from_nested(b));
+        assert_eq!(multiply_matrix(&flat_a, &flat_b)?.to_nested(), expected);
+        let cloned = multiply_matrix_parallel_cloning(&flat_a, &flat_b)?;
+        assert_eq!(cloned, multiply_matrix_parallel(&Arc::new(flat_a), &Arc::new(flat_b))?);
+        assert_eq!(cloned.to_nested(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn multiply_parallel_agrees_on_every_shape() -> Result<(), MatMulError> {
+        for (seed, &shape) in SHAPES.iter().enumerate() {
+            let (a, b) = random_pair(shape, seed as u64 + 100);
+            let expected = multiply_generic(&a, &b)?;
+            assert_same_result("multiply_parallel", multiply_parallel(&a, &b), &expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn known_product() -> Result<(), MatMulError> {
+        let a = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        let b = vec![vec![7, 8], vec![9, 10], vec![11, 12]];
+        assert_eq!(multiply(&a, &b)?, vec![vec![58, 64], vec![139, 154]]);
+        let signed = vec![vec![1i64, -2], vec![-3, 4]];
+        assert_eq!(multiply_parallel(&signed, &signed)?, vec![vec![7, -10], vec![-15, 22]]);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_input_is_an_error() {
+        let two_by_three = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        let mismatched = MatMulError::DimensionMismatch { a_cols: 3, b_rows: 2 };
+        assert_eq!(multiply(&two_by_three, &two_by_three), Err(mismatched.clone()));
+        assert_eq!(multiply_parallel(&two_by_three, &two_by_three), Err(mismatched.clone()));
+        assert_eq!(multiply_nested(&two_by_three, &two_by_three), Err(mismatched));
+        for (a, b) in [(vec![], two_by_three.clone()), (two_by_three.clone(), vec![vec![], vec![], vec![]])] {
+            assert_eq!(multiply(&a, &b), Err(MatMulError::EmptyMatrix));
+            assert_eq!(multiply_parallel(&a, &b), Err(MatMulError::EmptyMatrix));
+            assert_eq!(multiply_nested(&a, &b), Err(MatMulError::EmptyMatrix));
+        }
+        let ragged = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8], vec![9, 10, 11]];
+        let ragged_err = MatMulError::Ragged { row: 2, expected: 3, found: 2 };
+        let square_3 = Matrix::identity(3).to_nested();
+        assert_eq!(multiply(&ragged, &square_3), Err(ragged_err.clone()));
+        assert_eq!(multiply_parallel(&ragged, &square_3), Err(ragged_err.clone()));
+        assert_eq!(multiply_nested(&ragged, &square_3), Err(ragged_err));
+    }
+
+    #[test]
+    fn save_and_reload_gives_back_the_same_matrix() -> io::Result<()> {
+        let dir = env::temp_dir().join(format!("multithreading-main-{}", process::id()));
+        let result = gen_matrix(10, 10, 1);
+        assert_eq!(save_and_reload(&dir, &result, Duration::from_millis(1))?, result);
+        assert!(!dir.exists());
+        Ok(())
     }
-    ans
 }
@@ -0,0 +1,341 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::thread;
+
+use crate::error::MatMulError;
+use crate::generic::{validate_matrix, Numeric};
+use crate::reduce::dot_unrolled;
+
+// The order the numbers of a `Matrix` are stored in. Row-major keeps each row together,
+// column-major each column. Multiplying reads a row of A against a column of B, so
+// storing B column-major makes both contiguous without transposing anything.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Layout {
+    #[default]
+    RowMajor,
+    ColMajor,
+}
+
+// A matrix stored as one flat `Vec<T>`. In row-major order row `r` occupies
+// `data[r * cols..(r + 1) * cols]`; in column-major order column `c` occupies
+// `data[c * rows..(c + 1) * rows]`. Compared to `Vec<Vec<T>>`, all the numbers sit in a
+// single allocation, so walking through them doesn't jump around the heap. Two matrices
+// with the same numbers in different layouts don't compare equal. The element type
+// defaults to `u64`, which is what most of this crate multiplies.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix<T = u64> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+    layout: Layout,
+}
+
+impl Matrix {
+    // The `n` x `n` identity: ones on the diagonal, zeros everywhere else. Multiplying by it
+    // on either side leaves a matrix unchanged. `Numeric` has no "one", so this is `u64`
+    // only.
+    pub fn identity(n: usize) -> Matrix {
+        let mut identity = Matrix::new(n, n);
+        for i in 0..n {
+            identity.set(i, i, 1);
+        }
+        identity
+    }
+}
+
+impl<T: Numeric> Matrix<T> {
+    // A `rows` x `cols` matrix filled with zeros (`T::default()`).
+    pub fn new(rows: usize, cols: usize) -> Matrix<T> {
+        Matrix {
+            rows,
+            cols,
+            data: vec![T::default(); rows * cols],
+            layout: Layout::RowMajor,
+        }
+    }
+
+    // Copy a nested matrix into flat storage. A ragged matrix is `MatMulError::Ragged`.
+    pub fn try_from_nested(nested: Vec<Vec<T>>) -> Result<Matrix<T>, MatMulError> {
+        let (rows, cols) = validate_matrix(&nested)?;
+        let data = nested.into_iter().flatten().collect();
+        Ok(Matrix { rows, cols, data, layout: Layout::RowMajor })
+    }
+
+    // `try_from_nested` for input that is known to be rectangular. Panics on a ragged
+    // matrix.
+    pub fn from_nested(nested: Vec<Vec<T>>) -> Matrix<T> {
+        Matrix::try_from_nested(nested).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    // Wrap row-major data that is already laid out flat. `data` must hold exactly
+    // `rows * cols` values.
+    pub fn from_flat(rows: usize, cols: usize, data: Vec<T>) -> Matrix<T> {
+        assert_eq!(
+            data.len(),
+            rows * cols,
+            "a {}x{} matrix needs {} values",
+            rows,
+            cols,
+            rows * cols
+        );
+        Matrix { rows, cols, data, layout: Layout::RowMajor }
+    }
+
+    // Convert back to the nested representation used by the original functions.
+    pub fn to_nested(&self) -> Vec<Vec<T>> {
+        let matrix = self.row_major();
+        (0..self.rows).map(|row| matrix.row(row).to_vec()).collect()
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    // A copy of this matrix stored in `layout`. The numbers are the same, so `get` gives
+    // the same value for every index.
+    pub fn to_layout(&self, layout: Layout) -> Matrix<T> {
+        let mut converted = Matrix { layout, ..Matrix::new(self.rows, self.cols) };
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                converted.set(row, col, self.get(row, col));
+            }
+        }
+        converted
+    }
+
+    // This matrix if it is row-major already, otherwise a row-major copy. The kernels call
+    // this on A so that they can read it row by row, whatever its layout.
+    pub(crate) fn row_major(&self) -> Cow<'_, Matrix<T>> {
+        match self.layout {
+            Layout::RowMajor => Cow::Borrowed(self),
+            Layout::ColMajor => Cow::Owned(self.to_layout(Layout::RowMajor)),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    // `(rows, cols)`.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> T {
+        self.data[self.index(row, col)]
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: T) {
+        let index = self.index(row, col);
+        self.data[index] = value;
+    }
+
+    // One row as a slice, without copying. Only a row-major matrix has its rows in one
+    // piece.
+    pub fn row(&self, row: usize) -> &[T] {
+        assert_eq!(self.layout, Layout::RowMajor, "row slices need a row-major matrix");
+        &self.data[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        assert_eq!(self.layout, Layout::RowMajor, "row slices need a row-major matrix");
+        &mut self.data[row * self.cols..(row + 1) * self.cols]
+    }
+
+    // One column as a slice, for a column-major matrix.
+    pub fn col(&self, col: usize) -> &[T] {
+        assert_eq!(self.layout, Layout::ColMajor, "column slices need a column-major matrix");
+        &self.data[col * self.rows..(col + 1) * self.rows]
+    }
+
+    // The position of an element in `data`. Checking the column as well as the row catches
+    // indices that would otherwise silently wrap into the next row.
+    fn index(&self, row: usize, col: usize) -> usize {
+        assert!(
+            row < self.rows && col < self.cols,
+            "index ({}, {}) out of bounds for a {}x{} matrix",
+            row,
+            col,
+            self.rows,
+            self.cols
+        );
+        match self.layout {
+            Layout::RowMajor => row * self.cols + col,
+            Layout::ColMajor => col * self.rows + row,
+        }
+    }
+}
+
+// The checks shared by every multiply function, given the (rows, cols) of A and B.
+pub(crate) fn check_dimensions(a: (usize, usize), b: (usize, usize)) -> Result<(), MatMulError> {
+    if a.0 == 0 || a.1 == 0 || b.0 == 0 || b.1 == 0 {
+        return Err(MatMulError::EmptyMatrix);
+    }
+    if a.1 != b.0 {
+        return Err(MatMulError::DimensionMismatch { a_cols: a.1, b_rows: b.0 });
+    }
+    Ok(())
+}
+
+// Compute one row of A x B into `out`, which must start out as zeros. Every multiply
+// function on `Matrix` is built from this; they only differ in which thread computes which
+// rows. For a row-major B the loops run k, col rather than col, k: each element of A's row
+// scales a whole row of B into `out`, so both are read front to back instead of striding
+// down a column of B. A column-major B has its columns in one piece, so there every cell
+// is simply the dot product of A's row with a column.
+pub(crate) fn multiply_row<T: Numeric>(a_row: &[T], matrix_b: &Matrix<T>, out: &mut [T]) {
+    match matrix_b.layout() {
+        Layout::RowMajor => {
+            for (k, &a) in a_row.iter().enumerate() {
+                for (cell, &b) in out.iter_mut().zip(matrix_b.row(k)) {
+                    *cell = *cell + a * b;
+                }
+            }
+        }
+        Layout::ColMajor => {
+            for (col, cell) in out.iter_mut().enumerate() {
+                *cell = dot_unrolled(a_row, matrix_b.col(col));
+            }
+        }
+    }
+}
+
+// Sequential multiplication on flat storage, one row of the result after another.
+pub fn multiply_matrix<T: Numeric>(
+    matrix_a: &Matrix<T>,
+    matrix_b: &Matrix<T>,
+) -> Result<Matrix<T>, MatMulError> {
+    check_dimensions(matrix_a.shape(), matrix_b.shape())?;
+    let matrix_a = &matrix_a.row_major();
+    let mut ans = Matrix::new(matrix_a.rows(), matrix_b.cols());
+    for row in 0..matrix_a.rows() {
+        multiply_row(matrix_a.row(row), matrix_b, ans.row_mut(row));
+    }
+    Ok(ans)
+}
+
+// One thread per row of the result. `thread::spawn` needs `'static` data the thread owns,
+// so the inputs come in `Arc`s and every thread gets a clone of them: a pointer and a
+// reference count increment, not a copy of the matrices. The caller keeps its own `Arc`s
+// to use again, so nothing is copied per call either, except A when it has to be turned
+// row-major first. Each thread reads its row of A directly through the `Arc` by index.
+pub fn multiply_matrix_parallel(
+    matrix_a: &Arc<Matrix>,
+    matrix_b: &Arc<Matrix>,
+) -> Result<Matrix, MatMulError> {
+    check_dimensions(matrix_a.shape(), matrix_b.shape())?;
+    let mut ans = Matrix::new(matrix_a.rows(), matrix_b.cols());
+    let shared_a = match matrix_a.layout() {
+        Layout::RowMajor => Arc::clone(matrix_a),
+        Layout::ColMajor => Arc::new(matrix_a.to_layout(Layout::RowMajor)),
+    };
+    let mut handles = vec![];
+    for row in 0..matrix_a.rows() {
+        let matrix_a = Arc::clone(&shared_a);
+        let matrix_b = Arc::clone(matrix_b);
+        let handle = thread::spawn(move || {
+            let mut result_row = vec![0; matrix_b.cols()];
+            multiply_row(matrix_a.row(row), &matrix_b, &mut result_row);
+            result_row
+        });
+        handles.push(handle);
+    }
+
+    // Collect results from all threads
+    for (i, handle) in handles.into_iter().enumerate() {
+        ans.row_mut(i).copy_from_slice(&handle.join().unwrap());
+    }
+    Ok(ans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::gen_matrix;
+
+    #[test]
+    fn nested_round_trip_and_element_access() {
+        let nested = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9], vec![10, 11, 12]];
+        let mut flat = Matrix::from_nested(nested.clone());
+        assert_eq!(flat.to_nested(), nested);
+        assert_eq!(flat.get(3, 2), 12);
+        flat.set(3, 2, 0);
+        assert_eq!(flat.get(3, 2), 0);
+        assert_eq!(flat.row(1), [4, 5, 6]);
+        assert_eq!(Matrix::from_flat(2, 2, vec![1, 2, 3, 4]).row(1), [3, 4]);
+        assert_eq!(Matrix::<u64>::from_nested(vec![vec![], vec![]]).to_nested(), vec![Vec::<u64>::new(); 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "ragged matrix: row 1 has 2 elements")]
+    fn ragged_input_is_an_error_or_a_panic() {
+        let ragged = vec![vec![1, 2, 3], vec![4, 5]];
+        let ragged_err = MatMulError::Ragged { row: 1, expected: 3, found: 2 };
+        assert_eq!(Matrix::try_from_nested(ragged.clone()), Err(ragged_err));
+        Matrix::from_nested(ragged);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn a_column_past_the_end_does_not_wrap() {
+        Matrix::<u64>::new(2, 2).get(0, 2);
+    }
+
+    #[test]
+    fn layouts_keep_the_numbers() {
+        let flat = Matrix::from_nested(gen_matrix(5, 7, 2));
+        let col_major = flat.to_layout(Layout::ColMajor);
+        assert_eq!(col_major.layout(), Layout::ColMajor);
+        assert_ne!(col_major, flat);
+        assert_eq!(col_major.to_layout(Layout::RowMajor), flat);
+        assert_eq!(flat.to_layout(Layout::RowMajor), flat);
+        assert_eq!(col_major.get(3, 6), flat.get(3, 6));
+        assert_eq!(col_major.to_nested(), flat.to_nested());
+        let mut tall = Matrix::from_nested(vec![vec![1, 2], vec![3, 4], vec![5, 6]]).to_layout(Layout::ColMajor);
+        assert_eq!(tall.col(1), [2, 4, 6]);
+        tall.set(2, 0, 50);
+        assert_eq!(tall.to_nested(), vec![vec![1, 2], vec![3, 4], vec![50, 6]]);
+    }
+
+    #[test]
+    fn every_layout_combination_gives_the_same_product() -> Result<(), MatMulError> {
+        let a = Matrix::from_nested(gen_matrix(6, 4, 3));
+        let b = Matrix::from_nested(gen_matrix(4, 5, 4));
+        let expected = multiply_matrix(&a, &b)?;
+        for layout_a in [Layout::RowMajor, Layout::ColMajor] {
+            for layout_b in [Layout::RowMajor, Layout::ColMajor] {
+                let (a, b) = (a.to_layout(layout_a), b.to_layout(layout_b));
+                assert_eq!(multiply_matrix(&a, &b)?, expected);
+                assert_eq!(multiply_matrix_parallel(&Arc::new(a), &Arc::new(b))?, expected);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn known_products_and_the_identity() -> Result<(), MatMulError> {
+        let a = Matrix::from_nested(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let b = Matrix::from_nested(vec![vec![7, 8], vec![9, 10], vec![11, 12]]);
+        let expected = vec![vec![58, 64], vec![139, 154]];
+        assert_eq!(multiply_matrix(&a, &b)?.to_nested(), expected);
+        assert_eq!(multiply_matrix(&Matrix::identity(2), &a)?, a);
+        assert_eq!(multiply_matrix(&a, &Matrix::identity(3))?, a);
+        assert_eq!(multiply_matrix_parallel(&Arc::new(a), &Arc::new(b))?.to_nested(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn bad_shapes_are_errors() {
+        let a = Matrix::from_nested(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let mismatched = MatMulError::DimensionMismatch { a_cols: 3, b_rows: 2 };
+        assert_eq!(multiply_matrix(&a, &a), Err(mismatched.clone()));
+        let shared = Arc::new(a.clone());
+        assert_eq!(multiply_matrix_parallel(&shared, &shared), Err(mismatched));
+        assert_eq!(multiply_matrix(&Matrix::new(0, 3), &a), Err(MatMulError::EmptyMatrix));
+        assert_eq!(check_dimensions((2, 0), (0, 2)), Err(MatMulError::EmptyMatrix));
+    }
+}
@@ -0,0 +1,76 @@
+// Every worker writes its finished rows into one shared output behind a `Mutex`, instead of
+// handing them back through `join` or a channel. It's here to compare against the designs
+// that share nothing mutable: each row costs one lock, and workers that finish a row
+// at the same time wait for each other.
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use crate::error::MatMulError;
+use crate::generic::Numeric;
+use crate::matrix::{check_dimensions, multiply_row, Matrix};
+
+// Multiply with `workers` threads (0 counts as 1, and there are never more than rows),
+// taking rows from a shared counter like `multiply_pooled`. Generic like it, too.
+pub fn multiply_mutex<T: Numeric + Send + Sync>(
+    matrix_a: &Matrix<T>,
+    matrix_b: &Matrix<T>,
+    workers: usize,
+) -> Result<Matrix<T>, MatMulError> {
+    check_dimensions(matrix_a.shape(), matrix_b.shape())?;
+    let rows = matrix_a.rows();
+    // Scoped threads, so the workers borrow the inputs, the counter and the output.
+    let matrix_a = &matrix_a.row_major();
+    let next_row = AtomicUsize::new(0);
+    let output = Mutex::new(vec![Vec::new(); rows]);
+
+    thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, rows) {
+            let (next_row, output) = (&next_row, &output);
+            scope.spawn(move || loop {
+                let row = next_row.fetch_add(1, Ordering::Relaxed);
+                if row >= rows {
+                    break;
+                }
+                // The row is computed without the lock; it is only taken to store the row,
+                // and released again at the end of the statement.
+                let mut result_row = vec![T::default(); matrix_b.cols()];
+                multiply_row(matrix_a.row(row), matrix_b, &mut result_row);
+                output.lock().unwrap()[row] = result_row;
+            });
+        }
+    });
+
+    // Every worker has finished when the scope ends, so the lock is free.
+    Ok(Matrix::from_nested(output.into_inner().unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::multiply_matrix;
+    use crate::random::gen_matrix;
+
+    #[test]
+    fn any_worker_count_gives_the_same_product() -> Result<(), MatMulError> {
+        let a = Matrix::from_nested(gen_matrix(40, 30, 1));
+        let b = Matrix::from_nested(gen_matrix(30, 20, 2));
+        let expected = multiply_matrix(&a, &b)?;
+        for workers in [0, 1, 4, 64] {
+            assert_eq!(multiply_mutex(&a, &b, workers)?, expected);
+        }
+        let row_vector = Matrix::from_nested(vec![vec![1, 2, 3]]);
+        let column_vector = Matrix::from_nested(vec![vec![4], vec![5], vec![6]]);
+        assert_eq!(multiply_mutex(&row_vector, &column_vector, 8)?.to_nested(), vec![vec![32]]);
+        Ok(())
+    }
+
+    #[test]
+    fn works_for_other_numeric_types() -> Result<(), MatMulError> {
+        let signed = Matrix::from_nested(vec![vec![1i64, -2], vec![-3, 4]]);
+        assert_eq!(multiply_mutex(&signed, &signed, 2)?.to_nested(), vec![vec![7, -10], vec![-15, 22]]);
+        let float = Matrix::from_nested(vec![vec![0.5, 2.0], vec![1.5, -1.0]]);
+        assert_eq!(multiply_mutex(&float, &float, 2)?.to_nested(), vec![vec![3.25, -1.0], vec![-0.75, 4.0]]);
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+// A fixed number of worker threads sharing the rows of the result between them, instead
+// of one thread per row.
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use std::thread;
+
+use crate::error::MatMulError;
+use crate::generic::Numeric;
+use crate::matrix::{check_dimensions, multiply_row, Matrix};
+
+// As many threads as the machine can run at once, or 1 if that can't be determined.
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+// Multiply with at most `num_threads` workers (0 counts as 1, and there are never more
+// workers than rows). Each worker repeatedly takes the next unclaimed row index from a
+// shared counter, so a worker that finishes early simply takes more rows. Every row of the
+// result has its own slot, a `OnceLock`, that exactly one worker fills. Any `Numeric`
+// type works, as long as the workers can share it.
+pub fn multiply_pooled<T: Numeric + Send + Sync>(
+    matrix_a: &Matrix<T>,
+    matrix_b: &Matrix<T>,
+    num_threads: usize,
+) -> Result<Matrix<T>, MatMulError> {
+    check_dimensions(matrix_a.shape(), matrix_b.shape())?;
+    let rows = matrix_a.rows();
+    let workers = num_threads.clamp(1, rows);
+    // The workers are scoped threads, so they can borrow the inputs, the counter and the
+    // slots instead of needing `Arc`ed copies of them.
+    let matrix_a = &matrix_a.row_major();
+    let next_row = AtomicUsize::new(0);
+    let slots: Vec<OnceLock<Vec<T>>> = (0..rows).map(|_| OnceLock::new()).collect();
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let (next_row, slots) = (&next_row, &slots);
+            scope.spawn(move || loop {
+                // `fetch_add` hands out every index exactly once, whichever worker asks.
+                let row = next_row.fetch_add(1, Ordering::Relaxed);
+                if row >= rows {
+                    break;
+                }
+                let mut result_row = vec![T::default(); matrix_b.cols()];
+                multiply_row(matrix_a.row(row), matrix_b, &mut result_row);
+                assert!(slots[row].set(result_row).is_ok(), "each row is computed once");
+            });
+        }
+    });
+
+    let mut ans = Matrix::new(rows, matrix_b.cols());
+    for (row, slot) in slots.iter().enumerate() {
+        ans.row_mut(row).copy_from_slice(slot.get().expect("every row was computed"));
+    }
+    Ok(ans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::multiply_matrix;
+
+    #[test]
+    fn any_pool_size_gives_the_same_product() -> Result<(), MatMulError> {
+        let a = Matrix::from_nested((0..40).map(|i| (0..30).map(|j| (i * 7 + j) % 11).collect()).collect());
+        let b = Matrix::from_nested((0..30).map(|i| (0..20).map(|j| (i + j * 3) % 13).collect()).collect());
+        let expected = multiply_matrix(&a, &b)?;
+        // 0 still uses one worker, and more workers than rows is capped at one per row.
+        for threads in [0, 1, 2, 4, default_threads(), 64] {
+            assert_eq!(multiply_pooled(&a, &b, threads)?, expected);
+        }
+        assert!(default_threads() >= 1);
+        Ok(())
+    }
+
+    #[test]
+    fn works_for_other_numeric_types() -> Result<(), MatMulError> {
+        let signed = Matrix::from_nested(vec![vec![1i64, -2], vec![-3, 4]]);
+        assert_eq!(multiply_pooled(&signed, &signed, 2)?.to_nested(), vec![vec![7, -10], vec![-15, 22]]);
+        let float = Matrix::from_nested(vec![vec![0.5, 2.0], vec![1.5, -1.0]]);
+        assert_eq!(multiply_pooled(&float, &float, 2)?.to_nested(), vec![vec![3.25, -1.0], vec![-0.75, 4.0]]);
+        Ok(())
+    }
+}
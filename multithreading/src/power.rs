@@ -0,0 +1,65 @@
+// Raising a square matrix to an integer power by repeated squaring. M^13 is
+// M^8 x M^4 x M^1 (13 is 1101 in binary), and M^2, M^4, M^8 each take one squaring, so
+// only O(log exp) multiplications are needed instead of exp - 1.
+use crate::error::MatMulError;
+use crate::matrix::{multiply_matrix, Matrix};
+
+// `matrix` to the power `exp`. `exp = 0` gives the identity of the same size, except for a
+// 0x0 matrix, which is `EmptyMatrix` like any other empty input rather than a 0x0
+// identity. The entries grow quickly with the exponent, and like `multiply` nothing checks
+// them for overflow.
+pub fn matrix_pow(matrix: &Matrix, exp: u32) -> Result<Matrix, MatMulError> {
+    let (rows, cols) = matrix.shape();
+    if rows != cols {
+        return Err(MatMulError::NotSquare { rows, cols });
+    }
+    if rows == 0 {
+        return Err(MatMulError::EmptyMatrix);
+    }
+    let mut result = Matrix::identity(rows);
+    let mut base = matrix.clone();
+    let mut exp = exp;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = multiply_matrix(&result, &base)?;
+        }
+        exp >>= 1;
+        // The last squaring would never be used.
+        if exp > 0 {
+            base = multiply_matrix(&base, &base)?;
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn powers_of_the_fibonacci_matrix() -> Result<(), MatMulError> {
+        // [[1, 1], [1, 0]]^n is [[fib(n + 1), fib(n)], [fib(n), fib(n - 1)]].
+        let fib_matrix = Matrix::from_nested(vec![vec![1, 1], vec![1, 0]]);
+        assert_eq!(matrix_pow(&fib_matrix, 30)?.get(0, 1), 832_040);
+        assert_eq!(matrix_pow(&fib_matrix, 0)?, Matrix::identity(2));
+        assert_eq!(matrix_pow(&fib_matrix, 1)?, fib_matrix);
+        Ok(())
+    }
+
+    #[test]
+    fn matches_repeated_multiplication() -> Result<(), MatMulError> {
+        let square = Matrix::from_nested(vec![vec![1, 2], vec![3, 4]]);
+        let by_hand = multiply_matrix(&multiply_matrix(&square, &square)?, &square)?;
+        assert_eq!(matrix_pow(&square, 3)?, by_hand);
+        Ok(())
+    }
+
+    #[test]
+    fn only_non_empty_square_matrices_have_powers() {
+        let two_by_three = Matrix::from_nested(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let not_square = MatMulError::NotSquare { rows: 2, cols: 3 };
+        assert_eq!(matrix_pow(&two_by_three, 2), Err(not_square.clone()));
+        assert_eq!(matrix_pow(&two_by_three, 0), Err(not_square));
+        assert_eq!(matrix_pow(&Matrix::new(0, 0), 0), Err(MatMulError::EmptyMatrix));
+    }
+}
@@ -0,0 +1,117 @@
+// Progress reporting for long multiplications. The workers only bump a shared counter of
+// finished rows; the calling thread looks at the counter on a timer and passes it on. That
+// keeps the callback off the workers entirely: it can be as slow as it likes (printing,
+// say) without holding up the multiplication.
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use crate::error::MatMulError;
+use crate::matrix::{check_dimensions, multiply_row, Matrix};
+
+// How often the counter is checked.
+const TICK: Duration = Duration::from_millis(10);
+
+// Multiply with `workers` threads (0 counts as 1, and there are never more than rows),
+// calling `report(done, total)` with the number of finished rows whenever it has changed
+// since the last tick. The values passed never decrease, and the last call is always
+// `report(total, total)`, unless a worker panics: then the checking stops as soon as
+// every worker has stopped, and the result is `MatMulError::WorkerPanicked`.
+pub fn multiply_with_progress(
+    matrix_a: &Matrix,
+    matrix_b: &Matrix,
+    workers: usize,
+    report: impl Fn(usize, usize) + Send + Sync,
+) -> Result<Matrix, MatMulError> {
+    check_dimensions(matrix_a.shape(), matrix_b.shape())?;
+    let matrix_a = &matrix_a.row_major();
+    let rows = matrix_a.rows();
+    let (next_row, done) = (AtomicUsize::new(0), AtomicUsize::new(0));
+    let mut ans = Matrix::new(rows, matrix_b.cols());
+    // Each worker wakes the checking thread when it runs out of rows, so the last tick
+    // doesn't have to run out first.
+    let checker = thread::current();
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers.clamp(1, rows))
+            .map(|_| {
+                let (next_row, done, checker) = (&next_row, &done, &checker);
+                scope.spawn(move || {
+                    let mut finished = Vec::new();
+                    loop {
+                        let row = next_row.fetch_add(1, Ordering::Relaxed);
+                        if row >= rows {
+                            checker.unpark();
+                            break finished;
+                        }
+                        let mut result_row = vec![0; matrix_b.cols()];
+                        multiply_row(matrix_a.row(row), matrix_b, &mut result_row);
+                        finished.push((row, result_row));
+                        done.fetch_add(1, Ordering::Relaxed);
+                    }
+                })
+            })
+            .collect();
+
+        // A panicking worker never finishes its rows, so waiting for all of them would wait
+        // forever; waiting for every worker to stop doesn't.
+        let mut reported = 0;
+        loop {
+            let stopped = handles.iter().all(|handle| handle.is_finished());
+            let now = done.load(Ordering::Relaxed);
+            if now > reported {
+                reported = now;
+                report(reported, rows);
+            }
+            if reported == rows || stopped {
+                break;
+            }
+            thread::park_timeout(TICK);
+        }
+        for handle in handles {
+            let finished = handle.join().map_err(|_| MatMulError::WorkerPanicked)?;
+            for (row, result_row) in finished {
+                ans.row_mut(row).copy_from_slice(&result_row);
+            }
+        }
+        Ok(())
+    })?;
+    Ok(ans)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::matrix::multiply_matrix;
+    use crate::random::gen_matrix;
+
+    #[test]
+    fn reports_never_decrease_and_end_at_the_total() -> Result<(), MatMulError> {
+        let a = Matrix::from_nested(gen_matrix(200, 50, 1));
+        let b = Matrix::from_nested(gen_matrix(50, 40, 2));
+        let reports = Mutex::new(Vec::new());
+        let product = multiply_with_progress(&a, &b, 3, |done, total| {
+            reports.lock().unwrap().push((done, total));
+        })?;
+        assert_eq!(product, multiply_matrix(&a, &b)?);
+        let reports = reports.into_inner().unwrap();
+        assert!(reports.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert_eq!(reports.last(), Some(&(200, 200)));
+        Ok(())
+    }
+
+    // Only debug builds check for overflow, which is what makes the worker panic here.
+    #[cfg(debug_assertions)]
+    #[test]
+    fn a_panicking_worker_is_an_error_instead_of_a_hang() {
+        let a = Matrix::from_nested(vec![vec![1], vec![u64::MAX]]);
+        let b = Matrix::from_nested(vec![vec![2]]);
+        let reports = Mutex::new(Vec::new());
+        let product = multiply_with_progress(&a, &b, 2, |done, total| {
+            reports.lock().unwrap().push((done, total));
+        });
+        assert_eq!(product, Err(MatMulError::WorkerPanicked));
+        assert!(reports.into_inner().unwrap().iter().all(|&(done, _)| done < 2));
+    }
+}
@@ -0,0 +1,126 @@
+// Reproducible pseudo-random numbers for generating test and benchmark matrices.
+
+// splitmix64 steps its state by this odd constant, the nearest integer to 2^64 over the
+// golden ratio.
+const SPLITMIX_GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+// A tiny xorshift generator (Marsaglia, 2003): fast and dependency-free, but not suitable
+// for anything security-related. The same seed always gives the same sequence.
+pub struct XorShift64 {
+    state: u64,
+}
+
+impl XorShift64 {
+    // Seeds are run through a single splitmix64 round before use. The round is invertible,
+    // so no two seeds collide, and consecutive seeds land far apart instead of giving
+    // sequences that start out alike. xorshift gets stuck at a zero state, so the one seed
+    // whose round comes out as zero is given `SPLITMIX_GAMMA` as its state instead.
+    pub fn new(seed: u64) -> Self {
+        let mut z = seed.wrapping_add(SPLITMIX_GAMMA);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        let state = z ^ (z >> 31);
+        XorShift64 { state: if state == 0 { SPLITMIX_GAMMA } else { state } }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    // A uniformly distributed value in [0, 1), built from the top 53 bits of the state
+    // (exactly the precision of an `f64` mantissa).
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+// Entries of `gen_matrix` are below this. A product of two entries is then below 10^6, so
+// a result cell can't overflow a `u64` unless the inner dimension is in the trillions.
+pub const MAX_ENTRY: u64 = 1000;
+
+// A `rows` x `cols` matrix of values in [0, MAX_ENTRY), the same for the same seed. Taking
+// the remainder favours small values very slightly, which doesn't matter for test data.
+pub fn gen_matrix(rows: usize, cols: usize, seed: u64) -> Vec<Vec<u64>> {
+    let mut rng = XorShift64::new(seed);
+    (0..rows)
+        .map(|_| (0..cols).map(|_| rng.next_u64() % MAX_ENTRY).collect())
+        .collect()
+}
+
+// Like `gen_matrix`, but with roughly `zeros_percent`% of the cells set to 0.
+pub fn gen_sparse_matrix(
+    rows: usize,
+    cols: usize,
+    zeros_percent: u64,
+    seed: u64,
+) -> Vec<Vec<u64>> {
+    let mut rng = XorShift64::new(seed);
+    (0..rows)
+        .map(|_| {
+            (0..cols)
+                .map(|_| {
+                    let keep = rng.next_u64() % 100 >= zeros_percent;
+                    let value = rng.next_u64() % MAX_ENTRY;
+                    if keep { value } else { 0 }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// A `rows` x `cols` matrix of values in [-1, 1), the same for the same seed.
+pub fn gen_matrix_f64(rows: usize, cols: usize, seed: u64) -> Vec<Vec<f64>> {
+    let mut rng = XorShift64::new(seed);
+    (0..rows)
+        .map(|_| (0..cols).map(|_| 2.0 * rng.next_f64() - 1.0).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_same_seed_gives_the_same_matrix() {
+        let seeded = gen_matrix(50, 40, 42);
+        assert_eq!(gen_matrix(50, 40, 42), seeded);
+        assert_ne!(gen_matrix(50, 40, 43), seeded);
+        assert!(seeded.iter().flatten().all(|&value| value < MAX_ENTRY));
+        assert_eq!((seeded.len(), seeded[0].len()), (50, 40));
+        assert_eq!(gen_matrix_f64(40, 30, 7), gen_matrix_f64(40, 30, 7));
+        assert_ne!(gen_matrix_f64(40, 30, 8), gen_matrix_f64(40, 30, 7));
+    }
+
+    #[test]
+    fn every_seed_gives_its_own_nonzero_state() {
+        // Each of the first 100 seeds starts its own stream, and the seed whose splitmix
+        // round is zero still produces nonzero numbers.
+        let first_values: Vec<u64> = (0..100).map(|seed| XorShift64::new(seed).next_u64()).collect();
+        let mut distinct = first_values.clone();
+        distinct.sort();
+        distinct.dedup();
+        assert_eq!(distinct.len(), first_values.len());
+        let mixes_to_zero = 0u64.wrapping_sub(SPLITMIX_GAMMA);
+        assert_ne!(XorShift64::new(mixes_to_zero).next_u64(), 0);
+    }
+
+    #[test]
+    fn floats_stay_in_range() {
+        let values = gen_matrix_f64(20, 20, 3);
+        assert!(values.iter().flatten().all(|value| (-1.0..1.0).contains(value)));
+        let mut rng = XorShift64::new(9);
+        assert!((0..1000).all(|_| (0.0..1.0).contains(&rng.next_f64())));
+    }
+
+    #[test]
+    fn sparse_matrices_are_mostly_zeros() {
+        let mostly_zeros = gen_sparse_matrix(120, 80, 90, 5);
+        let non_zeros = mostly_zeros.iter().flatten().filter(|&&value| value != 0).count();
+        assert!(non_zeros < 120 * 80 / 5);
+        assert!(gen_sparse_matrix(10, 10, 0, 5).iter().flatten().all(|&value| value < MAX_ENTRY));
+        assert!(gen_sparse_matrix(10, 10, 100, 5).iter().flatten().all(|&value| value == 0));
+    }
+}
@@ -0,0 +1,253 @@
+// Dot products, row/column sums and whole-matrix statistics, each with a parallel version
+// that splits the work with `map_chunks`: every scoped thread reduces its own part, and the
+// partial results are combined at the end.
+use std::ops::Range;
+use std::thread;
+
+use crate::chunked::chunk_ranges;
+use crate::error::MatMulError;
+use crate::generic::{validate_matrix, Numeric};
+
+// The sum of `a[i] * b[i]`. Slices of different lengths are an error instead of silently
+// ignoring the extra elements, like `zip` would. Two empty slices give 0.
+pub fn dot(a: &[u64], b: &[u64]) -> Result<u64, MatMulError> {
+    check_lengths(a, b)?;
+    Ok(a.iter().zip(b).map(|(a, b)| a * b).sum())
+}
+
+// Split `0..len` into `num_threads` blocks with `chunk_ranges` and run `f` on every
+// non-empty block in its own scoped thread. The results come back in block order, so
+// concatenating them keeps the order of the input.
+fn map_chunks<R: Send>(
+    len: usize,
+    num_threads: usize,
+    f: impl Fn(Range<usize>) -> R + Sync,
+) -> Vec<R> {
+    let f = &f;
+    thread::scope(|scope| {
+        let handles: Vec<_> = chunk_ranges(len, num_threads)
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .map(|range| scope.spawn(move || f(range)))
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    })
+}
+
+pub fn dot_parallel(a: &[u64], b: &[u64], num_threads: usize) -> Result<u64, MatMulError> {
+    check_lengths(a, b)?;
+    let partial_sums = map_chunks(a.len(), num_threads, |range| {
+        a[range.clone()].iter().zip(&b[range]).map(|(a, b)| a * b).sum::<u64>()
+    });
+    Ok(partial_sums.iter().sum())
+}
+
+// The same sum as `dot` for slices that are known to have the same length (which is
+// asserted), written for speed. A single running total makes every addition wait for the
+// one before it; four independent totals, one for each position mod 4, let the processor
+// (and the vectorizer) work on four products at once. The 0 to 3 elements left over after
+// the last group of four are added at the end. For integers the order of the additions
+// doesn't change the result, so this is bit-for-bit equal to `dot`. Any `Numeric` type
+// works, so the matrix kernels can use it too; for floats the different order can change
+// the last bits.
+pub fn dot_unrolled<T: Numeric>(a: &[T], b: &[T]) -> T {
+    assert_eq!(a.len(), b.len(), "dot_unrolled needs slices of the same length");
+    let (a_chunks, b_chunks) = (a.chunks_exact(4), b.chunks_exact(4));
+    let (a_tail, b_tail) = (a_chunks.remainder(), b_chunks.remainder());
+    let tail = a_tail.iter().zip(b_tail).fold(T::default(), |sum, (&a, &b)| sum + a * b);
+    let mut sums = [T::default(); 4];
+    for (a, b) in a_chunks.zip(b_chunks) {
+        sums[0] = sums[0] + a[0] * b[0];
+        sums[1] = sums[1] + a[1] * b[1];
+        sums[2] = sums[2] + a[2] * b[2];
+        sums[3] = sums[3] + a[3] * b[3];
+    }
+    (sums[0] + sums[1]) + (sums[2] + sums[3]) + tail
+}
+
+fn check_lengths(a: &[u64], b: &[u64]) -> Result<(), MatMulError> {
+    if a.len() != b.len() {
+        return Err(MatMulError::LengthMismatch { a: a.len(), b: b.len() });
+    }
+    Ok(())
+}
+
+// The sum of every row, one entry per row. Like the other sums here, a ragged matrix is
+// `MatMulError::Ragged` rather than summed as far as its rows go.
+pub fn row_sums(matrix: &[Vec<u64>]) -> Result<Vec<u64>, MatMulError> {
+    validate_matrix(matrix)?;
+    Ok(row_sums_of(matrix))
+}
+
+fn row_sums_of(rows: &[Vec<u64>]) -> Vec<u64> {
+    rows.iter().map(|row| row.iter().sum()).collect()
+}
+
+// The sum of every column. Rows are added up front to back, so the matrix is read in order.
+pub fn col_sums(matrix: &[Vec<u64>]) -> Result<Vec<u64>, MatMulError> {
+    let (_, cols) = validate_matrix(matrix)?;
+    Ok(col_sums_of(matrix, cols))
+}
+
+// `rows` must all have `cols` entries; a shorter row would only add up to its length.
+fn col_sums_of(rows: &[Vec<u64>], cols: usize) -> Vec<u64> {
+    let mut sums = vec![0; cols];
+    for row in rows {
+        for (sum, value) in sums.iter_mut().zip(row) {
+            *sum += value;
+        }
+    }
+    sums
+}
+
+// Each thread sums a block of rows; the blocks' sums are concatenated in order.
+pub fn row_sums_parallel(
+    matrix: &[Vec<u64>],
+    num_threads: usize,
+) -> Result<Vec<u64>, MatMulError> {
+    validate_matrix(matrix)?;
+    Ok(map_chunks(matrix.len(), num_threads, |range| row_sums_of(&matrix[range])).concat())
+}
+
+// Each thread computes the column sums of a block of rows, and those are added together.
+pub fn col_sums_parallel(
+    matrix: &[Vec<u64>],
+    num_threads: usize,
+) -> Result<Vec<u64>, MatMulError> {
+    let (_, cols) = validate_matrix(matrix)?;
+    let blocks = map_chunks(matrix.len(), num_threads, |range| col_sums_of(&matrix[range], cols));
+    Ok(col_sums_of(&blocks, cols))
+}
+
+// The sum, smallest and largest cell of a matrix. The sum is a `u128`, which can't
+// overflow: even 2^64 cells of `u64::MAX` would only just reach it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatrixStats {
+    pub sum: u128,
+    pub min: u64,
+    pub max: u64,
+}
+
+impl MatrixStats {
+    // The stats of two disjoint parts of a matrix combined into the stats of both.
+    fn merge(self, other: MatrixStats) -> MatrixStats {
+        MatrixStats {
+            sum: self.sum + other.sum,
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+}
+
+// The stats of a block of rows, or `None` if it has no cells.
+fn stats_of(rows: &[Vec<u64>]) -> Option<MatrixStats> {
+    rows.iter()
+        .flatten()
+        .map(|&cell| MatrixStats { sum: u128::from(cell), min: cell, max: cell })
+        .reduce(MatrixStats::merge)
+}
+
+// Each thread computes the stats of a block of rows, and those are merged. A matrix with
+// no cells (no rows, or only empty rows) has no smallest or largest cell, so it gives
+// `None`.
+pub fn matrix_stats(matrix: &[Vec<u64>], num_threads: usize) -> Option<MatrixStats> {
+    map_chunks(matrix.len(), num_threads, |range| stats_of(&matrix[range]))
+        .into_iter()
+        .flatten()
+        .reduce(MatrixStats::merge)
+}
+
+// The wrapping sum of every cell. Two results with different checksums are certainly
+// different, so comparing the checksums of two runs is a cheap first check before
+// comparing whole matrices.
+pub fn checksum(matrix: &[Vec<u64>]) -> u64 {
+    matrix.iter().flatten().fold(0, |sum, &cell| sum.wrapping_add(cell))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::gen_matrix;
+
+    #[test]
+    fn dot_products_agree() -> Result<(), MatMulError> {
+        assert_eq!(dot(&[1, 2, 3], &[4, 5, 6]), Ok(32));
+        assert_eq!(dot(&[], &[]), Ok(0));
+        assert_eq!(dot_parallel(&[], &[], 4), Ok(0));
+        let long_a = gen_matrix(1, 100_000, 31).remove(0);
+        let long_b = gen_matrix(1, 100_000, 32).remove(0);
+        let sequential = dot(&long_a, &long_b)?;
+        for worker_count in [1, 3, 64] {
+            assert_eq!(dot_parallel(&long_a, &long_b, worker_count)?, sequential);
+        }
+        assert_eq!(dot_unrolled(&long_a, &long_b), sequential);
+        // Lengths shorter than a group of four, and ones with a remainder.
+        for len in (0..=8).chain([13, 1000, 4097]) {
+            let a = gen_matrix(1, len, len as u64).remove(0);
+            let b = gen_matrix(1, len, 99).remove(0);
+            assert_eq!(dot_unrolled(&a, &b), dot(&a, &b)?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn lengths_must_match() {
+        assert_eq!(dot(&[1, 2], &[1, 2, 3]), Err(MatMulError::LengthMismatch { a: 2, b: 3 }));
+        let mismatched = MatMulError::LengthMismatch { a: 3, b: 1 };
+        assert_eq!(dot_parallel(&[1, 2, 3], &[1], 2), Err(mismatched));
+    }
+
+    #[test]
+    fn row_and_column_sums() -> Result<(), MatMulError> {
+        let m = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 10]];
+        assert_eq!((row_sums(&m)?, col_sums(&m)?), (vec![6, 15, 25], vec![12, 15, 19]));
+        let big = gen_matrix(500, 200, 21);
+        for worker_count in [1, 2, 1000] {
+            assert_eq!(row_sums_parallel(&big, worker_count), row_sums(&big));
+            assert_eq!(col_sums_parallel(&big, worker_count), col_sums(&big));
+        }
+        assert_eq!(row_sums(&[]), Ok(Vec::new()));
+        assert_eq!(col_sums_parallel(&[], 3), Ok(Vec::new()));
+        Ok(())
+    }
+
+    #[test]
+    fn ragged_matrices_are_not_summed() {
+        let ragged = vec![vec![1, 2, 3], vec![4, 5], vec![6, 7, 8]];
+        let ragged_err = Err(MatMulError::Ragged { row: 1, expected: 3, found: 2 });
+        assert_eq!(row_sums(&ragged), ragged_err);
+        assert_eq!(col_sums(&ragged), ragged_err);
+        assert_eq!(row_sums_parallel(&ragged, 2), ragged_err);
+        assert_eq!(col_sums_parallel(&ragged, 2), ragged_err);
+    }
+
+    #[test]
+    fn stats_match_one_sequential_fold() {
+        for (rows, cols, seed) in [(1, 50, 80), (37, 23, 81), (200, 200, 82)] {
+            let a = gen_matrix(rows, cols, seed);
+            let cells = || a.iter().flatten().copied();
+            let expected = MatrixStats {
+                sum: cells().map(u128::from).sum(),
+                min: cells().fold(u64::MAX, u64::min),
+                max: cells().fold(0, u64::max),
+            };
+            for worker_count in [1, 3, 1000] {
+                assert_eq!(matrix_stats(&a, worker_count), Some(expected));
+            }
+        }
+        let m = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 10]];
+        assert_eq!(matrix_stats(&m, 2), Some(MatrixStats { sum: 46, min: 1, max: 10 }));
+        assert_eq!(matrix_stats(&[vec![7]], 4), Some(MatrixStats { sum: 7, min: 7, max: 7 }));
+        assert_eq!(matrix_stats(&[], 4), None);
+        assert_eq!(matrix_stats(&[vec![], vec![]], 2), None);
+    }
+
+    #[test]
+    fn huge_cells_neither_overflow_the_sum_nor_the_checksum() {
+        let huge = vec![vec![u64::MAX; 3]; 2];
+        let huge_stats = MatrixStats { sum: 6 * u128::from(u64::MAX), min: u64::MAX, max: u64::MAX };
+        assert_eq!(matrix_stats(&huge, 2), Some(huge_stats));
+        assert_eq!(checksum(&huge), u64::MAX.wrapping_mul(6));
+        assert_eq!(checksum(&[vec![u64::MAX, 2], vec![3]]), 4);
+    }
+}
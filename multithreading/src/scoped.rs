@@ -0,0 +1,65 @@
+// Scoped threads: `thread::scope` guarantees that every thread spawned inside it has
+// finished before it returns, so the threads may borrow local data. The inputs are read
+// through plain references and each thread writes straight into its own part of the
+// output; no `Arc`, no clones and no `unsafe`.
+use std::thread;
+
+use crate::error::MatMulError;
+use crate::generic::{multiply_nested_row, validate_matrix, Numeric};
+use crate::matrix::check_dimensions;
+
+// Multiply nested matrices with `num_threads` scoped threads (0 counts as 1), one per block
+// of rows. `chunks_mut` splits the output into non-overlapping blocks of rows, and since no
+// two threads get the same block, the borrow checker accepts handing each of them a `&mut`
+// to theirs. Any `Numeric` element type works; `Send + Sync` is what lets the threads share
+// the inputs and fill in the output.
+pub fn multiply_scoped<T: Numeric + Send + Sync>(
+    matrix_a: &[Vec<T>],
+    matrix_b: &[Vec<T>],
+    num_threads: usize,
+) -> Result<Vec<Vec<T>>, MatMulError> {
+    let (a_shape, b_shape) = (validate_matrix(matrix_a)?, validate_matrix(matrix_b)?);
+    check_dimensions(a_shape, b_shape)?;
+    let mut ans = vec![vec![T::default(); b_shape.1]; a_shape.0];
+    let rows_per_thread = a_shape.0.div_ceil(num_threads.max(1));
+    thread::scope(|scope| {
+        let blocks = matrix_a.chunks(rows_per_thread).zip(ans.chunks_mut(rows_per_thread));
+        for (a_rows, ans_rows) in blocks {
+            scope.spawn(move || {
+                for (a_row, ans_row) in a_rows.iter().zip(ans_rows) {
+                    multiply_nested_row(a_row, matrix_b, ans_row);
+                }
+            });
+        }
+    });
+    Ok(ans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generic::multiply_generic;
+
+    #[test]
+    fn matches_the_sequential_product() -> Result<(), MatMulError> {
+        let a: Vec<Vec<u64>> = (0..30).map(|i| (0..20).map(|j| (i * 7 + j) % 11).collect()).collect();
+        let b: Vec<Vec<u64>> = (0..20).map(|i| (0..10).map(|j| (i + j * 3) % 13).collect()).collect();
+        let expected = multiply_generic(&a, &b)?;
+        for threads in [0, 1, 4, 64] {
+            assert_eq!(multiply_scoped(&a, &b, threads)?, expected);
+        }
+        assert_eq!(multiply_scoped(&[vec![2]], &[vec![3]], 2)?, vec![vec![6]]);
+        assert_eq!(multiply_scoped(&[], &b, 2), Err(MatMulError::EmptyMatrix));
+        Ok(())
+    }
+
+    #[test]
+    fn works_for_any_numeric_type() -> Result<(), MatMulError> {
+        let wide: Vec<Vec<i64>> = vec![vec![i64::from(u32::MAX), 1]];
+        let tall: Vec<Vec<i64>> = vec![vec![2], vec![3]];
+        assert_eq!(multiply_scoped(&wide, &tall, 2)?, vec![vec![8_589_934_593]]);
+        let single: Vec<Vec<f32>> = vec![vec![1.5, 2.0]];
+        assert_eq!(multiply_scoped(&single, &[vec![2.0], vec![0.25]], 2)?, vec![vec![3.5]]);
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+// Sparse matrices in compressed sparse row (CSR) form. Only the non-zero cells are stored:
+// `values` holds them row by row, `col_indices` the column of each, and row `r`'s cells are
+// `values[row_ptr[r]..row_ptr[r + 1]]`. For a 1000x1000 matrix with 1% non-zeros that is
+// 10,000 values instead of a million, and multiplying only touches those.
+use std::thread;
+
+use crate::error::MatMulError;
+use crate::generic::validate_matrix;
+use crate::matrix::check_dimensions;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsrMatrix {
+    values: Vec<u64>,
+    col_indices: Vec<usize>,
+    // One entry per row plus one, so that every row has an end; starts at 0.
+    row_ptr: Vec<usize>,
+    cols: usize,
+}
+
+impl CsrMatrix {
+    // The non-zero cells of `dense`. A ragged matrix is `MatMulError::Ragged`.
+    pub fn try_from_dense(dense: &[Vec<u64>]) -> Result<CsrMatrix, MatMulError> {
+        let (_, cols) = validate_matrix(dense)?;
+        let mut values = Vec::new();
+        let mut col_indices = Vec::new();
+        let mut row_ptr = vec![0];
+        for row in dense {
+            for (col, &value) in row.iter().enumerate() {
+                if value != 0 {
+                    values.push(value);
+                    col_indices.push(col);
+                }
+            }
+            row_ptr.push(values.len());
+        }
+        Ok(CsrMatrix { values, col_indices, row_ptr, cols })
+    }
+
+    // `try_from_dense` for input that is known to be rectangular. Panics on a ragged matrix.
+    pub fn from_dense(dense: &[Vec<u64>]) -> CsrMatrix {
+        CsrMatrix::try_from_dense(dense).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn to_dense(&self) -> Vec<Vec<u64>> {
+        (0..self.rows())
+            .map(|row| {
+                let mut dense_row = vec![0; self.cols];
+                for (col, value) in self.row(row) {
+                    dense_row[col] = value;
+                }
+                dense_row
+            })
+            .collect()
+    }
+
+    pub fn rows(&self) -> usize {
+        self.row_ptr.len() - 1
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    // The number of stored (non-zero) cells.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    // The `(col, value)` pairs of the non-zero cells of `row`, left to right.
+    fn row(&self, row: usize) -> impl Iterator<Item = (usize, u64)> + '_ {
+        let range = self.row_ptr[row]..self.row_ptr[row + 1];
+        self.col_indices[range.clone()].iter().copied().zip(self.values[range].iter().copied())
+    }
+
+    // One row of A x B into `out`, which must start out as zeros: every stored value scales
+    // the matching row of B, like `multiply_row`, except that the zeros are skipped.
+    fn multiply_row_dense(&self, row: usize, matrix_b: &[Vec<u64>], out: &mut [u64]) {
+        for (k, a) in self.row(row) {
+            for (cell, b) in out.iter_mut().zip(&matrix_b[k]) {
+                *cell += a * b;
+            }
+        }
+    }
+}
+
+// Sparse A x dense B, giving a dense result.
+pub fn multiply_csr_dense(
+    matrix_a: &CsrMatrix,
+    matrix_b: &[Vec<u64>],
+) -> Result<Vec<Vec<u64>>, MatMulError> {
+    let b_shape = validate_matrix(matrix_b)?;
+    check_dimensions((matrix_a.rows(), matrix_a.cols()), b_shape)?;
+    let mut ans = vec![vec![0; b_shape.1]; matrix_a.rows()];
+    for (row, out) in ans.iter_mut().enumerate() {
+        matrix_a.multiply_row_dense(row, matrix_b, out);
+    }
+    Ok(ans)
+}
+
+// `multiply_csr_dense` with one scoped thread per block of `num_threads` blocks of rows
+// (0 counts as 1). Rows can hold very different numbers of non-zeros, so the blocks aren't
+// necessarily equal amounts of work.
+pub fn multiply_csr_dense_parallel(
+    matrix_a: &CsrMatrix,
+    matrix_b: &[Vec<u64>],
+    num_threads: usize,
+) -> Result<Vec<Vec<u64>>, MatMulError> {
+    let b_shape = validate_matrix(matrix_b)?;
+    check_dimensions((matrix_a.rows(), matrix_a.cols()), b_shape)?;
+    let mut ans = vec![vec![0; b_shape.1]; matrix_a.rows()];
+    let rows_per_thread = matrix_a.rows().div_ceil(num_threads.max(1));
+    thread::scope(|scope| {
+        for (block, ans_rows) in ans.chunks_mut(rows_per_thread).enumerate() {
+            scope.spawn(move || {
+                let first_row = block * rows_per_thread;
+                for (offset, out) in ans_rows.iter_mut().enumerate() {
+                    matrix_a.multiply_row_dense(first_row + offset, matrix_b, out);
+                }
+            });
+        }
+    });
+    Ok(ans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generic::multiply_generic;
+    use crate::random::{gen_matrix, gen_sparse_matrix};
+
+    #[test]
+    fn keeps_only_the_non_zero_cells() {
+        let mostly_zeros = gen_sparse_matrix(120, 80, 90, 5);
+        let csr = CsrMatrix::from_dense(&mostly_zeros);
+        assert_eq!(csr.to_dense(), mostly_zeros);
+        assert_eq!((csr.rows(), csr.cols()), (120, 80));
+        let non_zeros = mostly_zeros.iter().flatten().filter(|&&value| value != 0).count();
+        assert_eq!(csr.nnz(), non_zeros);
+        let tiny = CsrMatrix::from_dense(&[vec![0, 2, 0], vec![0, 0, 0], vec![1, 0, 3]]);
+        let expected = "CsrMatrix { values: [2, 1, 3], col_indices: [1, 0, 2], row_ptr: [0, 1, 1, 3], cols: 3 }";
+        assert_eq!(format!("{:?}", tiny), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "ragged matrix: row 1 has 2 elements")]
+    fn ragged_input_is_an_error_or_a_panic() {
+        let ragged = vec![vec![1, 2, 3], vec![4, 5]];
+        let ragged_err = MatMulError::Ragged { row: 1, expected: 3, found: 2 };
+        assert_eq!(CsrMatrix::try_from_dense(&ragged), Err(ragged_err));
+        CsrMatrix::from_dense(&ragged);
+    }
+
+    #[test]
+    fn matches_the_dense_product() -> Result<(), MatMulError> {
+        let mostly_zeros = gen_sparse_matrix(120, 80, 90, 5);
+        let csr = CsrMatrix::from_dense(&mostly_zeros);
+        let dense_b = gen_matrix(80, 60, 6);
+        let dense_product = multiply_generic(&mostly_zeros, &dense_b)?;
+        assert_eq!(multiply_csr_dense(&csr, &dense_b)?, dense_product);
+        for worker_count in [0, 1, 3, 500] {
+            assert_eq!(multiply_csr_dense_parallel(&csr, &dense_b, worker_count)?, dense_product);
+        }
+        let mismatched = MatMulError::DimensionMismatch { a_cols: 80, b_rows: 60 };
+        assert_eq!(multiply_csr_dense(&csr, &gen_matrix(60, 5, 1)), Err(mismatched));
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+// How much faster the parallel implementations are than the sequential one. The speedup of
+// an implementation is the baseline's time divided by its own, and its efficiency is the
+// speedup per thread: 1.0 (100%) means every thread did a full share of useful work.
+use std::time::{Duration, Instant};
+
+use crate::error::MatMulError;
+use crate::verify::NestedVariant;
+
+// One implementation's line in a `SpeedupReport`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeedupEntry {
+    pub name: &'static str,
+    pub duration: Duration,
+    // The baseline's duration divided by `duration`.
+    pub speedup: f64,
+    // `speedup` divided by the number of threads in the report.
+    pub efficiency: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeedupReport {
+    pub baseline_name: &'static str,
+    pub baseline: Duration,
+    // The thread count the implementations were given. The ones that choose their own (one
+    // thread per row, say) are still divided by it, so their efficiency is only indicative.
+    pub threads: usize,
+    pub entries: Vec<SpeedupEntry>,
+}
+
+impl SpeedupReport {
+    // A report from durations that have already been measured, in the order given.
+    // `threads` of 0 is treated as 1.
+    pub fn from_durations(
+        (baseline_name, baseline): (&'static str, Duration),
+        threads: usize,
+        durations: &[(&'static str, Duration)],
+    ) -> SpeedupReport {
+        let threads = threads.max(1);
+        let entries = durations
+            .iter()
+            .map(|&(name, duration)| {
+                let speedup = baseline.as_secs_f64() / duration.as_secs_f64();
+                SpeedupEntry { name, duration, speedup, efficiency: speedup / threads as f64 }
+            })
+            .collect();
+        SpeedupReport { baseline_name, baseline, threads, entries }
+    }
+
+    // The report as a table, the baseline first:
+    //
+    //   implementation        time   speedup  efficiency
+    //   sequential          8.00ms     1.00x      100.0%
+    //   chunked             2.00ms     4.00x      100.0%
+    //
+    // The baseline's efficiency is for one thread, which is how it runs.
+    pub fn to_table(&self) -> String {
+        let mut table = format!(
+            "{:<16}{:>12}{:>10}{:>12}\n",
+            "implementation", "time", "speedup", "efficiency"
+        );
+        let baseline = SpeedupEntry {
+            name: self.baseline_name,
+            duration: self.baseline,
+            speedup: 1.0,
+            efficiency: 1.0,
+        };
+        for entry in std::iter::once(&baseline).chain(&self.entries) {
+            table.push_str(&format!(
+                "{:<16}{:>12}{:>10}{:>12}\n",
+                entry.name,
+                format!("{:.2?}", entry.duration),
+                format!("{:.2}x", entry.speedup),
+                format!("{:.1}%", entry.efficiency * 100.0)
+            ));
+        }
+        table
+    }
+}
+
+// Time `baseline` and then every implementation in `variants` once each on A x B, with
+// `threads` threads, and report how they compare. The first error from any of them is
+// returned instead.
+pub fn measure_speedup(
+    matrix_a: &[Vec<u64>],
+    matrix_b: &[Vec<u64>],
+    threads: usize,
+    baseline: NestedVariant,
+    variants: &[NestedVariant],
+) -> Result<SpeedupReport, MatMulError> {
+    let time = |(name, multiply): NestedVariant| -> Result<_, MatMulError> {
+        let start = Instant::now();
+        multiply(matrix_a, matrix_b, threads)?;
+        Ok((name, start.elapsed()))
+    };
+    let baseline = time(baseline)?;
+    let durations = variants.iter().map(|&variant| time(variant)).collect::<Result<Vec<_>, _>>()?;
+    Ok(SpeedupReport::from_durations(baseline, threads, &durations))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::gen_matrix;
+    use crate::verify::NESTED_VARIANTS;
+
+    #[test]
+    fn made_up_durations_give_exact_numbers() {
+        // A quarter of the baseline's time is a speedup of 4, which on 4 threads is 100%
+        // efficient.
+        let ms = Duration::from_millis;
+        let report = SpeedupReport::from_durations(
+            ("sequential", ms(8)),
+            4,
+            &[("chunked", ms(2)), ("mutex", ms(4)), ("slower", ms(16))],
+        );
+        let numbers: Vec<_> =
+            report.entries.iter().map(|e| (e.name, e.speedup, e.efficiency)).collect();
+        assert_eq!(numbers, [("chunked", 4.0, 1.0), ("mutex", 2.0, 0.5), ("slower", 0.5, 0.125)]);
+        let expected_table = "\
+implementation          time   speedup  efficiency
+sequential            8.00ms     1.00x      100.0%
+chunked               2.00ms     4.00x      100.0%
+mutex                 4.00ms     2.00x       50.0%
+slower               16.00ms     0.50x       12.5%
+";
+        assert_eq!(report.to_table(), expected_table);
+    }
+
+    #[test]
+    fn zero_threads_counts_as_one() {
+        let ms = Duration::from_millis;
+        let one = SpeedupEntry { name: "pooled", duration: ms(3), speedup: 1.0, efficiency: 1.0 };
+        let report = SpeedupReport::from_durations(("sequential", ms(3)), 0, &[("pooled", ms(3))]);
+        assert_eq!(report.entries, [one]);
+    }
+
+    #[test]
+    fn measures_every_variant() -> Result<(), MatMulError> {
+        let (a, b) = (gen_matrix(50, 50, 1), gen_matrix(50, 50, 2));
+        let report = measure_speedup(&a, &b, 3, NESTED_VARIANTS[0], &NESTED_VARIANTS[1..4])?;
+        let names: Vec<_> = report.entries.iter().map(|entry| entry.name).collect();
+        assert_eq!(names, ["thread per row", "pooled", "scoped"]);
+        assert!(report.entries.iter().all(|entry| (entry.efficiency - entry.speedup / 3.0).abs() < 1e-12));
+        assert_eq!(measure_speedup(&[], &b, 3, NESTED_VARIANTS[0], &[]), Err(MatMulError::EmptyMatrix));
+        Ok(())
+    }
+}
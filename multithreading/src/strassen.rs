@@ -0,0 +1,139 @@
+// Strassen's algorithm. Splitting each matrix into four quadrants, the ordinary method
+// needs 8 quadrant multiplications; Strassen's combines the quadrants so that 7 are
+// enough, at the cost of extra additions. Applied recursively that is O(n^2.81) instead
+// of O(n^3), but the additions make it slower for small matrices, so below `threshold`
+// the recursion stops and the plain kernel takes over.
+//
+// Some of the intermediate sums are differences that can go below zero. All the
+// arithmetic therefore wraps: the negative values are carried modulo 2^64, and as long as
+// the final result fits in a `u64` (the same condition as for `multiply`), the wrapped
+// values cancel out and every cell is exact.
+use crate::error::MatMulError;
+use crate::generic::validate_matrix;
+use crate::matrix::check_dimensions;
+
+// Inputs of any shape are padded with zeros to a square power-of-two size, multiplied, and
+// the result is cropped back to rows of A x columns of B. A `threshold` of 0 or 1 recurses
+// all the way down to single elements.
+pub fn multiply_strassen(
+    matrix_a: &[Vec<u64>],
+    matrix_b: &[Vec<u64>],
+    threshold: usize,
+) -> Result<Vec<Vec<u64>>, MatMulError> {
+    let (a_shape, b_shape) = (validate_matrix(matrix_a)?, validate_matrix(matrix_b)?);
+    check_dimensions(a_shape, b_shape)?;
+    let (rows, cols) = (a_shape.0, b_shape.1);
+    let size = rows.max(a_shape.1).max(cols).next_power_of_two();
+    let product = strassen(&pad(matrix_a, size), &pad(matrix_b, size), size, threshold.max(1));
+    Ok((0..rows)
+        .map(|row| product[row * size..row * size + cols].to_vec())
+        .collect())
+}
+
+// Copy a nested matrix into the top left corner of a flat `size` x `size` square of zeros.
+fn pad(matrix: &[Vec<u64>], size: usize) -> Vec<u64> {
+    let mut square = vec![0; size * size];
+    for (row, values) in matrix.iter().enumerate() {
+        square[row * size..row * size + values.len()].copy_from_slice(values);
+    }
+    square
+}
+
+// Multiply two flat `size` x `size` matrices, `size` being a power of two.
+fn strassen(a: &[u64], b: &[u64], size: usize, threshold: usize) -> Vec<u64> {
+    if size <= threshold {
+        return multiply_square(a, b, size);
+    }
+    let half = size / 2;
+    let [a11, a12, a21, a22] = quadrants(a, size);
+    let [b11, b12, b21, b22] = quadrants(b, size);
+    let product = |x: &[u64], y: &[u64]| strassen(x, y, half, threshold);
+
+    let m1 = product(&add(&a11, &a22), &add(&b11, &b22));
+    let m2 = product(&add(&a21, &a22), &b11);
+    let m3 = product(&a11, &sub(&b12, &b22));
+    let m4 = product(&a22, &sub(&b21, &b11));
+    let m5 = product(&add(&a11, &a12), &b22);
+    let m6 = product(&sub(&a21, &a11), &add(&b11, &b12));
+    let m7 = product(&sub(&a12, &a22), &add(&b21, &b22));
+
+    let c11 = add(&sub(&add(&m1, &m4), &m5), &m7);
+    let c12 = add(&m3, &m5);
+    let c21 = add(&m2, &m4);
+    let c22 = add(&add(&sub(&m1, &m2), &m3), &m6);
+    join(&[c11, c12, c21, c22], half)
+}
+
+// The plain kernel on a flat square, in the same k, col order as `multiply_row`.
+fn multiply_square(a: &[u64], b: &[u64], size: usize) -> Vec<u64> {
+    let mut ans = vec![0u64; size * size];
+    for (a_row, out) in a.chunks(size).zip(ans.chunks_mut(size)) {
+        for (a, b_row) in a_row.iter().zip(b.chunks(size)) {
+            for (cell, b) in out.iter_mut().zip(b_row) {
+                *cell = cell.wrapping_add(a.wrapping_mul(*b));
+            }
+        }
+    }
+    ans
+}
+
+// Split a flat `size` x `size` square into top left, top right, bottom left and bottom
+// right quadrants.
+fn quadrants(matrix: &[u64], size: usize) -> [Vec<u64>; 4] {
+    let half = size / 2;
+    let quadrant = |row_start: usize, col_start: usize| {
+        let mut out = Vec::with_capacity(half * half);
+        for row in row_start..row_start + half {
+            out.extend_from_slice(&matrix[row * size + col_start..row * size + col_start + half]);
+        }
+        out
+    };
+    [quadrant(0, 0), quadrant(0, half), quadrant(half, 0), quadrant(half, half)]
+}
+
+// The inverse of `quadrants`: four `half` x `half` squares into one twice the size.
+fn join(parts: &[Vec<u64>; 4], half: usize) -> Vec<u64> {
+    let mut out = Vec::with_capacity(4 * half * half);
+    for (top, bottom) in [(&parts[0], &parts[1]), (&parts[2], &parts[3])] {
+        for (left, right) in top.chunks(half).zip(bottom.chunks(half)) {
+            out.extend_from_slice(left);
+            out.extend_from_slice(right);
+        }
+    }
+    out
+}
+
+fn add(x: &[u64], y: &[u64]) -> Vec<u64> {
+    x.iter().zip(y).map(|(x, y)| x.wrapping_add(*y)).collect()
+}
+
+fn sub(x: &[u64], y: &[u64]) -> Vec<u64> {
+    x.iter().zip(y).map(|(x, y)| x.wrapping_sub(*y)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generic::multiply_generic;
+    use crate::transpose::transpose;
+
+    #[test]
+    fn matches_the_plain_product() -> Result<(), MatMulError> {
+        // 64 is already a power of two; 100 is padded to 128 and cropped back. The values
+        // are small so nothing overflows, and low thresholds force several levels.
+        for size in [64, 100] {
+            let a: Vec<Vec<u64>> = (0..size)
+                .map(|i| (0..size).map(|j| ((i * 31 + j * 17) % 10) as u64).collect())
+                .collect();
+            let b = transpose(&a);
+            let plain = multiply_generic(&a, &b)?;
+            for threshold in [0, 8, 32, 128] {
+                assert_eq!(multiply_strassen(&a, &b, threshold)?, plain);
+            }
+        }
+        let a = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        let b: Vec<Vec<u64>> = (0..3).map(|i| (0..5).map(|j| i * 5 + j).collect()).collect();
+        assert_eq!(multiply_strassen(&a, &b, 1)?, multiply_generic(&a, &b)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+// Multiplying against a transposed B. In A x B, the value at (row, col) is the dot
+// product of a row of A with a column of B, and a column of a nested matrix is spread
+// over every row. Transposing B once up front turns each column into a row, so every dot
+// product reads two contiguous slices.
+use crate::error::MatMulError;
+use crate::generic::{nested_shape, validate_matrix};
+use crate::matrix::check_dimensions;
+use crate::reduce::dot_unrolled;
+
+// Swap rows and columns: element (r, c) of the input is (c, r) of the output. An empty
+// matrix stays empty.
+pub fn transpose<T: Copy>(matrix: &[Vec<T>]) -> Vec<Vec<T>> {
+    let (rows, cols) = nested_shape(matrix);
+    (0..cols)
+        .map(|col| (0..rows).map(|row| matrix[row][col]).collect())
+        .collect()
+}
+
+pub fn multiply_transposed(
+    matrix_a: &[Vec<u64>],
+    matrix_b: &[Vec<u64>],
+) -> Result<Vec<Vec<u64>>, MatMulError> {
+    check_dimensions(validate_matrix(matrix_a)?, validate_matrix(matrix_b)?)?;
+    let b_columns = transpose(matrix_b);
+    Ok(matrix_a
+        .iter()
+        .map(|a_row| {
+            b_columns
+                .iter()
+                .map(|b_col| dot_unrolled(a_row, b_col))
+                .collect()
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generic::multiply_generic;
+
+    #[test]
+    fn transposing_twice_gives_back_the_original() {
+        let two_by_three = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        assert_eq!(transpose(&two_by_three), vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+        assert_eq!(transpose(&transpose(&two_by_three)), two_by_three);
+        let square: Vec<Vec<u64>> = (0..10).map(|i| (0..10).map(|j| i * 10 + j).collect()).collect();
+        assert_eq!(transpose(&transpose(&square)), square);
+        assert_eq!(transpose::<u64>(&[]), Vec::<Vec<u64>>::new());
+    }
+
+    #[test]
+    fn matches_the_plain_product() -> Result<(), MatMulError> {
+        let a: Vec<Vec<u64>> = (0..20).map(|i| (0..30).map(|j| (i * 7 + j) % 11).collect()).collect();
+        let b: Vec<Vec<u64>> = (0..30).map(|i| (0..10).map(|j| (i + j * 3) % 13).collect()).collect();
+        assert_eq!(multiply_transposed(&a, &b)?, multiply_generic(&a, &b)?);
+        let two_by_three = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        let gram = multiply_transposed(&two_by_three, &transpose(&two_by_three))?;
+        assert_eq!(gram, vec![vec![14, 32], vec![32, 77]]);
+        let mismatched = MatMulError::DimensionMismatch { a_cols: 3, b_rows: 2 };
+        assert_eq!(multiply_transposed(&two_by_three, &two_by_three), Err(mismatched));
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+// Matrix x vector: each entry of the result is the dot product of one row with the vector.
+// The vector counts as an n x 1 matrix, so the dimension checks are the same as for
+// matrix products.
+use std::thread;
+
+use crate::chunked::chunk_ranges;
+use crate::error::MatMulError;
+use crate::generic::validate_matrix;
+use crate::matrix::check_dimensions;
+
+fn row_times(row: &[u64], vector: &[u64]) -> u64 {
+    row.iter().zip(vector).map(|(a, b)| a * b).sum()
+}
+
+pub fn multiply_vec(matrix: &[Vec<u64>], vector: &[u64]) -> Result<Vec<u64>, MatMulError> {
+    check_dimensions(validate_matrix(matrix)?, (vector.len(), 1))?;
+    Ok(matrix.iter().map(|row| row_times(row, vector)).collect())
+}
+
+// The same split as `multiply_chunked`: one scoped thread per block of rows from
+// `chunk_ranges`, each writing its block of the result.
+pub fn multiply_vec_parallel(
+    matrix: &[Vec<u64>],
+    vector: &[u64],
+    num_threads: usize,
+) -> Result<Vec<u64>, MatMulError> {
+    check_dimensions(validate_matrix(matrix)?, (vector.len(), 1))?;
+    let mut ans = vec![0; matrix.len()];
+    thread::scope(|scope| {
+        let mut rest = ans.as_mut_slice();
+        for range in chunk_ranges(matrix.len(), num_threads) {
+            let (block, tail) = rest.split_at_mut(range.len());
+            rest = tail;
+            if block.is_empty() {
+                continue;
+            }
+            let rows = &matrix[range];
+            scope.spawn(move || {
+                for (cell, row) in block.iter_mut().zip(rows) {
+                    *cell = row_times(row, vector);
+                }
+            });
+        }
+    });
+    Ok(ans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::gen_matrix;
+    use crate::verify::identity_nested;
+
+    #[test]
+    fn multiplies_a_matrix_by_a_vector() -> Result<(), MatMulError> {
+        let v = vec![3, 1, 4];
+        assert_eq!(multiply_vec(&identity_nested(3), &v)?, v);
+        let m = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 10]];
+        assert_eq!(multiply_vec(&m, &v)?, vec![17, 41, 69]);
+        assert_eq!(multiply_vec_parallel(&m, &v, 2)?, vec![17, 41, 69]);
+        Ok(())
+    }
+
+    #[test]
+    fn sequential_and_parallel_agree() -> Result<(), MatMulError> {
+        let (m, v) = (gen_matrix(500, 2000, 21), gen_matrix(1, 2000, 22).remove(0));
+        let sequential = multiply_vec(&m, &v)?;
+        for worker_count in [1, 3, 1000] {
+            assert_eq!(multiply_vec_parallel(&m, &v, worker_count)?, sequential);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn bad_shapes_are_rejected() {
+        let m = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 10]];
+        let mismatched = MatMulError::DimensionMismatch { a_cols: 3, b_rows: 2 };
+        assert_eq!(multiply_vec(&m, &[1, 2]), Err(mismatched.clone()));
+        assert_eq!(multiply_vec_parallel(&m, &[1, 2], 2), Err(mismatched));
+        assert_eq!(multiply_vec(&m, &[]), Err(MatMulError::EmptyMatrix));
+        let ragged = vec![vec![1, 2, 3], vec![4, 5]];
+        let ragged_err = MatMulError::Ragged { row: 1, expected: 3, found: 2 };
+        assert_eq!(multiply_vec(&ragged, &[1, 2, 3]), Err(ragged_err));
+    }
+}
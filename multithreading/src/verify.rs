@@ -0,0 +1,195 @@
+// Shared checks that every multiply implementation gives the same answer as the sequential
+// kernel. The tests run them over a fixed set of shapes, and a new implementation only needs
+// an entry in `NESTED_VARIANTS` to be covered. `main` uses `NESTED_VARIANTS` to pick the
+// implementation named on the command line, so only the checks themselves are test-only.
+use std::sync::Arc;
+
+use crate::blocked::multiply_blocked;
+use crate::channel::multiply_mpsc;
+use crate::checked::multiply_checked;
+use crate::chunked::multiply_chunked;
+use crate::error::MatMulError;
+#[cfg(test)]
+use crate::generic::multiply_generic;
+use crate::generic::validate_matrix;
+use crate::matrix::{multiply_matrix, multiply_matrix_parallel, Matrix};
+use crate::mutex::multiply_mutex;
+use crate::pool::multiply_pooled;
+use crate::progress::multiply_with_progress;
+use crate::random::gen_matrix;
+use crate::scoped::multiply_scoped;
+use crate::strassen::multiply_strassen;
+use crate::transpose::multiply_transposed;
+
+// (rows of A, columns of A = rows of B, columns of B) for the equivalence checks: single
+// cells, a row times a column and the other way round, a rectangular product, and a
+// larger square one.
+#[cfg(test)]
+pub const SHAPES: &[(usize, usize, usize)] = &[
+    (1, 1, 1),
+    (1, 40, 1),
+    (40, 1, 40),
+    (1, 40, 25),
+    (40, 25, 1),
+    (20, 30, 10),
+    (100, 100, 100),
+];
+
+// A multiply implementation on nested matrices, given the number of threads to use (which
+// the sequential ones ignore).
+pub type NestedVariant = (
+    &'static str,
+    fn(&[Vec<u64>], &[Vec<u64>], usize) -> Result<Vec<Vec<u64>>, MatMulError>,
+);
+
+// Runs `multiply` on flat copies of the inputs and converts the result back. The inputs are
+// validated first, since `Matrix::from_nested` would panic on a ragged one. The copies are
+// in `Arc`s for `multiply_matrix_parallel`; the other kernels take them as `&Matrix`.
+fn via_matrix(
+    a: &[Vec<u64>],
+    b: &[Vec<u64>],
+    multiply: impl Fn(&Arc<Matrix>, &Arc<Matrix>) -> Result<Matrix, MatMulError>,
+) -> Result<Vec<Vec<u64>>, MatMulError> {
+    validate_matrix(a)?;
+    validate_matrix(b)?;
+    let (a, b) = (Arc::new(Matrix::from_nested(a.to_vec())), Arc::new(Matrix::from_nested(b.to_vec())));
+    Ok(multiply(&a, &b)?.to_nested())
+}
+
+pub const NESTED_VARIANTS: &[NestedVariant] = &[
+    ("sequential", |a, b, _| via_matrix(a, b, |a, b| multiply_matrix(a, b))),
+    ("thread per row", |a, b, _| via_matrix(a, b, multiply_matrix_parallel)),
+    ("pooled", |a, b, threads| via_matrix(a, b, |a, b| multiply_pooled(a, b, threads))),
+    ("scoped", |a, b, threads| multiply_scoped(a, b, threads)),
+    ("chunked", |a, b, threads| via_matrix(a, b, |a, b| multiply_chunked(a, b, threads))),
+    ("mpsc", |a, b, threads| via_matrix(a, b, |a, b| multiply_mpsc(a, b, threads))),
+    ("mutex", |a, b, threads| via_matrix(a, b, |a, b| multiply_mutex(a, b, threads))),
+    ("progress", |a, b, threads| {
+        via_matrix(a, b, |a, b| multiply_with_progress(a, b, threads, |_, _| {}))
+    }),
+    ("checked", |a, b, _| multiply_checked(a, b)),
+    ("transposed", |a, b, _| multiply_transposed(a, b)),
+    ("blocked", |a, b, _| multiply_blocked(a, b, 16)),
+    ("strassen", |a, b, _| multiply_strassen(a, b, 8)),
+];
+
+// Random inputs of the given shape, the same for the same seed.
+pub fn random_pair(shape: (usize, usize, usize), seed: u64) -> (Vec<Vec<u64>>, Vec<Vec<u64>>) {
+    let (rows, inner, cols) = shape;
+    (gen_matrix(rows, inner, seed), gen_matrix(inner, cols, seed.wrapping_add(1)))
+}
+
+#[cfg(test)]
+pub fn zero_matrix(rows: usize, cols: usize) -> Vec<Vec<u64>> {
+    vec![vec![0; cols]; rows]
+}
+
+#[cfg(test)]
+pub fn identity_nested(n: usize) -> Vec<Vec<u64>> {
+    Matrix::identity(n).to_nested()
+}
+
+// Panic unless `actual` is `Ok(expected)`, naming the implementation and the first cell
+// that differs, which says a lot more than two 100x100 matrices printed side by side.
+#[cfg(test)]
+pub fn assert_same_result(
+    name: &str,
+    actual: Result<Vec<Vec<u64>>, MatMulError>,
+    expected: &[Vec<u64>],
+) {
+    let actual = actual.unwrap_or_else(|err| panic!("{} failed: {}", name, err));
+    assert_eq!(actual.len(), expected.len(), "{} returned the wrong number of rows", name);
+    for (row, (actual_row, expected_row)) in actual.iter().zip(expected).enumerate() {
+        let message = format!("{}: row {} has the wrong length", name, row);
+        assert_eq!(actual_row.len(), expected_row.len(), "{}", message);
+        if let Some(col) = (0..actual_row.len()).find(|&col| actual_row[col] != expected_row[col]) {
+            panic!(
+                "{}: cell ({}, {}) is {}, expected {}",
+                name, row, col, actual_row[col], expected_row[col]
+            );
+        }
+    }
+}
+
+// Check every variant against `multiply_generic` on `a` x `b`, and return the expected
+// product so the caller can check more implementations against it.
+#[cfg(test)]
+pub fn assert_variants_agree(
+    a: &[Vec<u64>],
+    b: &[Vec<u64>],
+    threads: usize,
+) -> Result<Vec<Vec<u64>>, MatMulError> {
+    let expected = multiply_generic(a, b)?;
+    for &(name, multiply) in NESTED_VARIANTS {
+        assert_same_result(name, multiply(a, b, threads), &expected);
+    }
+    Ok(expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::Layout;
+    use crate::transpose::transpose;
+
+    #[test]
+    fn every_variant_agrees_on_every_shape() -> Result<(), MatMulError> {
+        for (seed, &shape) in SHAPES.iter().enumerate() {
+            let (a, b) = random_pair(shape, seed as u64 + 100);
+            for threads in [1, 3, 8] {
+                assert_variants_agree(&a, &b, threads)?;
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn known_answers() -> Result<(), MatMulError> {
+        // The identity changes nothing, zeros give zeros, and a product worked out by hand.
+        let (a, _) = random_pair((30, 20, 1), 7);
+        assert_eq!(assert_variants_agree(&identity_nested(30), &a, 4)?, a);
+        assert_eq!(assert_variants_agree(&a, &identity_nested(20), 4)?, a);
+        assert_eq!(assert_variants_agree(&a, &zero_matrix(20, 5), 4)?, zero_matrix(30, 5));
+        let left = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        let right = vec![vec![7, 8], vec![9, 10], vec![11, 12]];
+        assert_eq!(assert_variants_agree(&left, &right, 4)?, vec![vec![58, 64], vec![139, 154]]);
+        Ok(())
+    }
+
+    #[test]
+    fn column_major_inputs_agree() -> Result<(), MatMulError> {
+        let (a, b) = random_pair((20, 30, 10), 11);
+        let expected = multiply_generic(&a, &b)?;
+        let a = Matrix::from_nested(a).to_layout(Layout::ColMajor);
+        let b = Matrix::from_nested(b).to_layout(Layout::ColMajor);
+        let flat_results = [
+            ("pooled", multiply_pooled(&a, &b, 3)),
+            ("chunked", multiply_chunked(&a, &b, 3)),
+            ("mpsc", multiply_mpsc(&a, &b, 3)),
+            ("mutex", multiply_mutex(&a, &b, 3)),
+            ("progress", multiply_with_progress(&a, &b, 3, |_, _| {})),
+        ];
+        for (name, result) in flat_results {
+            assert_same_result(name, result.map(|product| product.to_nested()), &expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn every_variant_rejects_ragged_input() {
+        // The first row whose length differs from row 0's is named, whether it is A or B.
+        let ragged = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8], vec![9, 10, 11]];
+        let ragged_err = MatMulError::Ragged { row: 2, expected: 3, found: 2 };
+        for &(name, multiply) in NESTED_VARIANTS {
+            assert_eq!(multiply(&ragged, &identity_nested(3), 4), Err(ragged_err.clone()), "{}", name);
+            let ragged_b = multiply(&transpose(&ragged[..2]), &ragged, 4);
+            assert_eq!(ragged_b, Err(ragged_err.clone()), "{}", name);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "wrong: cell (1, 0) is 4, expected 3")]
+    fn names_the_first_cell_that_differs() {
+        assert_same_result("wrong", Ok(vec![vec![1, 2], vec![4, 4]]), &[vec![1, 2], vec![3, 4]]);
+    }
+}